@@ -13,9 +13,10 @@ pub struct Config {
     pub syntax: SyntaxConfig,
     pub completion: CompletionConfig,
     pub inlay_hints: InlayHintConfig,
+    pub citation: CitationConfig,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BuildConfig {
     pub program: String,
     pub args: Vec<String>,
@@ -82,6 +83,16 @@ pub struct InlayHintConfig {
     pub label_references: bool,
 }
 
+/// The CSL style that is used to render citations on hover and in completion.
+///
+/// Relative paths are resolved against the directory of the project.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CitationConfig {
+    pub style: Option<String>,
+    pub locale: Option<String>,
+    pub locales_dir: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct CompletionConfig {
     pub matcher: MatchingAlgo,
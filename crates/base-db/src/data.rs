@@ -3,6 +3,10 @@ pub struct BibtexEntryType<'a> {
     pub name: &'a str,
     pub category: BibtexEntryTypeCategory,
     pub documentation: Option<&'a str>,
+    /// The fields that need to be present. Each group lists alternatives of which one is sufficient.
+    /// Fields that biblatex considers omissible are not required.
+    pub required_fields: &'a [&'a [&'a str]],
+    pub optional_fields: &'a [&'a str],
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
@@ -45,269 +49,866 @@ pub static BIBTEX_ENTRY_TYPES: &[BibtexEntryType<'static>] = &[
         name: "@preamble",
         category: BibtexEntryTypeCategory::Misc,
         documentation: None,
+        required_fields: &[],
+        optional_fields: &[],
     },
     BibtexEntryType {
         name: "@string",
         category: BibtexEntryTypeCategory::String,
         documentation: None,
+        required_fields: &[],
+        optional_fields: &[],
     },
     BibtexEntryType {
         name: "@comment",
         category: BibtexEntryTypeCategory::Misc,
         documentation: None,
+        required_fields: &[],
+        optional_fields: &[],
     },
     BibtexEntryType {
         name: "@article",
         category: BibtexEntryTypeCategory::Article,
         documentation: Some("An article in a journal, magazine, newspaper, or other periodical which forms a \n self-contained unit with its own title. The title of the periodical is given in the \n journaltitle field. If the issue has its own title in addition to the main title of \n the periodical, it goes in the issuetitle field. Note that editor and related \n fields refer to the journal while translator and related fields refer to the article.\n\nRequired fields: `author`, `title`, `journaltitle`, `year/date`"),
+        required_fields: &[&["author"], &["title"], &["journaltitle", "journal"], &["year", "date"]],
+        optional_fields: ARTICLE_FIELDS,
     },
     BibtexEntryType {
         name: "@book",
         category: BibtexEntryTypeCategory::Book,
         documentation: Some("A single-volume book with one or more authors where the authors share credit for\n the work as a whole. This entry type also covers the function of the `@inbook` type\n of traditional BibTeX.\n\nRequired fields: `author`, `title`, `year/date`"),
+        required_fields: &[&["author"], &["title"], &["year", "date"]],
+        optional_fields: BOOK_FIELDS,
     },
     BibtexEntryType {
         name: "@mvbook",
         category: BibtexEntryTypeCategory::Book,
         documentation: Some("A multi-volume `@book`. For backwards compatibility, multi-volume books are also\n supported by the entry type `@book`. However, it is advisable to make use of the\n dedicated entry type `@mvbook`.\n\nRequired fields: `author`, `title`, `year/date`"),
+        required_fields: &[&["author"], &["title"], &["year", "date"]],
+        optional_fields: BOOK_FIELDS,
     },
     BibtexEntryType {
         name: "@inbook",
         category: BibtexEntryTypeCategory::Part,
         documentation: Some("A part of a book which forms a self-contained unit with its own title. Note that the\n profile of this entry type is different from standard BibTeX.\n\nRequired fields: `author`, `title`, `booktitle`, `year/date`"),
+        required_fields: &[&["author"], &["title"], &["booktitle"], &["year", "date"]],
+        optional_fields: IN_BOOK_FIELDS,
     },
     BibtexEntryType {
         name: "@bookinbook",
         category: BibtexEntryTypeCategory::Part,
         documentation: Some("This type is similar to `@inbook` but intended for works originally published as a\n stand-alone book. A typical example are books reprinted in the collected works of\n an author."),
+        required_fields: &[&["author"], &["title"], &["booktitle"], &["year", "date"]],
+        optional_fields: IN_BOOK_FIELDS,
     },
     BibtexEntryType {
         name: "@suppbook",
         category: BibtexEntryTypeCategory::Book,
         documentation: Some("Supplemental material in a `@book`. This type is closely related to the `@inbook`\n entry type. While `@inbook` is primarily intended for a part of a book with its own\n title (e. g., a single essay in a collection of essays by the same author), this type is\n provided for elements such as prefaces, introductions, forewords, afterwords, etc.\n which often have a generic title only. Style guides may require such items to be\n formatted differently from other `@inbook` items. The standard styles will treat this\n entry type as an alias for `@inbook`."),
+        required_fields: &[&["author"], &["title"], &["booktitle"], &["year", "date"]],
+        optional_fields: IN_BOOK_FIELDS,
     },
     BibtexEntryType {
         name: "@booklet",
         category: BibtexEntryTypeCategory::Book,
         documentation: Some("A book-like work without a formal publisher or sponsoring institution. Use the field\n howpublished to supply publishing information in free format, if applicable. The\n field type may be useful as well.\n\nRequired fields: `author/editor`, `title`, `year/date`"),
+        required_fields: &[&["author", "editor"], &["title"], &["year", "date"]],
+        optional_fields: BOOKLET_FIELDS,
     },
     BibtexEntryType {
         name: "@collection",
         category: BibtexEntryTypeCategory::Collection,
         documentation: Some("A single-volume collection with multiple, self-contained contributions by distinct\n authors which have their own title. The work as a whole has no overall author but it\n will usually have an editor.\n\nRequired fields: `editor`, `title`, `year/date`"),
+        required_fields: &[&["editor"], &["title"], &["year", "date"]],
+        optional_fields: COLLECTION_FIELDS,
     },
     BibtexEntryType {
         name: "@mvcollection",
         category: BibtexEntryTypeCategory::Collection,
         documentation: Some("A multi-volume `@collection`. For backwards compatibility, multi-volume collections\n are also supported by the entry type `@collection`. However, it is advisable\n to make use of the dedicated entry type `@mvcollection`.\n\nRequired fields: `editor`, `title`, `year/date`"),
+        required_fields: &[&["editor"], &["title"], &["year", "date"]],
+        optional_fields: COLLECTION_FIELDS,
     },
     BibtexEntryType {
         name: "@incollection",
         category: BibtexEntryTypeCategory::Part,
         documentation: Some("A contribution to a collection which forms a self-contained unit with a distinct author\n and title. The `author` refers to the `title`, the `editor` to the `booktitle`, i. e.,\n the title of the collection.\n\nRequired fields: `author`, `title`, `booktitle`, `year/date`"),
+        required_fields: &[&["author"], &["title"], &["booktitle"], &["year", "date"]],
+        optional_fields: IN_COLLECTION_FIELDS,
     },
     BibtexEntryType {
         name: "@suppcollection",
         category: BibtexEntryTypeCategory::Collection,
         documentation: Some("Supplemental material in a `@collection`. This type is similar to `@suppbook` but\n related to the `@collection` entry type. The standard styles will treat this entry\n type as an alias for `@incollection`."),
+        required_fields: &[&["author"], &["title"], &["booktitle"], &["year", "date"]],
+        optional_fields: IN_COLLECTION_FIELDS,
     },
     BibtexEntryType {
         name: "@manual",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Technical or other documentation, not necessarily in printed form. The author or\n editor is omissible.\n\nRequired fields: `author/editor`, `title`, `year/date`"),
+        required_fields: &[&["title"], &["year", "date"]],
+        optional_fields: MANUAL_FIELDS,
     },
     BibtexEntryType {
         name: "@misc",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("A fallback type for entries which do not fit into any other category. Use the field\n howpublished to supply publishing information in free format, if applicable. The\n field type may be useful as well. author, editor, and year are omissible.\n\nRequired fields: `author/editor`, `title`, `year/date`"),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@online",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("An online resource. `author`, `editor`, and `year` are omissible.\n This entry type is intended for sources such as web sites which are intrinsically\n online resources. Note that all entry types support the url field. For example, when\n adding an article from an online journal, it may be preferable to use the `@article`\n type and its url field.\n\nRequired fields: `author/editor`, `title`, `year/date`, `url`"),
+        required_fields: &[&["title"], &["url", "doi", "eprint"]],
+        optional_fields: ONLINE_FIELDS,
     },
     BibtexEntryType {
         name: "@patent",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("A patent or patent request. The number or record token is given in the number\n field. Use the type field to specify the type and the location field to indicate the\n scope of the patent, if different from the scope implied by the type. Note that the\n location field is treated as a key list with this entry type.\n\nRequired fields: `author`, `title`, `number`, `year/date`"),
+        required_fields: &[&["author"], &["title"], &["number"], &["year", "date"]],
+        optional_fields: PATENT_FIELDS,
     },
     BibtexEntryType {
         name: "@periodical",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("An complete issue of a periodical, such as a special issue of a journal. The title of\n the periodical is given in the title field. If the issue has its own title in addition to\n the main title of the periodical, it goes in the issuetitle field. The editor is\n omissible.\n\nRequired fields: `editor`, `title`, `year/date`"),
+        required_fields: &[&["title"], &["year", "date"]],
+        optional_fields: PERIODICAL_FIELDS,
     },
     BibtexEntryType {
         name: "@suppperiodical",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Supplemental material in a `@periodical`. This type is similar to `@suppbook`\n but related to the `@periodical` entry type. The role of this entry type may be\n more obvious if you bear in mind that the `@article` type could also be called\n `@inperiodical`. This type may be useful when referring to items such as regular\n columns, obituaries, letters to the editor, etc. which only have a generic title. Style\n guides may require such items to be formatted differently from articles in the strict\n sense of the word. The standard styles will treat this entry type as an alias for\n `@article`."),
+        required_fields: &[&["author"], &["title"], &["journaltitle", "journal"], &["year", "date"]],
+        optional_fields: ARTICLE_FIELDS,
     },
     BibtexEntryType {
         name: "@proceedings",
         category: BibtexEntryTypeCategory::Book,
         documentation: Some("A single-volume conference proceedings. This type is very similar to `@collection`.\n It supports an optional organization field which holds the sponsoring institution.\n The editor is omissible.\n\nRequired fields: `title`, `year/date`"),
+        required_fields: &[&["title"], &["year", "date"]],
+        optional_fields: PROCEEDINGS_FIELDS,
     },
     BibtexEntryType {
         name: "@mvproceedings",
         category: BibtexEntryTypeCategory::Book,
         documentation: Some("A multi-volume `@proceedings` entry. For backwards compatibility, multi-volume\n proceedings are also supported by the entry type `@proceedings`. However, it is\n advisable to make use of the dedicated entry type `@mvproceedings`\n\nRequired fields: `title`, `year/date`"),
+        required_fields: &[&["title"], &["year", "date"]],
+        optional_fields: PROCEEDINGS_FIELDS,
     },
     BibtexEntryType {
         name: "@inproceedings",
         category: BibtexEntryTypeCategory::Part,
         documentation: Some("An article in a conference proceedings. This type is similar to `@incollection`. It\n supports an optional `organization` field.\n\nRequired fields: `author`, `title`, `booktitle`, `year/date`"),
+        required_fields: &[&["author"], &["title"], &["booktitle"], &["year", "date"]],
+        optional_fields: IN_PROCEEDINGS_FIELDS,
     },
     BibtexEntryType {
         name: "@reference",
         category: BibtexEntryTypeCategory::Collection,
         documentation: Some("A single-volume work of reference such as an encyclopedia or a dictionary. This is a\n more specific variant of the generic `@collection` entry type. The standard styles\n will treat this entry type as an alias for `@collection`."),
+        required_fields: &[&["editor"], &["title"], &["year", "date"]],
+        optional_fields: COLLECTION_FIELDS,
     },
     BibtexEntryType {
         name: "@mvreference",
         category: BibtexEntryTypeCategory::Collection,
         documentation: Some("A multi-volume `@reference` entry. The standard styles will treat this entry type\n as an alias for `@mvcollection`. For backwards compatibility, multi-volume references\n are also supported by the entry type `@reference`. However, it is advisable\n to make use of the dedicated entry type `@mvreference`."),
+        required_fields: &[&["editor"], &["title"], &["year", "date"]],
+        optional_fields: COLLECTION_FIELDS,
     },
     BibtexEntryType {
         name: "@inreference",
         category: BibtexEntryTypeCategory::Part,
         documentation: Some("An article in a work of reference. This is a more specific variant of the generic\n `@incollection` entry type. The standard styles will treat this entry type as an\n alias for `@incollection`."),
+        required_fields: &[&["author"], &["title"], &["booktitle"], &["year", "date"]],
+        optional_fields: IN_COLLECTION_FIELDS,
     },
     BibtexEntryType {
         name: "@report",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("A technical report, research report, or white paper published by a university or some\n other institution. Use the `type` field to specify the type of report. The sponsoring\n institution goes in the `institution` field.\n\nRequired fields: `author`, `title`, `type`, `institution`, `year/date`"),
+        required_fields: &[&["author"], &["title"], &["type"], &["institution", "school"], &["year", "date"]],
+        optional_fields: REPORT_FIELDS,
     },
     BibtexEntryType {
         name: "@set",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("An entry set. This entry type is special."),
+        required_fields: &[&["entryset"]],
+        optional_fields: &[],
     },
     BibtexEntryType {
         name: "@thesis",
         category: BibtexEntryTypeCategory::Thesis,
         documentation: Some("A thesis written for an educational institution to satisfy the requirements for a degree.\n Use the `type` field to specify the type of thesis.\n\nRequired fields: `author`, `title`, `type`, `institution`, `year/date`"),
+        required_fields: &[&["author"], &["title"], &["type"], &["institution", "school"], &["year", "date"]],
+        optional_fields: THESIS_FIELDS,
     },
     BibtexEntryType {
         name: "@unpublished",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("A work with an author and a title which has not been formally published, such as\n a manuscript or the script of a talk. Use the fields `howpublished` and `note` to\n supply additional information in free format, if applicable.\n\nRequired fields: `author`, `title`, `year/date`"),
+        required_fields: &[&["author"], &["title"], &["year", "date"]],
+        optional_fields: UNPUBLISHED_FIELDS,
     },
     BibtexEntryType {
         name: "@xdata",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("This entry type is special. `@xdata` entries hold data which may be inherited by other\n entries using the `xdata` field. Entries of this type only serve as data containers;\n they may not be cited or added to the bibliography."),
+        required_fields: &[],
+        optional_fields: &[],
     },
     BibtexEntryType {
         name: "@conference",
         category: BibtexEntryTypeCategory::Part,
         documentation: Some("A legacy alias for `@inproceedings`."),
+        required_fields: &[&["author"], &["title"], &["booktitle"], &["year", "date"]],
+        optional_fields: IN_PROCEEDINGS_FIELDS,
     },
     BibtexEntryType {
         name: "@electronic",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("An alias for `@online`."),
+        required_fields: &[&["title"], &["url", "doi", "eprint"]],
+        optional_fields: ONLINE_FIELDS,
     },
     BibtexEntryType {
         name: "@mastersthesis",
         category: BibtexEntryTypeCategory::Thesis,
         documentation: Some("Similar to `@thesis` except that the `type` field is optional and defaults to the\n localised term ‘Master’s thesis’. You may still use the `type` field to override that."),
+        required_fields: &[&["author"], &["title"], &["institution", "school"], &["year", "date"]],
+        optional_fields: THESIS_FIELDS,
     },
     BibtexEntryType {
         name: "@phdthesis",
         category: BibtexEntryTypeCategory::Thesis,
         documentation: Some("Similar to `@thesis` except that the `type` field is optional and defaults to the\n localised term ‘PhD thesis’. You may still use the `type` field to override that."),
+        required_fields: &[&["author"], &["title"], &["institution", "school"], &["year", "date"]],
+        optional_fields: THESIS_FIELDS,
     },
     BibtexEntryType {
         name: "@techreport",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Similar to `@report` except that the `type` field is optional and defaults to the\n localised term ‘technical report’. You may still use the `type` field to override that."),
+        required_fields: &[&["author"], &["title"], &["institution", "school"], &["year", "date"]],
+        optional_fields: REPORT_FIELDS,
     },
     BibtexEntryType {
         name: "@www",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("An alias for `@online`, provided for `jurabib` compatibility."),
+        required_fields: &[&["title"], &["url", "doi", "eprint"]],
+        optional_fields: ONLINE_FIELDS,
     },
     BibtexEntryType {
         name: "@artwork",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Works of the visual arts such as paintings, sculpture, and installations."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@audio",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Audio recordings, typically on audio cd, dvd, audio cassette, or similar media. See\n also `@music`."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@bibnote",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("This special entry type is not meant to be used in the `bib` file like other types. It is\n provided for third-party packages like `notes2bib` which merge notes into the bibliography.\n The notes should go into the `note` field. Be advised that the `@bibnote`\n type is not related to the `defbibnote` command in any way. `defbibnote`\n is for adding comments at the beginning or the end of the bibliography, whereas\n the `@bibnote` type is meant for packages which render endnotes as bibliography\n entries."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@commentary",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Commentaries which have a status different from regular books, such as legal commentaries."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@image",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Images, pictures, photographs, and similar media."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@jurisdiction",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Court decisions, court recordings, and similar things."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@legislation",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Laws, bills, legislative proposals, and similar things."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@legal",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Legal documents such as treaties."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@letter",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Personal correspondence such as letters, emails, memoranda, etc."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@movie",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Motion pictures. See also `@video`."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@music",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Musical recordings. This is a more specific variant of `@audio`."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@performance",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Musical and theatrical performances as well as other works of the performing arts.\n This type refers to the event as opposed to a recording, a score, or a printed play."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@review",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Reviews of some other work. This is a more specific variant of the `@article` type.\n The standard styles will treat this entry type as an alias for `@article`."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@software",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Computer software."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@standard",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("National and international standards issued by a standards body such as the International\n Organization for Standardization."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     },
     BibtexEntryType {
         name: "@video",
         category: BibtexEntryTypeCategory::Misc,
         documentation: Some("Audiovisual recordings, typically on dvd, vhs cassette, or similar media. See also\n `@movie`."),
+        required_fields: &[&["title"]],
+        optional_fields: MISC_FIELDS,
     }
 ];
 
+const ARTICLE_FIELDS: &[&str] = &[
+    "translator",
+    "annotator",
+    "commentator",
+    "subtitle",
+    "titleaddon",
+    "editor",
+    "editora",
+    "editorb",
+    "editorc",
+    "journalsubtitle",
+    "issuetitle",
+    "issuesubtitle",
+    "language",
+    "origlanguage",
+    "series",
+    "volume",
+    "number",
+    "eid",
+    "issue",
+    "month",
+    "pages",
+    "version",
+    "note",
+    "issn",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
+const BOOK_FIELDS: &[&str] = &[
+    "editor",
+    "editora",
+    "editorb",
+    "editorc",
+    "translator",
+    "annotator",
+    "commentator",
+    "introduction",
+    "foreword",
+    "afterword",
+    "subtitle",
+    "titleaddon",
+    "maintitle",
+    "mainsubtitle",
+    "maintitleaddon",
+    "language",
+    "origlanguage",
+    "volume",
+    "part",
+    "edition",
+    "volumes",
+    "series",
+    "number",
+    "note",
+    "publisher",
+    "location",
+    "isbn",
+    "eid",
+    "chapter",
+    "pages",
+    "pagetotal",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
+const IN_BOOK_FIELDS: &[&str] = &[
+    "bookauthor",
+    "editor",
+    "editora",
+    "editorb",
+    "editorc",
+    "translator",
+    "annotator",
+    "commentator",
+    "introduction",
+    "foreword",
+    "afterword",
+    "subtitle",
+    "titleaddon",
+    "maintitle",
+    "mainsubtitle",
+    "maintitleaddon",
+    "booksubtitle",
+    "booktitleaddon",
+    "language",
+    "origlanguage",
+    "volume",
+    "part",
+    "edition",
+    "volumes",
+    "series",
+    "number",
+    "note",
+    "publisher",
+    "location",
+    "isbn",
+    "eid",
+    "chapter",
+    "pages",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
+const BOOKLET_FIELDS: &[&str] = &[
+    "subtitle",
+    "titleaddon",
+    "language",
+    "howpublished",
+    "type",
+    "note",
+    "location",
+    "eid",
+    "chapter",
+    "pages",
+    "pagetotal",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
+const COLLECTION_FIELDS: &[&str] = &[
+    "editora",
+    "editorb",
+    "editorc",
+    "translator",
+    "annotator",
+    "commentator",
+    "introduction",
+    "foreword",
+    "afterword",
+    "subtitle",
+    "titleaddon",
+    "maintitle",
+    "mainsubtitle",
+    "maintitleaddon",
+    "language",
+    "origlanguage",
+    "volume",
+    "part",
+    "edition",
+    "volumes",
+    "series",
+    "number",
+    "note",
+    "publisher",
+    "location",
+    "isbn",
+    "eid",
+    "chapter",
+    "pages",
+    "pagetotal",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
+const IN_COLLECTION_FIELDS: &[&str] = &[
+    "editor",
+    "editora",
+    "editorb",
+    "editorc",
+    "translator",
+    "annotator",
+    "commentator",
+    "introduction",
+    "foreword",
+    "afterword",
+    "subtitle",
+    "titleaddon",
+    "maintitle",
+    "mainsubtitle",
+    "maintitleaddon",
+    "booksubtitle",
+    "booktitleaddon",
+    "language",
+    "origlanguage",
+    "volume",
+    "part",
+    "edition",
+    "volumes",
+    "series",
+    "number",
+    "note",
+    "publisher",
+    "location",
+    "isbn",
+    "eid",
+    "chapter",
+    "pages",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
+const MANUAL_FIELDS: &[&str] = &[
+    "author",
+    "editor",
+    "subtitle",
+    "titleaddon",
+    "language",
+    "edition",
+    "type",
+    "series",
+    "number",
+    "version",
+    "note",
+    "organization",
+    "publisher",
+    "location",
+    "isbn",
+    "eid",
+    "chapter",
+    "pages",
+    "pagetotal",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
+const MISC_FIELDS: &[&str] = &[
+    "author",
+    "editor",
+    "year",
+    "date",
+    "subtitle",
+    "titleaddon",
+    "language",
+    "howpublished",
+    "type",
+    "version",
+    "note",
+    "organization",
+    "location",
+    "month",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
+const ONLINE_FIELDS: &[&str] = &[
+    "author",
+    "editor",
+    "year",
+    "date",
+    "subtitle",
+    "titleaddon",
+    "language",
+    "version",
+    "note",
+    "organization",
+    "month",
+    "addendum",
+    "pubstate",
+    "eprintclass",
+    "eprinttype",
+    "urldate",
+];
+
+const PATENT_FIELDS: &[&str] = &[
+    "holder",
+    "subtitle",
+    "titleaddon",
+    "type",
+    "version",
+    "location",
+    "note",
+    "month",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
+const PERIODICAL_FIELDS: &[&str] = &[
+    "editor",
+    "editora",
+    "editorb",
+    "editorc",
+    "subtitle",
+    "issuetitle",
+    "issuesubtitle",
+    "language",
+    "series",
+    "volume",
+    "number",
+    "issue",
+    "month",
+    "note",
+    "issn",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
+const PROCEEDINGS_FIELDS: &[&str] = &[
+    "editor",
+    "subtitle",
+    "titleaddon",
+    "maintitle",
+    "mainsubtitle",
+    "maintitleaddon",
+    "eventtitle",
+    "eventtitleaddon",
+    "eventdate",
+    "venue",
+    "language",
+    "volume",
+    "part",
+    "volumes",
+    "series",
+    "number",
+    "note",
+    "organization",
+    "publisher",
+    "location",
+    "month",
+    "isbn",
+    "eid",
+    "chapter",
+    "pages",
+    "pagetotal",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
+const IN_PROCEEDINGS_FIELDS: &[&str] = &[
+    "editor",
+    "subtitle",
+    "titleaddon",
+    "maintitle",
+    "mainsubtitle",
+    "maintitleaddon",
+    "booksubtitle",
+    "booktitleaddon",
+    "eventtitle",
+    "eventtitleaddon",
+    "eventdate",
+    "venue",
+    "language",
+    "volume",
+    "part",
+    "volumes",
+    "series",
+    "number",
+    "note",
+    "organization",
+    "publisher",
+    "location",
+    "month",
+    "isbn",
+    "eid",
+    "chapter",
+    "pages",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
+const REPORT_FIELDS: &[&str] = &[
+    "subtitle",
+    "titleaddon",
+    "language",
+    "number",
+    "version",
+    "note",
+    "location",
+    "month",
+    "isrn",
+    "eid",
+    "chapter",
+    "pages",
+    "pagetotal",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
+const THESIS_FIELDS: &[&str] = &[
+    "subtitle",
+    "titleaddon",
+    "language",
+    "note",
+    "location",
+    "month",
+    "isbn",
+    "eid",
+    "chapter",
+    "pages",
+    "pagetotal",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
+const UNPUBLISHED_FIELDS: &[&str] = &[
+    "subtitle",
+    "titleaddon",
+    "type",
+    "eventtitle",
+    "eventtitleaddon",
+    "eventdate",
+    "venue",
+    "language",
+    "howpublished",
+    "note",
+    "location",
+    "isbn",
+    "month",
+    "addendum",
+    "pubstate",
+    "doi",
+    "eprint",
+    "eprintclass",
+    "eprinttype",
+    "url",
+    "urldate",
+];
+
 pub static BIBTEX_FIELD_TYPES: &[BibtexFieldType<'static>] = &[
     BibtexFieldType {
         name: "abstract",
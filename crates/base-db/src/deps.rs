@@ -1,11 +1,13 @@
 mod discover;
 mod graph;
+mod graphics;
 mod project;
 mod root;
 
 pub use self::{
     discover::{discover, watch},
-    graph::{DirectLinkData, Edge, EdgeData, Graph, HOME_DIR},
-    project::{parents, Project},
-    root::ProjectRoot,
+    graph::{DirectLinkData, Edge, EdgeData, Graph, UnresolvedLink, HOME_DIR},
+    graphics::{resolve_graphics, GraphicsDirs},
+    project::{magic_children, magic_root, parents, root, Project},
+    root::{BuildTarget, ProjectRoot},
 };
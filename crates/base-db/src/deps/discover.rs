@@ -6,7 +6,7 @@ use rustc_hash::FxHashSet;
 
 use crate::Workspace;
 
-use super::ProjectRoot;
+use super::{GraphicsDirs, ProjectRoot};
 
 pub fn watch(
     workspace: &mut Workspace,
@@ -29,6 +29,27 @@ pub fn watch(
             }
         }
     }
+
+    // Images are not part of the workspace but the diagnostics need to know when they appear.
+    let graphics_dirs = workspace
+        .iter()
+        .filter(|document| document.uri.scheme() == "file")
+        .filter(|document| {
+            document
+                .data
+                .as_tex()
+                .is_some_and(|data| !data.semantics.graphics_paths.is_empty())
+        })
+        .map(|document| GraphicsDirs::new(workspace, document))
+        .unique()
+        .flat_map(|dirs| dirs.existing_dirs(workspace))
+        .collect::<Vec<_>>();
+
+    for path in graphics_dirs {
+        if watched_dirs.insert(path.clone()) {
+            let _ = watcher.watch(&path, notify::RecursiveMode::NonRecursive);
+        }
+    }
 }
 
 pub fn discover(workspace: &mut Workspace, checked_paths: &mut FxHashSet<PathBuf>) {
@@ -4,12 +4,15 @@ use distro::Language;
 use itertools::Itertools;
 use once_cell::sync::Lazy;
 use percent_encoding::percent_decode_str;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use url::Url;
 
-use crate::{semantics, util, Document, Workspace};
+use crate::{
+    semantics::{self, tex::LinkKind},
+    util, Document, Workspace,
+};
 
-use super::ProjectRoot;
+use super::{project, ProjectRoot};
 
 pub static HOME_DIR: Lazy<Option<PathBuf>> = Lazy::new(dirs::home_dir);
 
@@ -25,6 +28,8 @@ pub enum EdgeData {
     DirectLink(Box<DirectLinkData>),
     AdditionalFiles,
     Artifact,
+    Recorder,
+    MagicComment,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
@@ -33,6 +38,13 @@ pub struct DirectLinkData {
     pub new_root: Option<ProjectRoot>,
 }
 
+/// A link to a LaTeX or BibTeX document that does not match any of the candidate paths.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct UnresolvedLink {
+    pub source: Url,
+    pub link: semantics::tex::Link,
+}
+
 #[derive(Debug, Clone, Copy)]
 struct Start<'a, 'b> {
     source: &'a Document,
@@ -42,20 +54,32 @@ struct Start<'a, 'b> {
 #[derive(Debug)]
 pub struct Graph {
     pub missing: Vec<Url>,
+    pub unresolved: Vec<UnresolvedLink>,
     pub edges: Vec<Edge>,
     pub start: Url,
+    /// The project root of the start document.
+    pub root: ProjectRoot,
 }
 
 impl Graph {
-    pub fn new(workspace: &Workspace, start: &Document) -> Self {
+    /// Builds the dependency graph of the start document.
+    ///
+    /// The documents that declare their root with a `% !TEX root` comment are looked up
+    /// in `magic_children`, see [`project::magic_children`].
+    pub fn new(
+        workspace: &Workspace,
+        start: &Document,
+        magic_children: &FxHashMap<Url, Vec<Url>>,
+    ) -> Self {
+        let root = ProjectRoot::walk_and_find(workspace, &start.dir);
         let mut graph = Self {
             missing: Vec::new(),
+            unresolved: Vec::new(),
             edges: Vec::new(),
             start: start.uri.clone(),
+            root: root.clone(),
         };
 
-        let root = ProjectRoot::walk_and_find(workspace, &start.dir);
-
         let mut stack = vec![(start, Rc::new(root))];
         let mut visited = FxHashSet::default();
 
@@ -68,6 +92,7 @@ impl Graph {
                     source,
                     root: &root,
                 },
+                magic_children,
             );
 
             for edge in &graph.edges[index..] {
@@ -97,10 +122,45 @@ impl Graph {
             .filter_map(|uri| workspace.lookup(uri))
     }
 
-    fn process(&mut self, workspace: &Workspace, start: Start) {
+    fn process(
+        &mut self,
+        workspace: &Workspace,
+        start: Start,
+        magic_children: &FxHashMap<Url, Vec<Url>>,
+    ) {
         self.add_direct_links(workspace, start);
         self.add_artifacts(workspace, start);
         self.add_additional_files(workspace, start);
+        self.add_recorded_files(workspace, start);
+        self.add_magic_root(workspace, start, magic_children);
+    }
+
+    /// Adds the documents that declare the start document as their root with a `% !TEX root` comment.
+    fn add_magic_root(
+        &mut self,
+        workspace: &Workspace,
+        start: Start,
+        magic_children: &FxHashMap<Url, Vec<Url>>,
+    ) {
+        for uri in project::magic_root_candidates(start.source) {
+            if workspace.lookup(&uri).is_some() {
+                break;
+            }
+
+            self.missing.push(uri);
+        }
+
+        if start.source.uri != self.start {
+            return;
+        }
+
+        for child in magic_children.get(&start.source.uri).into_iter().flatten() {
+            self.edges.push(Edge {
+                source: start.source.uri.clone(),
+                target: child.clone(),
+                data: EdgeData::MagicComment,
+            });
+        }
     }
 
     fn add_additional_files(&mut self, workspace: &Workspace, start: Start) {
@@ -136,6 +196,11 @@ impl Graph {
         start: Start,
         link: &semantics::tex::Link,
     ) {
+        // Images are not part of the workspace, see `deps::resolve_graphics`.
+        if link.kind == LinkKind::Graphics {
+            return;
+        }
+
         let home_dir = HOME_DIR.as_deref();
 
         let stem = &link.path.text;
@@ -182,13 +247,20 @@ impl Graph {
                         data: EdgeData::DirectLink(Box::new(link_data)),
                     });
 
-                    break;
+                    return;
                 }
                 None => {
                     self.missing.push(target_uri);
                 }
             };
         }
+
+        if matches!(link.kind, LinkKind::Tex | LinkKind::Bib) {
+            self.unresolved.push(UnresolvedLink {
+                source: start.source.uri.clone(),
+                link: link.clone(),
+            });
+        }
     }
 
     fn add_artifacts(&mut self, workspace: &Workspace, start: Start) {
@@ -209,6 +281,12 @@ impl Graph {
         self.add_artifact(workspace, start.source, &root.aux_dir, "aux");
         self.add_artifact(workspace, start.source, &root.compile_dir, "aux");
 
+        self.add_artifact(workspace, start.source, &root.aux_dir, "fls");
+        self.add_artifact(workspace, start.source, &root.compile_dir, "fls");
+
+        self.add_artifact(workspace, start.source, &root.aux_dir, "blg");
+        self.add_artifact(workspace, start.source, &root.compile_dir, "blg");
+
         self.add_artifact(
             workspace,
             start.source,
@@ -218,6 +296,88 @@ impl Graph {
 
         self.add_artifact(workspace, start.source, &root.log_dir, "log");
         self.add_artifact(workspace, start.source, &root.compile_dir, "log");
+
+        // The artifacts are named after the job instead of the compiled file.
+        if let Some(jobname) = &root.jobname {
+            for (dir, extension) in [
+                (&root.aux_dir, "aux"),
+                (&root.aux_dir, "fls"),
+                (&root.aux_dir, "blg"),
+                (&root.log_dir, "log"),
+            ] {
+                if let Ok(target_uri) = dir.join(&format!("{jobname}.{extension}")) {
+                    self.add_artifact_uri(workspace, start.source, target_uri);
+                }
+            }
+        }
+
+        // Tectonic names the artifacts after the output instead of the input files.
+        for target in root
+            .targets
+            .iter()
+            .filter(|target| target.additional_files.contains(&start.source.uri))
+        {
+            for extension in ["aux", "log"] {
+                let name = format!("{}.{extension}", target.name);
+                if let Ok(target_uri) = target.out_dir.join(&name) {
+                    self.add_artifact_uri(workspace, start.source, target_uri);
+                }
+            }
+        }
+    }
+
+    /// Adds the source files and artifacts listed in a `.fls` file
+    /// to include files that cannot be found by looking at the links.
+    fn add_recorded_files(&mut self, workspace: &Workspace, start: Start) -> Option<()> {
+        let data = start.source.data.as_fls()?;
+        let compile_dir = start.root.compile_dir.to_file_path().ok()?;
+        let file_name_db = &workspace.distro().file_name_db;
+        let home_dir = HOME_DIR.as_deref();
+
+        let inputs = data.inputs.iter().filter(|path| {
+            matches!(
+                Language::from_path(path),
+                Some(Language::Tex | Language::Bib)
+            )
+        });
+
+        let outputs = data.outputs.iter().filter(|path| {
+            matches!(
+                Language::from_path(path),
+                Some(Language::Aux | Language::Log)
+            )
+        });
+
+        for path in inputs.chain(outputs) {
+            let path = compile_dir.join(path);
+            if file_name_db.contains(&path) && !home_dir.is_some_and(|dir| path.starts_with(dir)) {
+                continue;
+            }
+
+            let Ok(target_uri) = Url::from_file_path(&path) else {
+                continue;
+            };
+
+            if target_uri == self.start {
+                continue;
+            }
+
+            match workspace.lookup(&target_uri) {
+                Some(target) => {
+                    self.edges.push(Edge {
+                        source: start.source.uri.clone(),
+                        target: target.uri.clone(),
+                        data: EdgeData::Recorder,
+                    });
+                }
+                None if workspace.contains(&path) => {
+                    self.missing.push(target_uri);
+                }
+                None => {}
+            };
+        }
+
+        Some(())
     }
 
     fn add_artifact(
@@ -242,6 +402,10 @@ impl Graph {
             return;
         };
 
+        self.add_artifact_uri(workspace, source, target_uri);
+    }
+
+    fn add_artifact_uri(&mut self, workspace: &Workspace, source: &Document, target_uri: Url) {
         match workspace.lookup(&target_uri) {
             Some(target) => {
                 self.edges.push(Edge {
@@ -0,0 +1,110 @@
+use std::path::PathBuf;
+
+use url::Url;
+
+use crate::{
+    semantics::tex::{Link, LinkKind},
+    util, Document, Workspace,
+};
+
+use super::{root, Project, ProjectRoot};
+
+/// Finds the image file that is referenced by `\includegraphics` and similar commands.
+///
+/// Like LaTeX, the path is looked up in the source directory and in every directory
+/// of `\graphicspath`, trying the known extensions if the file cannot be found as is.
+pub fn resolve_graphics(
+    workspace: &Workspace,
+    document: &Document,
+    link: &Link,
+) -> Option<PathBuf> {
+    if link.kind != LinkKind::Graphics {
+        return None;
+    }
+
+    GraphicsDirs::new(workspace, document).resolve(workspace, link)
+}
+
+/// The directories in which the images of a document are looked up.
+///
+/// Computing them requires the project of the document,
+/// so they should be reused when resolving multiple links of the same document.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct GraphicsDirs {
+    pub src_dir: Url,
+    pub dirs: Vec<String>,
+}
+
+impl GraphicsDirs {
+    pub fn new(workspace: &Workspace, document: &Document) -> Self {
+        let parent = root(workspace, document);
+        let root = ProjectRoot::walk_and_find(workspace, &parent.dir);
+
+        let mut dirs = vec![String::new()];
+        for data in Project::from_child(workspace, document)
+            .documents
+            .into_iter()
+            .chain(std::iter::once(document))
+            .filter_map(|document| document.data.as_tex())
+        {
+            for path in &data.semantics.graphics_paths {
+                let mut dir = path.clone();
+                if !dir.ends_with('/') {
+                    dir.push('/');
+                }
+
+                if !dirs.contains(&dir) {
+                    dirs.push(dir);
+                }
+            }
+        }
+
+        Self {
+            src_dir: root.src_dir,
+            dirs,
+        }
+    }
+
+    /// Returns the first candidate of the link that exists on disk.
+    pub fn resolve(&self, workspace: &Workspace, link: &Link) -> Option<PathBuf> {
+        if link.kind != LinkKind::Graphics {
+            return None;
+        }
+
+        self.candidates(workspace, link)
+            .into_iter()
+            .filter_map(|uri| uri.to_file_path().ok())
+            .find(|path| path.is_file())
+    }
+
+    fn candidates(&self, workspace: &Workspace, link: &Link) -> Vec<Url> {
+        let stem = &link.path.text;
+        let mut file_names = vec![stem.clone()];
+        link.kind
+            .extensions()
+            .iter()
+            .map(|ext| format!("{stem}.{ext}"))
+            .for_each(|name| file_names.push(name));
+
+        self.dirs
+            .iter()
+            .flat_map(|dir| file_names.iter().map(move |name| format!("{dir}{name}")))
+            .filter_map(|path| {
+                util::expand_relative_path(&path, &self.src_dir, workspace.folders()).ok()
+            })
+            .collect()
+    }
+
+    /// Returns the directories of `\graphicspath` that exist on disk.
+    pub fn existing_dirs(&self, workspace: &Workspace) -> Vec<PathBuf> {
+        self.dirs
+            .iter()
+            .filter(|dir| !dir.is_empty())
+            .filter_map(|dir| {
+                util::expand_relative_path(dir, &self.src_dir, workspace.folders()).ok()
+            })
+            .filter_map(|uri| uri.to_file_path().ok())
+            .filter(|path| path.is_dir())
+            .collect()
+    }
+}
@@ -1,8 +1,12 @@
 use itertools::Itertools;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use url::Url;
 
 use crate::{Document, Workspace};
 
+use super::ProjectRoot;
+
 #[derive(Debug, Clone)]
 pub struct Project<'a> {
     pub documents: FxHashSet<&'a Document>,
@@ -19,6 +23,27 @@ impl<'a> Project<'a> {
 
         Self { documents }
     }
+
+    /// Finds the packages that are loaded by the project, including the LaTeX kernel.
+    pub fn included_packages(
+        &self,
+    ) -> impl Iterator<Item = &'static completion_data::Package<'static>> + '_ {
+        let db = &completion_data::DATABASE;
+        self.documents
+            .iter()
+            .filter_map(|document| document.data.as_tex())
+            .flat_map(|data| data.semantics.links.iter())
+            .filter_map(|link| link.package_name())
+            .filter_map(|name| db.find(&name))
+            .chain(std::iter::once(db.kernel()))
+            .flat_map(|package| {
+                package
+                    .references
+                    .iter()
+                    .filter_map(|name| db.find(name))
+                    .chain(std::iter::once(package))
+            })
+    }
 }
 
 pub fn parents<'a>(workspace: &'a Workspace, child: &'a Document) -> FxHashSet<&'a Document> {
@@ -37,3 +62,78 @@ pub fn parents<'a>(workspace: &'a Workspace, child: &'a Document) -> FxHashSet<&
         })
         .collect()
 }
+
+/// Finds the document that needs to be compiled to build the given document.
+///
+/// A `% !TEX root` magic comment takes precedence over the dependency graph.
+/// Otherwise, the files listed in `@default_files` of a `latexmkrc` file are preferred.
+/// If the document belongs to multiple projects, then the document itself is preferred
+/// and the parent with the smallest URI otherwise so that the choice is stable.
+pub fn root<'a>(workspace: &'a Workspace, child: &'a Document) -> &'a Document {
+    if let Some(root) = magic_root(workspace, child) {
+        return root;
+    }
+
+    let parents = parents(workspace, child);
+    let default_files = ProjectRoot::walk_and_find(workspace, &child.dir).default_files(workspace);
+    if let Some(root) = default_files
+        .iter()
+        .find_map(|uri| parents.iter().find(|parent| parent.uri == *uri))
+    {
+        return root;
+    }
+
+    if parents.contains(child) {
+        return child;
+    }
+
+    parents
+        .into_iter()
+        .min_by(|a, b| a.uri.cmp(&b.uri))
+        .unwrap_or(child)
+}
+
+/// Finds the document that is referenced by the `% !TEX root` magic comment of the given document.
+pub fn magic_root<'a>(workspace: &'a Workspace, child: &Document) -> Option<&'a Document> {
+    magic_root_candidates(child)
+        .iter()
+        .find_map(|uri| workspace.lookup(uri))
+}
+
+/// Maps every document to the documents that declare it as their root
+/// with a `% !TEX root` magic comment.
+pub fn magic_children(workspace: &Workspace) -> FxHashMap<Url, Vec<Url>> {
+    let mut children: FxHashMap<Url, Vec<Url>> = FxHashMap::default();
+    for child in workspace.iter() {
+        if let Some(root) = magic_root(workspace, child).filter(|root| *root != child) {
+            children
+                .entry(root.uri.clone())
+                .or_default()
+                .push(child.uri.clone());
+        }
+    }
+
+    children
+}
+
+/// Returns the possible URIs of the `% !TEX root` magic comment.
+///
+/// The path is relative to the document and the `.tex` extension may be omitted.
+pub(super) fn magic_root_candidates(child: &Document) -> Vec<Url> {
+    let Some(path) = child
+        .data
+        .as_tex()
+        .and_then(|data| data.semantics.magic_comments.root.as_ref())
+        .map(|root| &root.text)
+    else {
+        return Vec::new();
+    };
+
+    [path.clone(), format!("{path}.tex")]
+        .into_iter()
+        .filter_map(|path| child.dir.join(&path).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,70 @@
+use distro::Language;
+use line_index::LineCol;
+use url::Url;
+
+use crate::{Owner, Workspace};
+
+fn root_of(documents: &[(&str, &str)], child: &str) -> String {
+    let mut workspace = Workspace::default();
+    for (name, text) in documents {
+        let uri = Url::parse(&format!("file:///texlab/{name}")).unwrap();
+        let language = Language::from_path(std::path::Path::new(name)).unwrap();
+        workspace.open(
+            uri,
+            text.to_string(),
+            language,
+            Owner::Client,
+            LineCol { line: 0, col: 0 },
+        );
+    }
+
+    let uri = Url::parse(&format!("file:///texlab/{child}")).unwrap();
+    let child = workspace.lookup(&uri).unwrap();
+    super::root(&workspace, child).uri.to_string()
+}
+
+#[test]
+fn test_root_smallest_parent() {
+    let parent = r#"\begin{document}\input{chapter}\end{document}"#;
+    for documents in [
+        [("b.tex", parent), ("a.tex", parent), ("chapter.tex", "")],
+        [("a.tex", parent), ("b.tex", parent), ("chapter.tex", "")],
+    ] {
+        assert_eq!(root_of(&documents, "chapter.tex"), "file:///texlab/a.tex");
+    }
+}
+
+#[test]
+fn test_root_self() {
+    let parent = r#"\begin{document}\input{b}\end{document}"#;
+    let documents = [
+        ("a.tex", parent),
+        ("b.tex", r#"\begin{document}\end{document}"#),
+    ];
+    assert_eq!(root_of(&documents, "b.tex"), "file:///texlab/b.tex");
+}
+
+#[test]
+fn test_root_magic_comment() {
+    let parent = r#"\begin{document}\input{chapter}\end{document}"#;
+    let documents = [
+        ("a.tex", parent),
+        ("b.tex", parent),
+        ("chapter.tex", "% !TEX root = b"),
+    ];
+
+    assert_eq!(root_of(&documents, "chapter.tex"), "file:///texlab/b.tex");
+}
+
+#[test]
+fn test_root_default_files() {
+    let parent = r#"\begin{document}\input{chapter}\end{document}"#;
+    let documents = [
+        (".latexmkrc", "@default_files = ('b.tex');"),
+        ("a.tex", parent),
+        ("b.tex", parent),
+        ("chapter.tex", ""),
+    ];
+
+    assert_eq!(root_of(&documents, "chapter.tex"), "file:///texlab/b.tex");
+}
@@ -1,10 +1,13 @@
+use itertools::Itertools;
+use parser::SyntaxConfig;
+use syntax::{latexmkrc::LatexmkrcData, tectonic::TectonicOutput};
 use url::Url;
 
-use crate::{util, DocumentData, Workspace};
+use crate::{util, BuildConfig, CitationConfig, Workspace};
 
 use super::graph::HOME_DIR;
 
-#[derive(PartialEq, Eq, Clone, Hash)]
+#[derive(PartialEq, Eq, Clone)]
 pub struct ProjectRoot {
     pub compile_dir: Url,
     pub src_dir: Url,
@@ -12,6 +15,35 @@ pub struct ProjectRoot {
     pub log_dir: Url,
     pub pdf_dir: Url,
     pub additional_files: Vec<Url>,
+    pub targets: Vec<BuildTarget>,
+    /// The name of the artifacts if it does not match the name of the compiled file like `$jobname` of `latexmkrc`.
+    pub jobname: Option<String>,
+    pub build: BuildConfig,
+    pub citation: CitationConfig,
+    pub syntax: SyntaxConfig,
+}
+
+impl std::hash::Hash for ProjectRoot {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        // The configuration is determined by the directories.
+        self.compile_dir.hash(state);
+        self.src_dir.hash(state);
+        self.aux_dir.hash(state);
+        self.log_dir.hash(state);
+        self.pdf_dir.hash(state);
+        self.additional_files.hash(state);
+        self.targets.hash(state);
+        self.jobname.hash(state);
+    }
+}
+
+/// An output of a project that can be built on its own like the `[[output]]` sections of `Tectonic.toml`.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct BuildTarget {
+    pub name: String,
+    pub out_dir: Url,
+    pub pdf_name: Option<String>,
+    pub additional_files: Vec<Url>,
 }
 
 impl ProjectRoot {
@@ -43,25 +75,54 @@ impl ProjectRoot {
     }
 
     pub fn from_tectonic(workspace: &Workspace, dir: &Url) -> Option<Self> {
-        let exists = workspace
+        let data = workspace
             .iter()
             .filter(|document| document.dir == *dir)
-            .any(|document| matches!(document.data, DocumentData::Tectonic));
-
-        if !exists {
-            return None;
-        }
+            .find_map(|document| document.data.as_tectonic())?;
 
+        let config = workspace.config();
         let compile_dir = dir.clone();
         let src_dir = dir.join("src/").unwrap();
-        let out_dir = dir.join("build/").unwrap();
-        let aux_dir = out_dir.clone();
-        let log_dir = out_dir.clone();
-        let pdf_dir = out_dir;
-        let additional_files = vec![
-            src_dir.join("_preamble.tex").unwrap(),
-            src_dir.join("_postamble.tex").unwrap(),
-        ];
+        let build_dir = dir.join("build/").unwrap();
+
+        let mut outputs = data.outputs.clone();
+        if outputs.is_empty() {
+            outputs.push(TectonicOutput::default());
+        }
+
+        let targets: Vec<_> = outputs
+            .iter()
+            .filter_map(|output| {
+                let out_dir = build_dir.join(&format!("{}/", output.name)).ok()?;
+                let additional_files = output
+                    .inputs
+                    .iter()
+                    .filter_map(|input| src_dir.join(input).ok())
+                    .collect();
+
+                Some(BuildTarget {
+                    name: output.name.clone(),
+                    out_dir,
+                    pdf_name: output.pdf_name(),
+                    additional_files,
+                })
+            })
+            .collect();
+
+        // The first output is used for the forward search and the diagnostics of the build log.
+        let default = targets.first()?;
+        let aux_dir = default.out_dir.clone();
+        let log_dir = default.out_dir.clone();
+        let pdf_dir = default.out_dir.clone();
+
+        let mut build = config.build.clone();
+        build.output_filename = default.pdf_name.clone();
+
+        let additional_files = targets
+            .iter()
+            .flat_map(|target| target.additional_files.iter().cloned())
+            .unique()
+            .collect();
 
         Some(Self {
             compile_dir,
@@ -70,6 +131,11 @@ impl ProjectRoot {
             log_dir,
             pdf_dir,
             additional_files,
+            targets,
+            jobname: None,
+            build,
+            citation: config.citation.clone(),
+            syntax: config.syntax.clone(),
         })
     }
 
@@ -108,6 +174,13 @@ impl ProjectRoot {
 
         let additional_files = vec![];
 
+        // The job name can contain placeholders like `%A` that depend on the compiled file.
+        let jobname = rcfile.jobname.clone().filter(|name| !name.contains('%'));
+        let mut build = config.build.clone();
+        if let Some(jobname) = &jobname {
+            build.output_filename = Some(format!("{jobname}.pdf"));
+        }
+
         Some(Self {
             compile_dir,
             src_dir,
@@ -115,32 +188,49 @@ impl ProjectRoot {
             log_dir,
             pdf_dir,
             additional_files,
+            targets: Vec::new(),
+            jobname,
+            build,
+            citation: config.citation.clone(),
+            syntax: config.syntax.clone(),
         })
     }
 
     pub fn from_rootfile(workspace: &Workspace, dir: &Url) -> Option<Self> {
-        let exists = workspace
+        let project_config = workspace
             .iter()
             .filter(|document| document.dir == *dir)
-            .any(|document| matches!(document.data, DocumentData::Root));
+            .find_map(|document| document.data.as_root())?;
 
-        if !exists {
-            return None;
-        }
-
-        Some(Self::from_config(workspace, dir))
+        let config = workspace.config();
+        let build = project_config.merge_build(&config.build);
+        let citation = project_config.merge_citation(&config.citation);
+        let syntax = project_config.merge_syntax(&config.syntax);
+        Some(Self::from_build_config(
+            workspace, dir, build, citation, syntax,
+        ))
     }
 
     pub fn from_config(workspace: &Workspace, dir: &Url) -> Self {
+        let config = workspace.config();
+        let build = config.build.clone();
+        let citation = config.citation.clone();
+        let syntax = config.syntax.clone();
+        Self::from_build_config(workspace, dir, build, citation, syntax)
+    }
+
+    fn from_build_config(
+        workspace: &Workspace,
+        dir: &Url,
+        build: BuildConfig,
+        citation: CitationConfig,
+        syntax: SyntaxConfig,
+    ) -> Self {
         let compile_dir = dir.clone();
         let src_dir = dir.clone();
-        let config = workspace.config();
-        let aux_dir =
-            append_dir(dir, &config.build.aux_dir, workspace).unwrap_or_else(|_| dir.clone());
-        let log_dir =
-            append_dir(dir, &config.build.log_dir, workspace).unwrap_or_else(|_| dir.clone());
-        let pdf_dir =
-            append_dir(dir, &config.build.pdf_dir, workspace).unwrap_or_else(|_| dir.clone());
+        let aux_dir = append_dir(dir, &build.aux_dir, workspace).unwrap_or_else(|_| dir.clone());
+        let log_dir = append_dir(dir, &build.log_dir, workspace).unwrap_or_else(|_| dir.clone());
+        let pdf_dir = append_dir(dir, &build.pdf_dir, workspace).unwrap_or_else(|_| dir.clone());
         let additional_files = vec![];
 
         Self {
@@ -150,8 +240,33 @@ impl ProjectRoot {
             log_dir,
             pdf_dir,
             additional_files,
+            targets: Vec::new(),
+            jobname: None,
+            build,
+            citation,
+            syntax,
         }
     }
+
+    /// Returns the settings of the `latexmkrc` file that defines this project.
+    pub fn latexmkrc<'a>(&self, workspace: &'a Workspace) -> Option<&'a LatexmkrcData> {
+        workspace
+            .iter()
+            .filter(|document| document.dir == self.compile_dir)
+            .find_map(|document| document.data.as_latexmkrc())
+    }
+
+    /// Returns the documents that are listed in `@default_files` of the `latexmkrc` file.
+    ///
+    /// Wildcard patterns are ignored.
+    pub fn default_files(&self, workspace: &Workspace) -> Vec<Url> {
+        self.latexmkrc(workspace)
+            .into_iter()
+            .flat_map(|rcfile| rcfile.default_files.iter())
+            .filter(|file| !file.contains(['*', '?']))
+            .filter_map(|file| self.src_dir.join(file).ok())
+            .collect()
+    }
 }
 
 fn append_dir(dir: &Url, path: &str, workspace: &Workspace) -> Result<Url, url::ParseError> {
@@ -172,6 +287,8 @@ impl std::fmt::Debug for ProjectRoot {
             .field("log_dir", &self.log_dir.as_str())
             .field("pdf_dir", &self.pdf_dir.as_str())
             .field("additional_files", &self.additional_files)
+            .field("targets", &self.targets)
+            .field("jobname", &self.jobname)
             .finish()
     }
 }
@@ -3,10 +3,15 @@ use std::path::PathBuf;
 use distro::Language;
 use line_index::{LineCol, LineIndex};
 use rowan::TextRange;
-use syntax::{bibtex, latex, latexmkrc::LatexmkrcData, BuildError};
+use syntax::{
+    bibtex, blg::BlgData, fls::FlsData, latex, latexmkrc::LatexmkrcData, tectonic::TectonicData,
+    BuildError,
+};
 use url::Url;
 
-use crate::{semantics, Config};
+use parser::SyntaxConfig;
+
+use crate::{semantics, ProjectConfig};
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum Owner {
@@ -22,7 +27,7 @@ pub struct DocumentParams<'a> {
     pub language: Language,
     pub owner: Owner,
     pub cursor: LineCol,
-    pub config: &'a Config,
+    pub syntax: &'a SyntaxConfig,
 }
 
 #[derive(Clone)]
@@ -54,7 +59,7 @@ impl Document {
 
         let data = match params.language {
             Language::Tex => {
-                let green = parser::parse_latex(&text, &params.config.syntax);
+                let green = parser::parse_latex(&text, params.syntax);
                 let mut semantics = semantics::tex::Semantics::default();
                 semantics.process_root(&latex::SyntaxNode::new_root(green.clone()));
                 DocumentData::Tex(TexDocumentData { green, semantics })
@@ -66,7 +71,7 @@ impl Document {
                 DocumentData::Bib(BibDocumentData { green, semantics })
             }
             Language::Aux => {
-                let green = parser::parse_latex(&text, &params.config.syntax);
+                let green = parser::parse_latex(&text, params.syntax);
                 let mut semantics = semantics::auxiliary::Semantics::default();
                 semantics.process_root(&latex::SyntaxNode::new_root(green.clone()));
                 DocumentData::Aux(AuxDocumentData { green, semantics })
@@ -75,7 +80,16 @@ impl Document {
                 let errors = parser::parse_build_log(&text).errors;
                 DocumentData::Log(LogDocumentData { errors })
             }
-            Language::Root => DocumentData::Root,
+            Language::Fls => DocumentData::Fls(parser::parse_fls(&text)),
+            Language::Blg => DocumentData::Blg(parser::parse_blg(&text)),
+            Language::Root => {
+                let config = ProjectConfig::parse(&text).unwrap_or_else(|why| {
+                    log::warn!("Invalid project configuration {uri}: {why}");
+                    ProjectConfig::default()
+                });
+
+                DocumentData::Root(Box::new(config))
+            }
             Language::Latexmkrc => {
                 let data = path
                     .as_deref()
@@ -85,7 +99,14 @@ impl Document {
 
                 DocumentData::Latexmkrc(data)
             }
-            Language::Tectonic => DocumentData::Tectonic,
+            Language::Tectonic => {
+                let data = parser::parse_tectonic(&text).unwrap_or_else(|why| {
+                    log::warn!("Invalid Tectonic configuration {uri}: {why}");
+                    TectonicData::default()
+                });
+
+                DocumentData::Tectonic(data)
+            }
         };
 
         Self {
@@ -100,6 +121,52 @@ impl Document {
             data,
         }
     }
+
+    /// Applies a change sent by the client.
+    ///
+    /// LaTeX documents are reparsed incrementally if the change is contained in a group or an environment.
+    /// All other documents are parsed from scratch.
+    pub fn edit(
+        &self,
+        delete: TextRange,
+        insert: &str,
+        cursor: LineCol,
+        syntax: &SyntaxConfig,
+    ) -> Self {
+        let mut text = self.text.clone();
+        text.replace_range(std::ops::Range::<usize>::from(delete), insert);
+
+        let green = self
+            .data
+            .as_tex()
+            .and_then(|data| parser::reparse_latex(&data.root_node(), delete, insert, syntax));
+
+        let Some(green) = green else {
+            return Self::parse(DocumentParams {
+                uri: self.uri.clone(),
+                text,
+                language: self.language,
+                owner: Owner::Client,
+                cursor,
+                syntax,
+            });
+        };
+
+        let mut semantics = semantics::tex::Semantics::default();
+        semantics.process_root(&latex::SyntaxNode::new_root(green.clone()));
+
+        Self {
+            uri: self.uri.clone(),
+            dir: self.dir.clone(),
+            path: self.path.clone(),
+            line_index: LineIndex::new(&text),
+            text,
+            owner: Owner::Client,
+            cursor,
+            language: self.language,
+            data: DocumentData::Tex(TexDocumentData { green, semantics }),
+        }
+    }
 }
 
 impl std::fmt::Debug for Document {
@@ -140,9 +207,11 @@ pub enum DocumentData {
     Bib(BibDocumentData),
     Aux(AuxDocumentData),
     Log(LogDocumentData),
-    Root,
+    Fls(FlsData),
+    Blg(BlgData),
+    Root(Box<ProjectConfig>),
     Latexmkrc(LatexmkrcData),
-    Tectonic,
+    Tectonic(TectonicData),
 }
 
 impl DocumentData {
@@ -178,6 +247,22 @@ impl DocumentData {
         }
     }
 
+    pub fn as_fls(&self) -> Option<&FlsData> {
+        if let DocumentData::Fls(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_blg(&self) -> Option<&BlgData> {
+        if let DocumentData::Blg(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
+
     pub fn as_latexmkrc(&self) -> Option<&LatexmkrcData> {
         if let DocumentData::Latexmkrc(data) = self {
             Some(data)
@@ -185,6 +270,22 @@ impl DocumentData {
             None
         }
     }
+
+    pub fn as_tectonic(&self) -> Option<&TectonicData> {
+        if let DocumentData::Tectonic(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_root(&self) -> Option<&ProjectConfig> {
+        if let DocumentData::Root(config) = self {
+            Some(config)
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -2,11 +2,12 @@ mod config;
 pub mod data;
 pub mod deps;
 mod document;
+mod project_config;
 pub mod semantics;
 pub mod util;
 mod workspace;
 
-pub use self::{config::*, document::*, workspace::*};
+pub use self::{config::*, document::*, project_config::*, workspace::*};
 
 #[derive(Debug)]
 pub struct FeatureParams<'a> {
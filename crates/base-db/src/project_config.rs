@@ -0,0 +1,150 @@
+use parser::SyntaxConfig;
+use serde::Deserialize;
+
+use crate::{BuildConfig, CitationConfig};
+
+/// The configuration of a project that is read from `.texlabroot` or `texlab.toml`.
+///
+/// The format mirrors the settings of the client so that they can be copied into the file.
+/// Only the build settings, the citation settings and the syntax settings of the `experimental` section
+/// are applied per project. All other settings are ignored.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct ProjectConfig {
+    pub aux_directory: Option<String>,
+    pub build: ProjectBuildConfig,
+    pub citation: ProjectCitationConfig,
+    pub experimental: ProjectSyntaxConfig,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct ProjectBuildConfig {
+    pub executable: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub on_save: Option<bool>,
+    pub forward_search_after: Option<bool>,
+    pub aux_directory: Option<String>,
+    pub log_directory: Option<String>,
+    pub pdf_directory: Option<String>,
+    pub filename: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct ProjectCitationConfig {
+    pub style: Option<String>,
+    pub locale: Option<String>,
+    pub locales_directory: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct ProjectSyntaxConfig {
+    pub follow_package_links: Option<bool>,
+    pub math_environments: Vec<String>,
+    pub enum_environments: Vec<String>,
+    pub verbatim_environments: Vec<String>,
+    pub citation_commands: Vec<String>,
+    pub label_definition_commands: Vec<String>,
+    pub label_reference_commands: Vec<String>,
+}
+
+impl ProjectConfig {
+    /// Parses the configuration file. An empty file is a valid configuration.
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    /// Applies the project settings on top of the settings of the client.
+    pub fn merge_build(&self, base: &BuildConfig) -> BuildConfig {
+        let build = &self.build;
+        let mut config = base.clone();
+
+        if let Some(program) = &build.executable {
+            config.program = program.clone();
+        }
+
+        if let Some(args) = &build.args {
+            config.args = args.clone();
+        }
+
+        config.on_save = build.on_save.unwrap_or(config.on_save);
+        config.forward_search_after = build
+            .forward_search_after
+            .unwrap_or(config.forward_search_after);
+
+        let aux_dir = build.aux_directory.as_ref().or(self.aux_directory.as_ref());
+        if let Some(aux_dir) = aux_dir {
+            config.aux_dir = aux_dir.clone();
+        }
+
+        let pdf_dir = build.pdf_directory.as_ref().or(self.aux_directory.as_ref());
+        if let Some(pdf_dir) = pdf_dir {
+            config.pdf_dir = pdf_dir.clone();
+        }
+
+        if let Some(log_dir) = build.log_directory.as_ref().or(pdf_dir) {
+            config.log_dir = log_dir.clone();
+        }
+
+        if build.filename.is_some() {
+            config.output_filename = build.filename.clone();
+        }
+
+        config
+    }
+
+    /// Applies the project settings on top of the settings of the client.
+    pub fn merge_citation(&self, base: &CitationConfig) -> CitationConfig {
+        let citation = &self.citation;
+        CitationConfig {
+            style: citation.style.clone().or_else(|| base.style.clone()),
+            locale: citation.locale.clone().or_else(|| base.locale.clone()),
+            locales_dir: citation
+                .locales_directory
+                .clone()
+                .or_else(|| base.locales_dir.clone()),
+        }
+    }
+
+    /// Applies the project settings on top of the settings of the client.
+    pub fn merge_syntax(&self, base: &SyntaxConfig) -> SyntaxConfig {
+        let syntax = &self.experimental;
+        let mut config = base.clone();
+
+        config.follow_package_links = syntax
+            .follow_package_links
+            .unwrap_or(config.follow_package_links);
+
+        config
+            .math_environments
+            .extend(syntax.math_environments.iter().cloned());
+
+        config
+            .enum_environments
+            .extend(syntax.enum_environments.iter().cloned());
+
+        config
+            .verbatim_environments
+            .extend(syntax.verbatim_environments.iter().cloned());
+
+        config
+            .citation_commands
+            .extend(syntax.citation_commands.iter().cloned());
+
+        config
+            .label_definition_commands
+            .extend(syntax.label_definition_commands.iter().cloned());
+
+        config
+            .label_reference_commands
+            .extend(syntax.label_reference_commands.iter().cloned());
+
+        config
+    }
+}
@@ -1,10 +1,15 @@
 use bibtex_utils::field::text::TextFieldData;
 use itertools::Itertools;
-use rowan::{ast::AstNode, TextRange};
-use rustc_hash::FxHashMap;
+use rowan::{ast::AstNode, TextLen, TextRange, TextSize};
+use rustc_hash::{FxHashMap, FxHashSet};
 use syntax::bibtex::{self, HasName, HasType, HasValue};
 
-use crate::data::{BibtexEntryType, BibtexEntryTypeCategory};
+use crate::{
+    data::{BibtexEntryType, BibtexEntryTypeCategory},
+    deps::Project,
+    semantics::tex::Citation,
+    util::queries::Object,
+};
 
 use super::Span;
 
@@ -12,6 +17,8 @@ use super::Span;
 pub struct Semantics {
     pub entries: Vec<Entry>,
     pub strings: Vec<StringDef>,
+    /// The keys of other entries that are referenced by fields like `crossref` or `xdata`.
+    pub links: Vec<EntryLink>,
     /// Map from string definition keys to their expanded values.
     pub expanded_defs: FxHashMap<String, String>,
 }
@@ -42,6 +49,33 @@ impl Semantics {
                 .chain(field_values)
                 .join(" ");
 
+            let mut aliases = Vec::new();
+            for field in entry.fields() {
+                let (Some(field_name), Some(value)) = (field.name_token(), field.value()) else {
+                    continue;
+                };
+
+                let keys = split_keys(value.syntax());
+                let kind = match field_name.text().to_ascii_lowercase().as_str() {
+                    "ids" => {
+                        aliases.extend(keys);
+                        continue;
+                    }
+                    "crossref" => EntryLinkKind::Crossref,
+                    "xref" => EntryLinkKind::Xref,
+                    "xdata" => EntryLinkKind::Xdata,
+                    "related" => EntryLinkKind::Related,
+                    "entryset" | "set" => EntryLinkKind::Set,
+                    _ => continue,
+                };
+
+                self.links.extend(keys.into_iter().map(|key| EntryLink {
+                    name: key,
+                    kind,
+                    full_range: field.syntax().text_range(),
+                }));
+            }
+
             self.entries.push(Entry {
                 name: Span {
                     range: name.text_range(),
@@ -50,6 +84,7 @@ impl Semantics {
                 full_range: entry.syntax().text_range(),
                 category,
                 keywords,
+                aliases,
             });
         }
     }
@@ -78,6 +113,57 @@ pub struct Entry {
     pub full_range: TextRange,
     pub keywords: String,
     pub category: BibtexEntryTypeCategory,
+    /// The alternative keys of the entry that are given by the `ids` field.
+    pub aliases: Vec<Span>,
+}
+
+impl Entry {
+    /// Checks whether the entry can be cited with the given key.
+    pub fn has_key(&self, key: &str) -> bool {
+        self.name.text == key || self.aliases.iter().any(|alias| alias.text == key)
+    }
+
+    /// Checks whether one of the keys of the entry is in the given set, see [`used_keys`].
+    pub fn is_used(&self, used_keys: &FxHashSet<&str>) -> bool {
+        std::iter::once(&self.name)
+            .chain(&self.aliases)
+            .any(|key| used_keys.contains(key.text.as_str()))
+    }
+}
+
+/// Collects the keys of all entries in the project including the aliases given by `ids`.
+pub fn entry_keys<'a>(project: &Project<'a>) -> FxHashSet<&'a str> {
+    Entry::find_all(project)
+        .flat_map(|(_, entry)| std::iter::once(&entry.name).chain(&entry.aliases))
+        .map(|key| key.text.as_str())
+        .collect()
+}
+
+/// Collects the keys that are cited in the project.
+///
+/// Entries that are referenced by other entries (e.g. with `crossref`) are used as well.
+pub fn used_keys<'a>(project: &Project<'a>) -> FxHashSet<&'a str> {
+    Citation::find_all(project)
+        .map(|(_, citation)| citation.name_text())
+        .chain(EntryLink::find_all(project).map(|(_, link)| link.name_text()))
+        .collect()
+}
+
+/// A reference to another entry inside a field value.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EntryLink {
+    pub name: Span,
+    pub kind: EntryLinkKind,
+    pub full_range: TextRange,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum EntryLinkKind {
+    Crossref,
+    Xref,
+    Xdata,
+    Related,
+    Set,
 }
 
 #[derive(Debug, Clone)]
@@ -85,3 +171,36 @@ pub struct StringDef {
     pub name: Span,
     pub full_range: TextRange,
 }
+
+/// Splits a value like `{foo, bar}` into the keys it contains.
+fn split_keys(value: &bibtex::SyntaxNode) -> Vec<Span> {
+    let text = value.text().to_string();
+    let mut start = value.text_range().start();
+    let mut inner = text.as_str();
+    if let Some(rest) = inner
+        .strip_prefix('{')
+        .and_then(|rest| rest.strip_suffix('}'))
+        .or_else(|| {
+            inner
+                .strip_prefix('"')
+                .and_then(|rest| rest.strip_suffix('"'))
+        })
+    {
+        inner = rest;
+        start += TextSize::from(1);
+    }
+
+    let mut keys = Vec::new();
+    for part in inner.split(',') {
+        let key = part.trim();
+        if !key.is_empty() {
+            let leading = &part[..part.len() - part.trim_start().len()];
+            let offset = start + leading.text_len();
+            keys.push(Span::new(key.into(), TextRange::at(offset, key.text_len())));
+        }
+
+        start += part.text_len() + TextSize::from(1);
+    }
+
+    keys
+}
@@ -1,8 +1,11 @@
-use rowan::{ast::AstNode, TextRange};
+use itertools::Itertools;
+use rowan::{ast::AstNode, TextLen, TextRange};
 use rustc_hash::FxHashSet;
-use syntax::latex::{self, HasBrack, HasCurly};
+use syntax::latex::{self, HasBrack, HasCurly, HasKeyValueBody};
 use titlecase::titlecase;
 
+use crate::{Document, Workspace};
+
 use super::Span;
 
 #[derive(Debug, Clone, Default)]
@@ -10,16 +13,22 @@ pub struct Semantics {
     pub links: Vec<Link>,
     pub labels: Vec<Label>,
     pub citations: Vec<Citation>,
+    pub glossary_entries: Vec<GlossaryEntry>,
     pub commands: Vec<Span>,
     pub environments: Vec<Span>,
     pub theorem_definitions: Vec<TheoremDefinition>,
+    pub command_definitions: Vec<CommandDefinition>,
+    pub environment_definitions: Vec<EnvironmentDefinition>,
     pub graphics_paths: FxHashSet<String>,
+    pub magic_comments: Box<MagicComments>,
     pub can_be_root: bool,
     pub can_be_compiled: bool,
 }
 
 impl Semantics {
     pub fn process_root(&mut self, root: &latex::SyntaxNode) {
+        // Like TeXShop and TeXworks, only the comments at the top of the file are magic comments.
+        let mut in_header = true;
         for node in root.descendants_with_tokens() {
             match node {
                 latex::SyntaxElement::Node(node) => {
@@ -28,7 +37,14 @@ impl Semantics {
                 latex::SyntaxElement::Token(token) => {
                     if token.kind() == latex::COMMAND_NAME {
                         self.commands.push(Span::command(&token));
+                    } else if token.kind() == latex::COMMENT && in_header {
+                        self.process_comment(&token);
                     }
+
+                    in_header &= matches!(
+                        token.kind(),
+                        latex::WHITESPACE | latex::LINE_BREAK | latex::COMMENT
+                    );
                 }
             };
         }
@@ -53,10 +69,24 @@ impl Semantics {
             self.process_label_reference_range(label);
         } else if let Some(citation) = latex::Citation::cast(node.clone()) {
             self.process_citation(citation);
+        } else if let Some(entry) = latex::GlossaryEntryDefinition::cast(node.clone()) {
+            self.process_glossary_entry_definition(entry);
+        } else if let Some(acronym) = latex::AcronymDefinition::cast(node.clone()) {
+            self.process_acronym_definition(acronym);
+        } else if let Some(acronym) = latex::AcronymDeclaration::cast(node.clone()) {
+            self.process_acronym_declaration(acronym);
+        } else if let Some(entry) = latex::GlossaryEntryReference::cast(node.clone()) {
+            self.process_glossary_entry_reference(entry.name(), latex::small_range(&entry));
+        } else if let Some(acronym) = latex::AcronymReference::cast(node.clone()) {
+            self.process_glossary_entry_reference(acronym.name(), latex::small_range(&acronym));
         } else if let Some(environment) = latex::Environment::cast(node.clone()) {
             self.process_environment(environment);
         } else if let Some(theorem_def) = latex::TheoremDefinition::cast(node.clone()) {
             self.process_theorem_definition(theorem_def);
+        } else if let Some(definition) = latex::NewCommandDefinition::cast(node.clone()) {
+            self.process_command_definition(definition);
+        } else if let Some(definition) = latex::EnvironmentDefinition::cast(node.clone()) {
+            self.process_environment_definition(definition);
         } else if let Some(graphics_path) = latex::GraphicsPath::cast(node.clone()) {
             self.process_graphics_path(graphics_path);
         }
@@ -74,9 +104,17 @@ impl Semantics {
                 latex::LATEX_INCLUDE => LinkKind::Tex,
                 latex::BIBLATEX_INCLUDE => LinkKind::Bib,
                 latex::BIBTEX_INCLUDE => LinkKind::Bib,
+                latex::GRAPHICS_INCLUDE | latex::SVG_INCLUDE | latex::INKSCAPE_INCLUDE => {
+                    LinkKind::Graphics
+                }
                 _ => continue,
             };
 
+            // Paths like `#1` can only be resolved when the surrounding macro is expanded.
+            if path.to_string().contains('#') {
+                continue;
+            }
+
             self.links.push(Link {
                 kind,
                 path: Span::from(&path),
@@ -257,6 +295,80 @@ impl Semantics {
         }
     }
 
+    fn process_glossary_entry_definition(&mut self, entry: latex::GlossaryEntryDefinition) {
+        let Some(name) = entry.name().and_then(|group| group.key()) else {
+            return;
+        };
+
+        let options = entry.options().and_then(|group| group.body());
+        self.glossary_entries.push(GlossaryEntry {
+            kind: GlossaryEntryKind::Definition,
+            name: Span::from(&name),
+            title: find_option(options.as_ref(), "name"),
+            description: find_option(options.as_ref(), "description"),
+            full_range: latex::small_range(&entry),
+        });
+    }
+
+    fn process_acronym_definition(&mut self, acronym: latex::AcronymDefinition) {
+        let Some(name) = acronym.name().and_then(|group| group.key()) else {
+            return;
+        };
+
+        // `\newacronym{key}{short}{long}` and `\acro{key}[short]{long}`
+        let mut arguments = acronym.arguments().filter_map(|group| group.content_text());
+        let short = acronym
+            .short_option()
+            .and_then(|group| group.content_text())
+            .or_else(|| arguments.next());
+
+        self.glossary_entries.push(GlossaryEntry {
+            kind: GlossaryEntryKind::AcronymDefinition,
+            name: Span::from(&name),
+            title: short,
+            description: arguments.next(),
+            full_range: latex::small_range(&acronym),
+        });
+    }
+
+    fn process_acronym_declaration(&mut self, acronym: latex::AcronymDeclaration) {
+        let Some(name) = acronym.name().and_then(|group| group.key()) else {
+            return;
+        };
+
+        let options = acronym.options().and_then(|group| group.body());
+        self.glossary_entries.push(GlossaryEntry {
+            kind: GlossaryEntryKind::AcronymDefinition,
+            name: Span::from(&name),
+            title: find_option(options.as_ref(), "short"),
+            description: find_option(options.as_ref(), "long"),
+            full_range: latex::small_range(&acronym),
+        });
+    }
+
+    fn process_glossary_entry_reference(
+        &mut self,
+        name: Option<latex::CurlyGroupWord>,
+        full_range: TextRange,
+    ) {
+        let Some(name) = name.and_then(|group| group.key()) else {
+            return;
+        };
+
+        let name = Span::from(&name);
+        if name.text.contains('#') {
+            return;
+        }
+
+        self.glossary_entries.push(GlossaryEntry {
+            kind: GlossaryEntryKind::Reference,
+            name,
+            title: None,
+            description: None,
+            full_range,
+        });
+    }
+
     fn process_environment(&mut self, environment: latex::Environment) {
         let Some(name) = environment
             .begin()
@@ -283,6 +395,63 @@ impl Semantics {
         }
     }
 
+    fn process_command_definition(&mut self, definition: latex::NewCommandDefinition) {
+        let Some(name) = definition.name().and_then(|group| group.command()) else {
+            return;
+        };
+
+        self.command_definitions.push(CommandDefinition {
+            name: Span::command(&name),
+            arity: parse_arity(definition.arity()),
+            default_argument: definition
+                .default_argument()
+                .and_then(|group| group.content_text()),
+            full_range: latex::small_range(&definition),
+        });
+    }
+
+    fn process_environment_definition(&mut self, definition: latex::EnvironmentDefinition) {
+        let Some(name) = definition.name().and_then(|group| group.key()) else {
+            return;
+        };
+
+        self.environment_definitions.push(EnvironmentDefinition {
+            name: Span::from(&name),
+            arity: parse_arity(definition.arity()),
+            default_argument: definition
+                .default_argument()
+                .and_then(|group| group.content_text()),
+            full_range: latex::small_range(&definition),
+        });
+    }
+
+    fn process_comment(&mut self, token: &latex::SyntaxToken) {
+        let Some((key, value)) = parse_magic_comment(token.text()) else {
+            return;
+        };
+
+        let magic = &mut self.magic_comments;
+        let slot = match key.to_lowercase().as_str() {
+            "root" => {
+                if magic.root.is_none() {
+                    let end = token.text_range().start() + token.text().trim_end().text_len();
+                    let range = TextRange::new(end - value.text_len(), end);
+                    magic.root = Some(Span::new(value.into(), range));
+                }
+
+                return;
+            }
+            "program" | "ts-program" => &mut magic.program,
+            "spellcheck" => &mut magic.spellcheck,
+            "encoding" => &mut magic.encoding,
+            _ => return,
+        };
+
+        if slot.is_none() {
+            *slot = Some(value.into());
+        }
+    }
+
     fn process_graphics_path(&mut self, graphics_path: latex::GraphicsPath) {
         for path in graphics_path.path_list().filter_map(|path| path.key()) {
             self.graphics_paths.insert(path.to_string());
@@ -290,12 +459,58 @@ impl Semantics {
     }
 }
 
+fn find_option(body: Option<&latex::KeyValueBody>, key: &str) -> Option<String> {
+    body?
+        .pairs()
+        .find(|pair| pair.key().is_some_and(|name| name.to_string() == key))?
+        .value()?
+        .text()
+        .map(|text| {
+            text.strip_prefix('{')
+                .and_then(|text| text.strip_suffix('}'))
+                .map_or(text.clone(), String::from)
+        })
+}
+
+/// Splits a magic comment like `% !TEX program = lualatex` into its key and value.
+fn parse_magic_comment(text: &str) -> Option<(&str, &str)> {
+    let text = text.strip_prefix('%')?.trim_start().strip_prefix('!')?;
+    let prefix = text.get(..3)?;
+    let rest = text.get(3..)?;
+    if !prefix.eq_ignore_ascii_case("tex") || !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let (key, value) = rest.split_once('=')?;
+    let key = key.trim();
+    let value = value.trim();
+    (!key.is_empty() && !value.is_empty()).then_some((key, value))
+}
+
+/// Parses the number of arguments in `\newcommand{\foo}[2]{...}`.
+fn parse_arity(group: Option<latex::BrackGroupWord>) -> usize {
+    group
+        .and_then(|group| group.key())
+        .and_then(|key| key.to_string().parse().ok())
+        .unwrap_or(0)
+}
+
+/// The directives that are written as `% !TEX key = value` comments.
+#[derive(Debug, Clone, Default)]
+pub struct MagicComments {
+    pub root: Option<Span>,
+    pub program: Option<String>,
+    pub spellcheck: Option<String>,
+    pub encoding: Option<String>,
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum LinkKind {
     Sty,
     Cls,
     Tex,
     Bib,
+    Graphics,
 }
 
 impl LinkKind {
@@ -305,6 +520,7 @@ impl LinkKind {
             Self::Cls => &["cls"],
             Self::Tex => &["tex"],
             Self::Bib => &["bib"],
+            Self::Graphics => &["pdf", "png", "jpg", "jpeg", "eps", "ps", "svg", "bmp"],
         }
     }
 }
@@ -341,6 +557,52 @@ pub struct Label {
     pub full_range: TextRange,
 }
 
+/// Returns the labels of the document and whether each of them is resolved.
+///
+/// A definition is resolved if it is referenced and a reference is resolved if it is defined
+/// in one of the projects that contain the document.
+pub fn resolve_labels<'a>(
+    workspace: &'a Workspace,
+    document: &'a Document,
+) -> Vec<(&'a Label, bool)> {
+    let Some(data) = document.data.as_tex() else {
+        return Vec::new();
+    };
+
+    let mut label_refs = FxHashSet::default();
+    let mut label_defs = FxHashSet::default();
+    let project = workspace
+        .graphs()
+        .values()
+        .filter(|graph| graph.preorder(workspace).contains(&document))
+        .flat_map(|graph| graph.preorder(workspace));
+
+    for label in project
+        .filter_map(|child| child.data.as_tex())
+        .flat_map(|data| data.semantics.labels.iter())
+    {
+        if label.kind == LabelKind::Definition {
+            label_defs.insert(&label.name.text);
+        } else {
+            label_refs.insert(&label.name.text);
+        }
+    }
+
+    data.semantics
+        .labels
+        .iter()
+        .map(|label| {
+            let resolved = if label.kind == LabelKind::Definition {
+                label_refs.contains(&label.name.text)
+            } else {
+                label_defs.contains(&label.name.text)
+            };
+
+            (label, resolved)
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 pub struct LabelTarget {
     pub object: LabelObject,
@@ -367,8 +629,52 @@ pub struct TheoremDefinition {
     pub heading: String,
 }
 
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum GlossaryEntryKind {
+    Definition,
+    AcronymDefinition,
+    Reference,
+}
+
+/// A glossary entry or an acronym from the `glossaries`, `acro` or `acronym` packages.
+///
+/// Acronyms share their names with glossary entries because `\gls` can refer to both of them.
+#[derive(Debug, Clone)]
+pub struct GlossaryEntry {
+    pub kind: GlossaryEntryKind,
+    pub name: Span,
+    /// The name of a glossary entry or the short form of an acronym.
+    pub title: Option<String>,
+    /// The description of a glossary entry or the long form of an acronym.
+    pub description: Option<String>,
+    pub full_range: TextRange,
+}
+
+/// A command defined with `\newcommand` and friends.
+///
+/// If there is a default argument, then the first argument is optional.
+#[derive(Debug, Clone)]
+pub struct CommandDefinition {
+    pub name: Span,
+    pub arity: usize,
+    pub default_argument: Option<String>,
+    pub full_range: TextRange,
+}
+
+/// An environment defined with `\newenvironment` and friends.
+#[derive(Debug, Clone)]
+pub struct EnvironmentDefinition {
+    pub name: Span,
+    pub arity: usize,
+    pub default_argument: Option<String>,
+    pub full_range: TextRange,
+}
+
 #[derive(Debug, Clone)]
 pub struct Citation {
     pub name: Span,
     pub full_range: TextRange,
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,77 @@
+use parser::{parse_latex, SyntaxConfig};
+use syntax::latex;
+
+use super::{parse_magic_comment, GlossaryEntryKind, Semantics};
+
+fn process(input: &str) -> Semantics {
+    let green = parse_latex(input, &SyntaxConfig::default());
+    let root = latex::SyntaxNode::new_root(green);
+    let mut semantics = Semantics::default();
+    semantics.process_root(&root);
+    semantics
+}
+
+fn glossary_entries(input: &str) -> Vec<(GlossaryEntryKind, String)> {
+    process(input)
+        .glossary_entries
+        .into_iter()
+        .map(|entry| (entry.kind, entry.name.text))
+        .collect()
+}
+
+#[test]
+fn test_label_in_glossary_entry() {
+    let entries =
+        glossary_entries(r#"\newglossaryentry{foo}{name=Foo, description={Bar \label{baz}}}"#);
+
+    assert_eq!(entries, [(GlossaryEntryKind::Definition, "foo".into())]);
+}
+
+#[test]
+fn test_label_in_acronym() {
+    let entries = glossary_entries(r#"\newacronym{foo}{FOO}{Foo \label{bar}}"#);
+    assert_eq!(
+        entries,
+        [(GlossaryEntryKind::AcronymDefinition, "foo".into())]
+    );
+}
+
+#[test]
+fn test_parse_magic_comment() {
+    assert_eq!(
+        parse_magic_comment("% !TEX root = main.tex"),
+        Some(("root", "main.tex"))
+    );
+
+    assert_eq!(
+        parse_magic_comment("%!tex TS-program=lualatex "),
+        Some(("TS-program", "lualatex"))
+    );
+}
+
+#[test]
+fn test_parse_magic_comment_invalid() {
+    assert_eq!(parse_magic_comment("% TEX root = main.tex"), None);
+    assert_eq!(parse_magic_comment("% !TEXroot = main.tex"), None);
+    assert_eq!(parse_magic_comment("% !BIB program = biber"), None);
+    assert_eq!(parse_magic_comment("% !TEX root"), None);
+    assert_eq!(parse_magic_comment("% !TEX root = "), None);
+}
+
+#[test]
+fn test_magic_comment_header() {
+    let semantics =
+        process("% Thesis\n\n% !TEX root = main.tex\n%!TEX program = xelatex\n\\input{foo}");
+    let magic = &semantics.magic_comments;
+    assert_eq!(
+        magic.root.as_ref().map(|root| root.text.as_str()),
+        Some("main.tex")
+    );
+    assert_eq!(magic.program.as_deref(), Some("xelatex"));
+}
+
+#[test]
+fn test_magic_comment_after_content() {
+    let semantics = process("\\section{Foo}\n% !TEX root = main.tex");
+    assert!(semantics.magic_comments.root.is_none());
+}
@@ -5,7 +5,7 @@ use rowan::TextRange;
 use self::RenderedObject::*;
 
 use crate::{
-    deps::Project,
+    deps::{Project, ProjectRoot},
     semantics::tex::{Label, LabelObject},
     Workspace,
 };
@@ -151,7 +151,23 @@ pub fn render_label<'a>(
                 options,
                 caption,
             } => {
-                let config = &workspace.config().syntax;
+                // The project configuration of the document that defines the label applies.
+                let root = project
+                    .documents
+                    .iter()
+                    .find(|document| {
+                        document.data.as_tex().is_some_and(|data| {
+                            data.semantics
+                                .labels
+                                .iter()
+                                .any(|other| std::ptr::eq(other, label))
+                        })
+                    })
+                    .map(|document| ProjectRoot::walk_and_find(workspace, &document.dir));
+
+                let config = root
+                    .as_ref()
+                    .map_or(&workspace.config().syntax, |root| &root.syntax);
                 if config.math_environments.contains(name.as_str()) {
                     return Some(RenderedLabel {
                         range: target.range,
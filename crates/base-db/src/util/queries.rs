@@ -102,6 +102,37 @@ impl Object for tex::Citation {
     }
 }
 
+impl Object for tex::GlossaryEntry {
+    fn name_text(&self) -> &str {
+        &self.name.text
+    }
+
+    fn name_range(&self) -> TextRange {
+        self.name.range
+    }
+
+    fn full_range(&self) -> TextRange {
+        self.full_range
+    }
+
+    fn kind(&self) -> ObjectKind {
+        match self.kind {
+            tex::GlossaryEntryKind::Definition => ObjectKind::Definition,
+            tex::GlossaryEntryKind::AcronymDefinition => ObjectKind::Definition,
+            tex::GlossaryEntryKind::Reference => ObjectKind::Reference,
+        }
+    }
+
+    fn find<'db>(document: &'db Document) -> Box<dyn Iterator<Item = &'db Self> + 'db> {
+        let data = document.data.as_tex();
+        let iter = data
+            .into_iter()
+            .flat_map(|data| data.semantics.glossary_entries.iter());
+
+        Box::new(iter)
+    }
+}
+
 impl Object for bib::Entry {
     fn name_text(&self) -> &str {
         &self.name.text
@@ -129,6 +160,33 @@ impl Object for bib::Entry {
     }
 }
 
+impl Object for bib::EntryLink {
+    fn name_text(&self) -> &str {
+        &self.name.text
+    }
+
+    fn name_range(&self) -> TextRange {
+        self.name.range
+    }
+
+    fn full_range(&self) -> TextRange {
+        self.full_range
+    }
+
+    fn find<'db>(document: &'db Document) -> Box<dyn Iterator<Item = &'db Self> + 'db> {
+        let data = document.data.as_bib();
+        let iter = data
+            .into_iter()
+            .flat_map(|data| data.semantics.links.iter());
+
+        Box::new(iter)
+    }
+
+    fn kind(&self) -> ObjectKind {
+        ObjectKind::Reference
+    }
+}
+
 #[derive(Debug)]
 pub struct ObjectWithRange<T> {
     pub object: T,
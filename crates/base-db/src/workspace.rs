@@ -74,6 +74,7 @@ impl Workspace {
         cursor: LineCol,
     ) {
         log::debug!("Opening document {uri}...");
+        let root = deps::ProjectRoot::walk_and_find(self, &uri.join(".").unwrap());
         self.documents.remove(&uri);
         self.documents.insert(Document::parse(DocumentParams {
             uri,
@@ -81,13 +82,14 @@ impl Workspace {
             language,
             owner,
             cursor,
-            config: &self.config,
+            syntax: &root.syntax,
         }));
 
-        self.graphs = self
-            .iter()
-            .map(|start| (start.uri.clone(), deps::Graph::new(self, start)))
-            .collect();
+        match language {
+            // The project configuration can change the syntax of the LaTeX documents.
+            Language::Root => self.reload(),
+            _ => self.rebuild_graphs(),
+        };
     }
 
     pub fn load(&mut self, path: &Path, language: Language) -> std::io::Result<()> {
@@ -117,22 +119,28 @@ impl Workspace {
 
     pub fn edit(&mut self, uri: &Url, delete: TextRange, insert: &str) -> Option<()> {
         let document = self.lookup(uri)?;
-        let mut text = document.text.clone();
-        let cursor = if delete.len() == text.text_len() {
-            let line = document.cursor.line.min(text.lines().count() as u32);
+        let cursor = if delete.len() == document.text.text_len() {
+            let line = document
+                .cursor
+                .line
+                .min(document.text.lines().count() as u32);
             LineCol { line, col: 0 }
         } else {
             document.line_index.line_col(delete.start())
         };
 
-        text.replace_range(std::ops::Range::<usize>::from(delete), insert);
-        self.open(
-            document.uri.clone(),
-            text,
-            document.language,
-            Owner::Client,
-            cursor,
-        );
+        let root = deps::ProjectRoot::walk_and_find(self, &document.dir);
+        let document = document.edit(delete, insert, cursor, &root.syntax);
+        let language = document.language;
+        self.documents.replace(document);
+
+        match language {
+            // Only the links of LaTeX documents are part of the dependency graphs.
+            Language::Tex => self.update_graphs(uri),
+            Language::Bib => {}
+            Language::Root => self.reload(),
+            _ => self.rebuild_graphs(),
+        };
 
         Some(())
     }
@@ -185,6 +193,8 @@ impl Workspace {
                 document.cursor,
             );
         }
+
+        self.rebuild_graphs();
     }
 
     pub fn remove(&mut self, uri: &Url) {
@@ -192,6 +202,50 @@ impl Workspace {
         self.documents.remove(uri);
     }
 
+    fn rebuild_graphs(&mut self) {
+        let magic_children = deps::magic_children(self);
+        self.graphs = self
+            .iter()
+            .map(|start| {
+                let graph = deps::Graph::new(self, start, &magic_children);
+                (start.uri.clone(), graph)
+            })
+            .collect();
+    }
+
+    /// Rebuilds the graphs that contain the given document.
+    fn update_graphs(&mut self, uri: &Url) {
+        let magic_root = self
+            .lookup(uri)
+            .and_then(|document| deps::magic_root(self, document))
+            .map(|root| root.uri.clone());
+
+        let graphs = self
+            .graphs
+            .iter()
+            .filter(|(start, graph)| {
+                magic_root.as_ref() == Some(*start)
+                    || graph.preorder(self).any(|document| document.uri == *uri)
+            })
+            .filter_map(|(start, _)| self.lookup(start))
+            .collect::<Vec<_>>();
+
+        if graphs.is_empty() {
+            return;
+        }
+
+        let magic_children = deps::magic_children(self);
+        let graphs = graphs
+            .into_iter()
+            .map(|start| {
+                let graph = deps::Graph::new(self, start, &magic_children);
+                (start.uri.clone(), graph)
+            })
+            .collect::<Vec<_>>();
+
+        self.graphs.extend(graphs);
+    }
+
     pub fn close(&mut self, uri: &Url) -> Option<()> {
         let mut document = self.lookup(uri)?.clone();
         document.owner = Owner::Server;
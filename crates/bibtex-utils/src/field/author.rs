@@ -60,6 +60,11 @@ impl fmt::Display for AuthorFieldData {
 impl AuthorFieldData {
     pub fn parse(value: &Value, cache: &FieldParseCache) -> Option<Self> {
         let TextFieldData { text } = TextFieldData::parse(value, cache)?;
+        Self::parse_text(&text)
+    }
+
+    /// Parses a list of names separated by `and` after the field value has been expanded.
+    pub fn parse_text(text: &str) -> Option<Self> {
         let mut authors = Vec::new();
         let mut words = Vec::new();
         for word in text.split_whitespace() {
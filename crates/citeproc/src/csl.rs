@@ -0,0 +1,78 @@
+mod locale;
+mod render;
+mod style;
+mod variables;
+mod xml;
+
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::{
+    entry::EntryData,
+    output::{Inline, InlineBuilder, Punct},
+};
+
+use self::{locale::Locale, render::Renderer, style::StyleData, variables::Variables};
+
+#[derive(Debug, Error)]
+pub enum StyleError {
+    #[error("Unable to read style: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid XML in line {0}")]
+    InvalidXml(usize),
+
+    #[error("The file is not a CSL style")]
+    NotAStyle,
+}
+
+/// A CSL 1.0 style together with the locale that is used to render it.
+#[derive(Debug, Clone)]
+pub struct Style {
+    data: StyleData,
+    locale: Locale,
+}
+
+impl Style {
+    /// Reads a `.csl` file.
+    ///
+    /// The locale files (`locales-<lang>.xml`) are looked up in `locales_dir` which defaults
+    /// to the directory of the style. The locale defaults to the `default-locale` of the style.
+    pub fn load(
+        path: &Path,
+        locale: Option<&str>,
+        locales_dir: Option<&Path>,
+    ) -> Result<Self, StyleError> {
+        let text = std::fs::read_to_string(path)?;
+        let locales_dir = locales_dir.or_else(|| path.parent());
+        Self::parse(&text, locale, locales_dir)
+    }
+
+    pub fn parse(
+        text: &str,
+        locale: Option<&str>,
+        locales_dir: Option<&Path>,
+    ) -> Result<Self, StyleError> {
+        let root = xml::parse(text).map_err(|error| StyleError::InvalidXml(error.line))?;
+        let data = StyleData::parse(&root).ok_or(StyleError::NotAStyle)?;
+        let lang = locale.or(data.default_locale.as_deref()).unwrap_or("en-US");
+
+        let locale = Locale::resolve(&data.locales, lang, locales_dir);
+        Ok(Self { data, locale })
+    }
+
+    /// Renders the bibliography entry. The punctuation is part of the rendered text.
+    pub(crate) fn render(&self, entry: &EntryData) -> impl Iterator<Item = (Inline, Punct)> {
+        let variables = Variables::new(entry);
+        let mut builder = InlineBuilder::default();
+        for inline in Renderer::new(&self.data, &self.locale, &variables).render() {
+            builder.push(inline, Punct::Nothing, Punct::Nothing);
+        }
+
+        builder.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,335 @@
+use std::path::Path;
+
+use rustc_hash::FxHashMap;
+
+use super::{
+    style::{parse_display, Display},
+    xml,
+};
+
+/// The terms and date formats that are used if no locale file is available.
+const FALLBACK_LOCALE: &str = r#"
+<locale xml:lang="en-US">
+  <date form="text">
+    <date-part name="month" suffix=" "/>
+    <date-part name="day" suffix=", "/>
+    <date-part name="year"/>
+  </date>
+  <date form="numeric">
+    <date-part name="month" form="numeric-leading-zeros" suffix="/"/>
+    <date-part name="day" form="numeric-leading-zeros" suffix="/"/>
+    <date-part name="year"/>
+  </date>
+  <terms>
+    <term name="accessed">accessed</term>
+    <term name="and">and</term>
+    <term name="and others">and others</term>
+    <term name="anonymous">anonymous</term>
+    <term name="at">at</term>
+    <term name="available at">available at</term>
+    <term name="by">by</term>
+    <term name="et-al">et al.</term>
+    <term name="from">from</term>
+    <term name="in">in</term>
+    <term name="no date">no date</term>
+    <term name="no date" form="short">n.d.</term>
+    <term name="online">online</term>
+    <term name="presented at">presented at the</term>
+    <term name="retrieved">retrieved</term>
+    <term name="chapter"><single>chapter</single><multiple>chapters</multiple></term>
+    <term name="chapter" form="short"><single>chap.</single><multiple>chaps.</multiple></term>
+    <term name="edition"><single>edition</single><multiple>editions</multiple></term>
+    <term name="edition" form="short">ed.</term>
+    <term name="issue"><single>issue</single><multiple>issues</multiple></term>
+    <term name="issue" form="short"><single>no.</single><multiple>nos.</multiple></term>
+    <term name="number"><single>number</single><multiple>numbers</multiple></term>
+    <term name="number" form="short"><single>no.</single><multiple>nos.</multiple></term>
+    <term name="page"><single>page</single><multiple>pages</multiple></term>
+    <term name="page" form="short"><single>p.</single><multiple>pp.</multiple></term>
+    <term name="volume"><single>volume</single><multiple>volumes</multiple></term>
+    <term name="volume" form="short"><single>vol.</single><multiple>vols.</multiple></term>
+    <term name="editor"><single>editor</single><multiple>editors</multiple></term>
+    <term name="editor" form="short"><single>ed.</single><multiple>eds.</multiple></term>
+    <term name="editor" form="verb">edited by</term>
+    <term name="editor" form="verb-short">ed. by</term>
+    <term name="translator"><single>translator</single><multiple>translators</multiple></term>
+    <term name="translator" form="short"><single>tran.</single><multiple>trans.</multiple></term>
+    <term name="translator" form="verb">translated by</term>
+    <term name="translator" form="verb-short">trans. by</term>
+    <term name="ordinal">th</term>
+    <term name="ordinal-01">st</term>
+    <term name="ordinal-02">nd</term>
+    <term name="ordinal-03">rd</term>
+    <term name="ordinal-11">th</term>
+    <term name="ordinal-12">th</term>
+    <term name="ordinal-13">th</term>
+    <term name="long-ordinal-01">first</term>
+    <term name="long-ordinal-02">second</term>
+    <term name="long-ordinal-03">third</term>
+    <term name="long-ordinal-04">fourth</term>
+    <term name="long-ordinal-05">fifth</term>
+    <term name="long-ordinal-06">sixth</term>
+    <term name="long-ordinal-07">seventh</term>
+    <term name="long-ordinal-08">eighth</term>
+    <term name="long-ordinal-09">ninth</term>
+    <term name="long-ordinal-10">tenth</term>
+    <term name="month-01">January</term>
+    <term name="month-02">February</term>
+    <term name="month-03">March</term>
+    <term name="month-04">April</term>
+    <term name="month-05">May</term>
+    <term name="month-06">June</term>
+    <term name="month-07">July</term>
+    <term name="month-08">August</term>
+    <term name="month-09">September</term>
+    <term name="month-10">October</term>
+    <term name="month-11">November</term>
+    <term name="month-12">December</term>
+    <term name="month-01" form="short">Jan.</term>
+    <term name="month-02" form="short">Feb.</term>
+    <term name="month-03" form="short">Mar.</term>
+    <term name="month-04" form="short">Apr.</term>
+    <term name="month-05" form="short">May</term>
+    <term name="month-06" form="short">Jun.</term>
+    <term name="month-07" form="short">Jul.</term>
+    <term name="month-08" form="short">Aug.</term>
+    <term name="month-09" form="short">Sep.</term>
+    <term name="month-10" form="short">Oct.</term>
+    <term name="month-11" form="short">Nov.</term>
+    <term name="month-12" form="short">Dec.</term>
+  </terms>
+</locale>"#;
+
+/// The dialects that are used if a locale only specifies the language like `de`.
+const PRIMARY_DIALECTS: &[&str] = &[
+    "af-ZA", "ar", "bg-BG", "ca-AD", "cs-CZ", "cy-GB", "da-DK", "de-DE", "el-GR", "en-US", "es-ES",
+    "et-EE", "eu", "fa-IR", "fi-FI", "fr-FR", "he-IL", "hr-HR", "hu-HU", "id-ID", "is-IS", "it-IT",
+    "ja-JP", "km-KH", "ko-KR", "la", "lt-LT", "lv-LV", "mn-MN", "nb-NO", "nl-NL", "nn-NO", "pl-PL",
+    "pt-PT", "ro-RO", "ru-RU", "sk-SK", "sl-SI", "sr-RS", "sv-SE", "th-TH", "tr-TR", "uk-UA",
+    "vi-VN", "zh-CN",
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum TermForm {
+    Long,
+    Short,
+    Verb,
+    VerbShort,
+    Symbol,
+}
+
+impl TermForm {
+    pub fn parse(value: Option<&str>) -> Self {
+        match value {
+            Some("short") => Self::Short,
+            Some("verb") => Self::Verb,
+            Some("verb-short") => Self::VerbShort,
+            Some("symbol") => Self::Symbol,
+            _ => Self::Long,
+        }
+    }
+
+    fn fallback(self) -> Option<Self> {
+        match self {
+            Self::Long => None,
+            Self::Short | Self::Verb => Some(Self::Long),
+            Self::VerbShort => Some(Self::Verb),
+            Self::Symbol => Some(Self::Short),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum DateForm {
+    Text,
+    Numeric,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DateLayout {
+    pub parts: Vec<DatePart>,
+    pub delimiter: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DatePart {
+    pub name: DatePartName,
+    pub form: Option<String>,
+    pub display: Display,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DatePartName {
+    Year,
+    Month,
+    Day,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Term {
+    single: String,
+    multiple: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Locale {
+    pub lang: Option<String>,
+    terms: FxHashMap<(String, TermForm), Term>,
+    dates: FxHashMap<DateForm, DateLayout>,
+}
+
+impl Locale {
+    /// Converts a `cs:locale` element of a style or a locale file.
+    pub fn parse(element: &xml::Element) -> Self {
+        let mut locale = Self {
+            lang: element.attribute("xml:lang").map(String::from),
+            ..Self::default()
+        };
+
+        for date in element.children_named("date") {
+            let form = match date.attribute("form") {
+                Some("text") => DateForm::Text,
+                Some("numeric") => DateForm::Numeric,
+                _ => continue,
+            };
+
+            locale.dates.insert(form, parse_date_layout(date));
+        }
+
+        let terms = element
+            .children_named("terms")
+            .flat_map(|terms| terms.children_named("term"));
+
+        for term in terms {
+            let Some(name) = term.attribute("name") else {
+                continue;
+            };
+
+            let form = TermForm::parse(term.attribute("form"));
+            let text = |element: &xml::Element| element.text.trim().to_string();
+            let single = term.child("single").map_or_else(|| text(term), text);
+            let multiple = term.child("multiple").map_or_else(|| single.clone(), text);
+            locale
+                .terms
+                .insert((name.into(), form), Term { single, multiple });
+        }
+
+        locale
+    }
+
+    pub fn fallback() -> Self {
+        let root = xml::parse(FALLBACK_LOCALE).unwrap();
+        Self::parse(&root)
+    }
+
+    /// Reads `locales-<lang>.xml` from the given directory.
+    pub fn load(dir: &Path, lang: &str) -> Option<Self> {
+        let text = std::fs::read_to_string(dir.join(format!("locales-{lang}.xml"))).ok()?;
+        let root = xml::parse(&text).ok()?;
+        (root.name == "locale").then(|| Self::parse(&root))
+    }
+
+    /// Combines the locales of a style, the locale files and the fallback locale into one locale.
+    ///
+    /// Locales of the style that match the language take precedence over the locale files.
+    pub fn resolve(style_locales: &[Self], lang: &str, dir: Option<&Path>) -> Self {
+        let language = lang.split('-').next().unwrap_or(lang);
+        let mut candidates: Vec<&Self> = Vec::new();
+        candidates.extend(
+            style_locales
+                .iter()
+                .filter(|locale| locale.lang.as_deref() == Some(lang)),
+        );
+
+        candidates.extend(style_locales.iter().filter(|locale| {
+            locale.lang.as_deref() != Some(lang) && locale.lang.as_deref() == Some(language)
+        }));
+
+        candidates.extend(style_locales.iter().filter(|locale| locale.lang.is_none()));
+
+        let mut result = Self {
+            lang: Some(lang.into()),
+            ..Self::default()
+        };
+
+        for locale in candidates {
+            result.merge(locale);
+        }
+
+        if let Some(dir) = dir {
+            let dialect = PRIMARY_DIALECTS
+                .iter()
+                .find(|dialect| dialect.split('-').next() == Some(language));
+
+            let mut files = vec![lang];
+            files.extend(dialect.copied());
+            files.push("en-US");
+            for file in files {
+                if let Some(locale) = Self::load(dir, file) {
+                    result.merge(&locale);
+                    break;
+                }
+            }
+        }
+
+        result.merge(&Self::fallback());
+        result
+    }
+
+    /// Adds the terms and date formats of the other locale that are not defined yet.
+    fn merge(&mut self, other: &Self) {
+        for (key, term) in &other.terms {
+            self.terms
+                .entry(key.clone())
+                .or_insert_with(|| term.clone());
+        }
+
+        for (form, layout) in &other.dates {
+            self.dates.entry(*form).or_insert_with(|| layout.clone());
+        }
+    }
+
+    /// Looks up a term. Missing forms fall back to the next longer form.
+    pub fn term(&self, name: &str, form: TermForm, plural: bool) -> Option<&str> {
+        let mut form = Some(form);
+        while let Some(current) = form {
+            if let Some(term) = self.terms.get(&(name.to_string(), current)) {
+                let text = if plural { &term.multiple } else { &term.single };
+                return Some(text);
+            }
+
+            form = current.fallback();
+        }
+
+        None
+    }
+
+    pub fn date(&self, form: DateForm) -> Option<&DateLayout> {
+        self.dates.get(&form)
+    }
+}
+
+pub fn parse_date_layout(element: &xml::Element) -> DateLayout {
+    let parts = element
+        .children_named("date-part")
+        .filter_map(|part| {
+            let name = match part.attribute("name")? {
+                "year" => DatePartName::Year,
+                "month" => DatePartName::Month,
+                "day" => DatePartName::Day,
+                _ => return None,
+            };
+
+            Some(DatePart {
+                name,
+                form: part.attribute("form").map(String::from),
+                display: parse_display(part),
+            })
+        })
+        .collect();
+
+    DateLayout {
+        parts,
+        delimiter: element.attribute("delimiter").unwrap_or_default().into(),
+    }
+}
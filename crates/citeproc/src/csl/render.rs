@@ -0,0 +1,770 @@
+use human_name::Name as PersonName;
+use rustc_hash::FxHashSet;
+use titlecase::titlecase;
+
+use crate::output::Inline;
+
+use super::{
+    locale::{DateLayout, DatePart, DatePartName, Locale, TermForm},
+    style::{
+        Branch, Condition, Date, DatePartsFilter, DelimiterPrecedes, Display, Element, Group,
+        Label, LabelPlural, Match, Name, NameAnd, NameForm, NameSortOrder, Names, Number,
+        NumberForm, StyleData, Text, TextCase, TextSource,
+    },
+    variables::{DateValue, Variables},
+};
+
+/// The maximum depth of nested macro calls to guard against recursive macros.
+const MAX_MACRO_DEPTH: usize = 32;
+
+/// The output of a rendering element.
+///
+/// Groups are suppressed if they call variables but all of them are empty.
+#[derive(Debug, Default)]
+struct Output {
+    inlines: Vec<Inline>,
+    calls_variable: bool,
+    has_variable: bool,
+}
+
+impl Output {
+    fn variable(found: bool) -> Self {
+        Self {
+            inlines: Vec::new(),
+            calls_variable: true,
+            has_variable: found,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.inlines.is_empty()
+    }
+
+    /// Appends regular text and collapses duplicate punctuation at the boundary.
+    fn push_str(&mut self, text: &str) {
+        let mut text = text;
+        if let (Some(last), Some(first)) = (self.last_char(), text.chars().next()) {
+            let duplicate = (first == '.' && matches!(last, '.' | '?' | '!'))
+                || (matches!(first, ',' | ';' | ':') && first == last);
+
+            if duplicate {
+                text = &text[first.len_utf8()..];
+            }
+        }
+
+        if text.is_empty() {
+            return;
+        }
+
+        match self.inlines.last_mut() {
+            Some(Inline::Regular(last)) => last.push_str(text),
+            _ => self.inlines.push(Inline::Regular(text.into())),
+        }
+    }
+
+    fn push(&mut self, inline: Inline) {
+        match inline {
+            Inline::Regular(text) => self.push_str(&text),
+            inline => self.inlines.push(inline),
+        }
+    }
+
+    fn append(&mut self, other: Self) {
+        self.calls_variable |= other.calls_variable;
+        self.has_variable |= other.has_variable;
+        for inline in other.inlines {
+            self.push(inline);
+        }
+    }
+
+    fn last_char(&self) -> Option<char> {
+        match self.inlines.last()? {
+            Inline::Regular(text) | Inline::Italic(text) => text.chars().last(),
+            Inline::Quoted(_) => Some('"'),
+            Inline::Link { alt, .. } => alt.chars().last(),
+        }
+    }
+
+    fn text(&self) -> String {
+        self.inlines
+            .iter()
+            .map(|inline| match inline {
+                Inline::Regular(text) | Inline::Italic(text) => text.clone(),
+                Inline::Quoted(text) => format!("\"{text}\""),
+                Inline::Link { alt, .. } => alt.clone(),
+            })
+            .collect()
+    }
+}
+
+/// The options of a `cs:names` element that are inherited by the short form inside of `cs:substitute`.
+#[derive(Clone, Copy)]
+struct NamesContext<'a> {
+    name: &'a Name,
+    et_al: Option<&'a str>,
+    label: Option<(&'a Label, bool)>,
+}
+
+pub struct Renderer<'a> {
+    style: &'a StyleData,
+    locale: &'a Locale,
+    variables: &'a Variables,
+    suppressed: FxHashSet<String>,
+    accessed: Vec<String>,
+    depth: usize,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(style: &'a StyleData, locale: &'a Locale, variables: &'a Variables) -> Self {
+        Self {
+            style,
+            locale,
+            variables,
+            suppressed: FxHashSet::default(),
+            accessed: Vec::new(),
+            depth: 0,
+        }
+    }
+
+    pub fn render(mut self) -> Vec<Inline> {
+        let layout = &self.style.layout;
+        let output = self.elements(&layout.children, &layout.delimiter);
+        self.decorate(output, &layout.display).inlines
+    }
+
+    fn elements(&mut self, elements: &[Element], delimiter: &str) -> Output {
+        let mut output = Output::default();
+        for element in elements {
+            let child = self.element(element);
+            if !child.is_empty() && !output.is_empty() {
+                output.push_str(delimiter);
+            }
+
+            output.append(child);
+        }
+
+        output
+    }
+
+    fn element(&mut self, element: &Element) -> Output {
+        match element {
+            Element::Text(text) => self.text(text),
+            Element::Date(date) => self.date(date),
+            Element::Number(number) => self.number(number),
+            Element::Names(names) => self.names(names, None),
+            Element::Label(label) => {
+                let plural = self
+                    .text_variable(&label.variable)
+                    .as_deref()
+                    .map(is_plural);
+                self.label(label, &label.variable, plural)
+            }
+            Element::Group(group) => self.group(group),
+            Element::Choose(branches) => self.choose(branches),
+        }
+    }
+
+    fn text(&mut self, text: &Text) -> Output {
+        let output = match &text.source {
+            TextSource::Variable(name) => {
+                let Some(value) = self.text_variable(name) else {
+                    return Output::variable(false);
+                };
+
+                let mut output = Output::variable(true);
+                match name.as_str() {
+                    "URL" => output.push(Inline::Link {
+                        url: value.clone(),
+                        alt: value,
+                    }),
+                    "DOI" => {
+                        // Styles that print the resolver as prefix expect it to be part of the link.
+                        if text.display.prefix.starts_with("http") {
+                            let url = format!("{}{value}", text.display.prefix);
+                            output.push(Inline::Link {
+                                url: url.clone(),
+                                alt: url,
+                            });
+
+                            let display = Display {
+                                prefix: String::new(),
+                                ..text.display.clone()
+                            };
+
+                            return self.decorate(output, &display);
+                        }
+
+                        output.push(Inline::Link {
+                            url: format!("https://doi.org/{value}"),
+                            alt: value,
+                        });
+                    }
+                    "page" | "locator" => output.push_str(&format_range(&value)),
+                    _ => output.push_str(&value),
+                };
+
+                output
+            }
+            TextSource::Macro(name) => {
+                let Some(elements) = self.style.macros.get(name) else {
+                    return Output::default();
+                };
+
+                if self.depth >= MAX_MACRO_DEPTH {
+                    return Output::default();
+                }
+
+                self.depth += 1;
+                let output = self.elements(elements, "");
+                self.depth -= 1;
+                output
+            }
+            TextSource::Term { name, form, plural } => {
+                let mut output = Output::default();
+                output.push_str(self.locale.term(name, *form, *plural).unwrap_or_default());
+                output
+            }
+            TextSource::Value(value) => {
+                let mut output = Output::default();
+                output.push_str(value);
+                output
+            }
+        };
+
+        self.decorate(output, &text.display)
+    }
+
+    fn date(&mut self, date: &Date) -> Output {
+        let Some(value) = self.date_variable(&date.variable) else {
+            return Output::variable(false);
+        };
+
+        let mut output = Output::variable(true);
+        match value {
+            DateValue::Literal(text) => output.push_str(&text),
+            DateValue::Parts { year, month, day } => {
+                let localized = date.localized.and_then(|form| self.locale.date(form));
+                let layout = localized
+                    .map_or_else(|| date.layout.clone(), |layout| localize_date(layout, date));
+
+                let mut rendered = Output::default();
+                for part in &layout.parts {
+                    let text = match part.name {
+                        DatePartName::Year => match part.form.as_deref() {
+                            Some("short") => format!("{:02}", year.rem_euclid(100)),
+                            _ => year.to_string(),
+                        },
+                        DatePartName::Month => {
+                            let Some(month) = month else {
+                                continue;
+                            };
+
+                            match part.form.as_deref() {
+                                Some("numeric") => month.to_string(),
+                                Some("numeric-leading-zeros") => format!("{month:02}"),
+                                form => {
+                                    let form = TermForm::parse(form);
+                                    let name = format!("month-{month:02}");
+                                    let term = self.locale.term(&name, form, false);
+                                    term.map_or_else(|| month.to_string(), String::from)
+                                }
+                            }
+                        }
+                        DatePartName::Day => {
+                            let (Some(day), Some(_)) = (day, month) else {
+                                continue;
+                            };
+
+                            match part.form.as_deref() {
+                                Some("numeric-leading-zeros") => format!("{day:02}"),
+                                Some("ordinal") => format!("{day}{}", self.ordinal_suffix(day)),
+                                _ => day.to_string(),
+                            }
+                        }
+                    };
+
+                    let mut part_output = Output::default();
+                    part_output.push_str(&text);
+                    let part_output = self.decorate(part_output, &part.display);
+                    if !rendered.is_empty() {
+                        rendered.push_str(&layout.delimiter);
+                    }
+
+                    rendered.append(part_output);
+                }
+
+                output.append(rendered);
+            }
+        };
+
+        self.decorate(output, &date.display)
+    }
+
+    fn number(&mut self, number: &Number) -> Output {
+        let Some(value) = self.text_variable(&number.variable) else {
+            return Output::variable(false);
+        };
+
+        let format = |renderer: &Self, value: u32| match number.form {
+            NumberForm::Numeric => value.to_string(),
+            NumberForm::Ordinal => format!("{value}{}", renderer.ordinal_suffix(value)),
+            NumberForm::LongOrdinal => (1..=10)
+                .contains(&value)
+                .then(|| {
+                    let name = format!("long-ordinal-{value:02}");
+                    renderer.locale.term(&name, TermForm::Long, false)
+                })
+                .flatten()
+                .map_or_else(
+                    || format!("{value}{}", renderer.ordinal_suffix(value)),
+                    String::from,
+                ),
+            NumberForm::Roman => roman(value),
+        };
+
+        let text = match parse_range(&value) {
+            Some((start, None)) => format(self, start),
+            Some((start, Some(end))) => format!("{}–{}", format(self, start), format(self, end)),
+            None => value,
+        };
+
+        let mut output = Output::variable(true);
+        output.push_str(&text);
+        self.decorate(output, &number.display)
+    }
+
+    fn label(&mut self, label: &Label, variable: &str, plural: Option<bool>) -> Output {
+        let Some(plural) = plural else {
+            return Output::default();
+        };
+
+        let plural = match label.plural {
+            LabelPlural::Contextual => plural,
+            LabelPlural::Always => true,
+            LabelPlural::Never => false,
+        };
+
+        let term = match variable {
+            "page" | "number-of-pages" => "page",
+            "chapter-number" => "chapter",
+            "number-of-volumes" => "volume",
+            name => name,
+        };
+
+        let mut output = Output::default();
+        output.push_str(
+            self.locale
+                .term(term, label.form, plural)
+                .unwrap_or_default(),
+        );
+        self.decorate(output, &label.display)
+    }
+
+    fn group(&mut self, group: &Group) -> Output {
+        let output = self.elements(&group.children, &group.delimiter);
+        if output.calls_variable && !output.has_variable {
+            return Output::variable(false);
+        }
+
+        self.decorate(output, &group.display)
+    }
+
+    fn choose(&mut self, branches: &[Branch]) -> Output {
+        let branch = branches.iter().find(|branch| {
+            let mut results = branch
+                .conditions
+                .iter()
+                .map(|condition| self.evaluate(condition));
+
+            match branch.match_ {
+                Match::All => results.all(|result| result),
+                Match::Any => results.any(|result| result),
+                Match::None => !results.any(|result| result),
+            }
+        });
+
+        branch.map_or_else(Output::default, |branch| {
+            self.elements(&branch.children, "")
+        })
+    }
+
+    fn evaluate(&self, condition: &Condition) -> bool {
+        match condition {
+            Condition::Type(kind) => self.variables.kind == kind,
+            Condition::Variable(name) => {
+                !self.suppressed.contains(name) && !self.variables.is_empty(name)
+            }
+            Condition::IsNumeric(name) => self
+                .variables
+                .text
+                .get(name.as_str())
+                .is_some_and(|value| parse_range(value).is_some()),
+            Condition::Never => false,
+        }
+    }
+
+    fn names(&mut self, names: &Names, parent: Option<NamesContext>) -> Output {
+        let context = match (&names.name, parent) {
+            (None, Some(parent)) => parent,
+            (name, _) => NamesContext {
+                name: name.as_ref().unwrap_or(&self.style.default_name),
+                et_al: names.et_al.as_deref(),
+                label: names.label.as_ref().map(|label| (label, names.label_first)),
+            },
+        };
+
+        let mut output = Output::variable(false);
+        for variable in &names.variables {
+            let Some(list) = self.names_variable(variable) else {
+                continue;
+            };
+
+            let mut rendered = self.name_list(&list, context);
+            if let Some((label, label_first)) = context.label {
+                let label = self.label(label, variable, Some(list.len() > 1));
+                if label_first {
+                    let mut with_label = label;
+                    with_label.append(rendered);
+                    rendered = with_label;
+                } else {
+                    rendered.append(label);
+                }
+            }
+
+            if !output.is_empty() {
+                output.push_str(&names.delimiter);
+            }
+
+            output.append(rendered);
+            output.has_variable = true;
+        }
+
+        if output.is_empty() {
+            for element in &names.substitute {
+                let start = self.accessed.len();
+                let substitute = match element {
+                    Element::Names(child) => self.names(child, Some(context)),
+                    element => self.element(element),
+                };
+
+                if !substitute.is_empty() {
+                    let accessed = self.accessed.split_off(start);
+                    self.suppressed.extend(accessed);
+                    output.append(substitute);
+                    break;
+                }
+            }
+        }
+
+        self.decorate(output, &names.display)
+    }
+
+    fn name_list(&self, list: &[PersonName], context: NamesContext) -> Output {
+        let options = context.name;
+        let shown = options
+            .et_al_min
+            .zip(options.et_al_use_first)
+            .filter(|(min, first)| list.len() >= *min && *first < list.len())
+            .map(|(_, first)| first.max(1));
+
+        let names = &list[..shown.unwrap_or(list.len())];
+        let mut output = Output::default();
+        if options.form == NameForm::Count {
+            output.push_str(&names.len().to_string());
+            return output;
+        }
+
+        let mut text = String::new();
+        let mut previous_inverted = false;
+        for (i, name) in names.iter().enumerate() {
+            let inverted = match options.name_as_sort_order {
+                Some(NameSortOrder::All) => true,
+                Some(NameSortOrder::First) => i == 0,
+                None => false,
+            };
+
+            if i > 0 {
+                let is_last = i == names.len() - 1 && shown.is_none();
+                match options.and.filter(|_| is_last) {
+                    Some(and) => {
+                        let precedes = self.delimiter_precedes(
+                            options.delimiter_precedes_last,
+                            names.len() >= 3,
+                            previous_inverted,
+                        );
+
+                        text.push_str(if precedes { &options.delimiter } else { " " });
+                        text.push_str(match and {
+                            NameAnd::Text => self
+                                .locale
+                                .term("and", TermForm::Long, false)
+                                .unwrap_or("and"),
+                            NameAnd::Symbol => "&",
+                        });
+
+                        text.push(' ');
+                    }
+                    None => text.push_str(&options.delimiter),
+                };
+            }
+
+            text.push_str(&format_name(name, options, inverted));
+            previous_inverted = inverted;
+        }
+
+        if shown.is_some() {
+            let precedes = self.delimiter_precedes(
+                options.delimiter_precedes_et_al,
+                names.len() >= 2,
+                previous_inverted,
+            );
+
+            text.push_str(if precedes { &options.delimiter } else { " " });
+            let term = context.et_al.unwrap_or("et-al");
+            text.push_str(
+                self.locale
+                    .term(term, TermForm::Long, false)
+                    .unwrap_or("et al."),
+            );
+        }
+
+        output.push_str(&text);
+        self.decorate(output, &options.display)
+    }
+
+    fn delimiter_precedes(
+        &self,
+        precedes: DelimiterPrecedes,
+        contextual: bool,
+        previous_inverted: bool,
+    ) -> bool {
+        match precedes {
+            DelimiterPrecedes::Contextual => contextual,
+            DelimiterPrecedes::AfterInvertedName => previous_inverted,
+            DelimiterPrecedes::Always => true,
+            DelimiterPrecedes::Never => false,
+        }
+    }
+
+    fn ordinal_suffix(&self, value: u32) -> &str {
+        let mut names = Vec::new();
+        if value % 100 >= 10 {
+            names.push(format!("ordinal-{:02}", value % 100));
+        }
+
+        names.push(format!("ordinal-{:02}", value % 10));
+        names.push("ordinal".into());
+        names
+            .iter()
+            .find_map(|name| self.locale.term(name, TermForm::Long, false))
+            .unwrap_or_default()
+    }
+
+    fn text_variable(&mut self, name: &str) -> Option<String> {
+        let value = self.variables.text.get(name)?;
+        self.access(name).then(|| value.clone())
+    }
+
+    fn names_variable(&mut self, name: &str) -> Option<Vec<PersonName>> {
+        let value = self.variables.names.get(name)?;
+        self.access(name).then(|| value.clone())
+    }
+
+    fn date_variable(&mut self, name: &str) -> Option<DateValue> {
+        let value = self.variables.dates.get(name)?;
+        self.access(name).then(|| value.clone())
+    }
+
+    /// Records the access of a non-empty variable. Returns `false` if the variable has been substituted before.
+    fn access(&mut self, name: &str) -> bool {
+        if self.suppressed.contains(name) {
+            return false;
+        }
+
+        self.accessed.push(name.into());
+        true
+    }
+
+    /// Applies the formatting and the affixes of an element to its output.
+    fn decorate(&self, output: Output, display: &Display) -> Output {
+        if output.is_empty() {
+            return output;
+        }
+
+        let mut inlines = output.inlines;
+        for inline in &mut inlines {
+            if let Inline::Regular(text) | Inline::Italic(text) | Inline::Quoted(text) = inline {
+                if display.strip_periods {
+                    text.retain(|c| c != '.');
+                }
+
+                if let Some(case) = display.text_case {
+                    *text = apply_case(text, case);
+                }
+            }
+        }
+
+        let mut result = Output {
+            inlines: Vec::new(),
+            calls_variable: output.calls_variable,
+            has_variable: output.has_variable,
+        };
+
+        result.push_str(&display.prefix);
+        let content = Output {
+            inlines,
+            ..Output::default()
+        };
+
+        if display.italic {
+            result.push(Inline::Italic(content.text()));
+        } else if display.quotes {
+            result.push(Inline::Quoted(content.text()));
+        } else {
+            result.append(content);
+        }
+
+        result.push_str(&display.suffix);
+        result
+    }
+}
+
+/// Selects the parts of a localized date and applies the overrides of the style.
+fn localize_date(layout: &DateLayout, date: &Date) -> DateLayout {
+    let parts = layout
+        .parts
+        .iter()
+        .filter(|part| match date.date_parts {
+            DatePartsFilter::YearMonthDay => true,
+            DatePartsFilter::YearMonth => part.name != DatePartName::Day,
+            DatePartsFilter::Year => part.name == DatePartName::Year,
+        })
+        .map(|part| {
+            let form = date
+                .layout
+                .parts
+                .iter()
+                .find(|other| other.name == part.name)
+                .and_then(|other| other.form.clone())
+                .or_else(|| part.form.clone());
+
+            DatePart {
+                form,
+                ..part.clone()
+            }
+        })
+        .collect();
+
+    DateLayout {
+        parts,
+        delimiter: layout.delimiter.clone(),
+    }
+}
+
+fn format_name(name: &PersonName, options: &Name, inverted: bool) -> String {
+    let family = name.surname();
+    if options.form == NameForm::Short {
+        return family.into();
+    }
+
+    let given = match (&options.initialize_with, name.given_name()) {
+        (Some(separator), _) => initials(name, separator),
+        (None, Some(given)) => match name.middle_name() {
+            Some(middle) => format!("{given} {middle}"),
+            None => given.into(),
+        },
+        (None, None) => initials(name, ". "),
+    };
+
+    if inverted {
+        format!("{family}{}{given}", options.sort_separator)
+    } else {
+        format!("{given} {family}")
+    }
+}
+
+fn initials(name: &PersonName, separator: &str) -> String {
+    let initials: String = name
+        .initials()
+        .chars()
+        .map(|c| format!("{c}{separator}"))
+        .collect();
+
+    initials.trim_end().into()
+}
+
+/// Checks whether a number variable contains multiple values like a page range.
+fn is_plural(value: &str) -> bool {
+    value.contains(['-', '–', '&', ',']) || value.contains(" and ")
+}
+
+/// Parses a numeric value which is either a single number or a range of numbers.
+fn parse_range(value: &str) -> Option<(u32, Option<u32>)> {
+    let value = value.trim();
+    if let Ok(number) = value.parse() {
+        return Some((number, None));
+    }
+
+    let (start, end) = value.split_once(['-', '–'])?;
+    let start = start.trim().parse().ok()?;
+    let end = end.trim_start_matches(['-', '–']).trim().parse().ok()?;
+    Some((start, Some(end)))
+}
+
+fn format_range(value: &str) -> String {
+    match parse_range(value) {
+        Some((start, Some(end))) => format!("{start}–{end}"),
+        _ => value.into(),
+    }
+}
+
+fn roman(mut value: u32) -> String {
+    const NUMERALS: &[(u32, &str)] = &[
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+
+    let mut result = String::new();
+    for (number, numeral) in NUMERALS {
+        while value >= *number {
+            result.push_str(numeral);
+            value -= number;
+        }
+    }
+
+    result
+}
+
+fn apply_case(text: &str, case: TextCase) -> String {
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+        chars.next().map_or_else(String::new, |first| {
+            first.to_uppercase().chain(chars).collect()
+        })
+    };
+
+    match case {
+        TextCase::Lowercase => text.to_lowercase(),
+        TextCase::Uppercase => text.to_uppercase(),
+        TextCase::CapitalizeFirst => capitalize(text),
+        TextCase::CapitalizeAll => text
+            .split(' ')
+            .map(capitalize)
+            .collect::<Vec<_>>()
+            .join(" "),
+        TextCase::Sentence if text.chars().any(char::is_lowercase) => capitalize(text),
+        TextCase::Sentence => capitalize(&text.to_lowercase()),
+        TextCase::Title => titlecase(text),
+    }
+}
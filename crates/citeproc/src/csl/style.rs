@@ -0,0 +1,525 @@
+use rustc_hash::FxHashMap;
+
+use super::{
+    locale::{parse_date_layout, DateForm, DateLayout, Locale, TermForm},
+    xml,
+};
+
+/// The parts of a CSL style that are needed to render a bibliography entry.
+#[derive(Debug, Clone)]
+pub struct StyleData {
+    pub default_locale: Option<String>,
+    pub locales: Vec<Locale>,
+    pub macros: FxHashMap<String, Vec<Element>>,
+    pub layout: Layout,
+    /// The options of `cs:names` elements without a `cs:name` child.
+    pub default_name: Name,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Layout {
+    pub display: Display,
+    pub delimiter: String,
+    pub children: Vec<Element>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Element {
+    Text(Text),
+    Date(Date),
+    Number(Number),
+    Names(Box<Names>),
+    Label(Label),
+    Group(Group),
+    Choose(Vec<Branch>),
+}
+
+/// Affixes and formatting that can be applied to every rendering element.
+#[derive(Debug, Clone, Default)]
+pub struct Display {
+    pub prefix: String,
+    pub suffix: String,
+    pub italic: bool,
+    pub quotes: bool,
+    pub strip_periods: bool,
+    pub text_case: Option<TextCase>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TextCase {
+    Lowercase,
+    Uppercase,
+    CapitalizeFirst,
+    CapitalizeAll,
+    Sentence,
+    Title,
+}
+
+#[derive(Debug, Clone)]
+pub struct Text {
+    pub source: TextSource,
+    pub display: Display,
+}
+
+#[derive(Debug, Clone)]
+pub enum TextSource {
+    Variable(String),
+    Macro(String),
+    Term {
+        name: String,
+        form: TermForm,
+        plural: bool,
+    },
+    Value(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Date {
+    pub variable: String,
+    pub localized: Option<DateForm>,
+    pub date_parts: DatePartsFilter,
+    pub layout: DateLayout,
+    pub display: Display,
+}
+
+/// The date parts that are rendered by a localized date.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DatePartsFilter {
+    YearMonthDay,
+    YearMonth,
+    Year,
+}
+
+#[derive(Debug, Clone)]
+pub struct Number {
+    pub variable: String,
+    pub form: NumberForm,
+    pub display: Display,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NumberForm {
+    Numeric,
+    Ordinal,
+    LongOrdinal,
+    Roman,
+}
+
+#[derive(Debug, Clone)]
+pub struct Names {
+    pub variables: Vec<String>,
+    pub name: Option<Name>,
+    pub et_al: Option<String>,
+    pub label: Option<Label>,
+    pub label_first: bool,
+    pub substitute: Vec<Element>,
+    pub delimiter: String,
+    pub display: Display,
+}
+
+#[derive(Debug, Clone)]
+pub struct Name {
+    pub and: Option<NameAnd>,
+    pub delimiter: String,
+    pub delimiter_precedes_last: DelimiterPrecedes,
+    pub delimiter_precedes_et_al: DelimiterPrecedes,
+    pub et_al_min: Option<usize>,
+    pub et_al_use_first: Option<usize>,
+    pub form: NameForm,
+    pub initialize_with: Option<String>,
+    pub name_as_sort_order: Option<NameSortOrder>,
+    pub sort_separator: String,
+    pub display: Display,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NameAnd {
+    Text,
+    Symbol,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DelimiterPrecedes {
+    Contextual,
+    AfterInvertedName,
+    Always,
+    Never,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NameForm {
+    Long,
+    Short,
+    Count,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NameSortOrder {
+    First,
+    All,
+}
+
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub variable: String,
+    pub form: TermForm,
+    pub plural: LabelPlural,
+    pub display: Display,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LabelPlural {
+    Contextual,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone)]
+pub struct Group {
+    pub delimiter: String,
+    pub children: Vec<Element>,
+    pub display: Display,
+}
+
+/// A branch of `cs:choose`. The `cs:else` branch has no conditions.
+#[derive(Debug, Clone)]
+pub struct Branch {
+    pub conditions: Vec<Condition>,
+    pub match_: Match,
+    pub children: Vec<Element>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Condition {
+    Type(String),
+    Variable(String),
+    IsNumeric(String),
+    /// Conditions like `position` or `disambiguate` that never apply to a bibliography entry.
+    Never,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Match {
+    All,
+    Any,
+    None,
+}
+
+/// The options for names that can be set on `cs:style` and `cs:bibliography` and are inherited by `cs:name`.
+const INHERITABLE_NAME_OPTIONS: &[(&str, &str)] = &[
+    ("and", "and"),
+    ("delimiter-precedes-et-al", "delimiter-precedes-et-al"),
+    ("delimiter-precedes-last", "delimiter-precedes-last"),
+    ("et-al-min", "et-al-min"),
+    ("et-al-use-first", "et-al-use-first"),
+    ("initialize-with", "initialize-with"),
+    ("name-as-sort-order", "name-as-sort-order"),
+    ("sort-separator", "sort-separator"),
+    ("name-form", "form"),
+    ("name-delimiter", "delimiter"),
+];
+
+impl StyleData {
+    /// Converts the root element of a CSL style. Returns `None` if the element is not a style.
+    ///
+    /// The bibliography layout is used if the style has one and the citation layout otherwise.
+    pub fn parse(root: &xml::Element) -> Option<Self> {
+        if root.name != "style" {
+            return None;
+        }
+
+        let section = root
+            .child("bibliography")
+            .or_else(|| root.child("citation"))?;
+
+        let mut inherited = NameOptions::default();
+        inherited.inherit(root);
+        inherited.inherit(section);
+
+        let parser = Parser { inherited };
+        let macros = root
+            .children_named("macro")
+            .filter_map(|element| {
+                let name = element.attribute("name")?;
+                Some((name.into(), parser.children(element)))
+            })
+            .collect();
+
+        let layout = section.child("layout")?;
+        let layout = Layout {
+            display: parse_display(layout),
+            delimiter: layout.attribute("delimiter").unwrap_or_default().into(),
+            children: parser.children(layout),
+        };
+
+        let locales = root.children_named("locale").map(Locale::parse).collect();
+        Some(Self {
+            default_locale: root.attribute("default-locale").map(String::from),
+            locales,
+            macros,
+            layout,
+            default_name: parser.name(&xml::Element::default()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct NameOptions {
+    attributes: FxHashMap<&'static str, String>,
+}
+
+impl NameOptions {
+    fn inherit(&mut self, element: &xml::Element) {
+        for (source, target) in INHERITABLE_NAME_OPTIONS {
+            if let Some(value) = element.attribute(source) {
+                self.attributes.insert(target, value.into());
+            }
+        }
+
+        if let Some(delimiter) = element.attribute("names-delimiter") {
+            self.attributes.insert("names-delimiter", delimiter.into());
+        }
+    }
+}
+
+struct Parser {
+    inherited: NameOptions,
+}
+
+impl Parser {
+    fn children(&self, element: &xml::Element) -> Vec<Element> {
+        element
+            .children
+            .iter()
+            .filter_map(|child| self.element(child))
+            .collect()
+    }
+
+    fn element(&self, element: &xml::Element) -> Option<Element> {
+        let display = parse_display(element);
+        Some(match element.name.as_str() {
+            "text" => Element::Text(Text {
+                source: parse_text_source(element)?,
+                display,
+            }),
+            "date" => Element::Date(parse_date(element)?),
+            "number" => Element::Number(Number {
+                variable: element.attribute("variable")?.into(),
+                form: match element.attribute("form") {
+                    Some("ordinal") => NumberForm::Ordinal,
+                    Some("long-ordinal") => NumberForm::LongOrdinal,
+                    Some("roman") => NumberForm::Roman,
+                    _ => NumberForm::Numeric,
+                },
+                display,
+            }),
+            "names" => Element::Names(Box::new(self.names(element)?)),
+            "label" => Element::Label(parse_label(element)?),
+            "group" => Element::Group(Group {
+                delimiter: element.attribute("delimiter").unwrap_or_default().into(),
+                children: self.children(element),
+                display,
+            }),
+            "choose" => Element::Choose(
+                element
+                    .children
+                    .iter()
+                    .map(|branch| self.branch(branch))
+                    .collect(),
+            ),
+            _ => return None,
+        })
+    }
+
+    fn names(&self, element: &xml::Element) -> Option<Names> {
+        let variables = element
+            .attribute("variable")?
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+
+        let mut name = None;
+        let mut et_al = None;
+        let mut label = None;
+        let mut label_first = false;
+        let mut substitute = Vec::new();
+        for child in &element.children {
+            match child.name.as_str() {
+                "name" => name = Some(self.name(child)),
+                "et-al" => et_al = Some(child.attribute("term").unwrap_or("et-al").into()),
+                "label" => {
+                    label = parse_label(child);
+                    label_first = name.is_none() && element.child("name").is_some();
+                }
+                "substitute" => substitute = self.children(child),
+                _ => {}
+            }
+        }
+
+        let delimiter = element
+            .attribute("delimiter")
+            .or_else(|| {
+                self.inherited
+                    .attributes
+                    .get("names-delimiter")
+                    .map(String::as_str)
+            })
+            .unwrap_or_default()
+            .into();
+
+        Some(Names {
+            variables,
+            name,
+            et_al,
+            label,
+            label_first,
+            substitute,
+            delimiter,
+            display: parse_display(element),
+        })
+    }
+
+    /// Creates the options of `cs:name` including the options that are inherited from the style.
+    fn name(&self, element: &xml::Element) -> Name {
+        let attribute = |name: &str| {
+            element
+                .attribute(name)
+                .or_else(|| self.inherited.attributes.get(name).map(String::as_str))
+        };
+
+        let precedes = |name: &str| match attribute(name) {
+            Some("after-inverted-name") => DelimiterPrecedes::AfterInvertedName,
+            Some("always") => DelimiterPrecedes::Always,
+            Some("never") => DelimiterPrecedes::Never,
+            _ => DelimiterPrecedes::Contextual,
+        };
+
+        let number = |name: &str| attribute(name).and_then(|value| value.parse().ok());
+
+        Name {
+            and: match attribute("and") {
+                Some("text") => Some(NameAnd::Text),
+                Some("symbol") => Some(NameAnd::Symbol),
+                _ => None,
+            },
+            delimiter: attribute("delimiter").unwrap_or(", ").into(),
+            delimiter_precedes_last: precedes("delimiter-precedes-last"),
+            delimiter_precedes_et_al: precedes("delimiter-precedes-et-al"),
+            et_al_min: number("et-al-min"),
+            et_al_use_first: number("et-al-use-first"),
+            form: match attribute("form") {
+                Some("short") => NameForm::Short,
+                Some("count") => NameForm::Count,
+                _ => NameForm::Long,
+            },
+            initialize_with: attribute("initialize-with").map(String::from),
+            name_as_sort_order: match attribute("name-as-sort-order") {
+                Some("first") => Some(NameSortOrder::First),
+                Some("all") => Some(NameSortOrder::All),
+                _ => None,
+            },
+            sort_separator: attribute("sort-separator").unwrap_or(", ").into(),
+            display: parse_display(element),
+        }
+    }
+
+    fn branch(&self, element: &xml::Element) -> Branch {
+        let mut conditions = Vec::new();
+        for (key, value) in &element.attributes {
+            for value in value.split_whitespace() {
+                conditions.push(match key.as_str() {
+                    "type" => Condition::Type(value.into()),
+                    "variable" => Condition::Variable(value.into()),
+                    "is-numeric" => Condition::IsNumeric(value.into()),
+                    "match" => continue,
+                    _ => Condition::Never,
+                });
+            }
+        }
+
+        let match_ = match element.attribute("match") {
+            Some("any") => Match::Any,
+            Some("none") => Match::None,
+            _ => Match::All,
+        };
+
+        Branch {
+            conditions,
+            match_,
+            children: self.children(element),
+        }
+    }
+}
+
+pub fn parse_display(element: &xml::Element) -> Display {
+    let attribute = |name| element.attribute(name).unwrap_or_default();
+    Display {
+        prefix: attribute("prefix").into(),
+        suffix: attribute("suffix").into(),
+        italic: matches!(attribute("font-style"), "italic" | "oblique"),
+        quotes: attribute("quotes") == "true",
+        strip_periods: attribute("strip-periods") == "true",
+        text_case: match attribute("text-case") {
+            "lowercase" => Some(TextCase::Lowercase),
+            "uppercase" => Some(TextCase::Uppercase),
+            "capitalize-first" => Some(TextCase::CapitalizeFirst),
+            "capitalize-all" => Some(TextCase::CapitalizeAll),
+            "sentence" => Some(TextCase::Sentence),
+            "title" => Some(TextCase::Title),
+            _ => None,
+        },
+    }
+}
+
+fn parse_text_source(element: &xml::Element) -> Option<TextSource> {
+    if let Some(variable) = element.attribute("variable") {
+        Some(TextSource::Variable(variable.into()))
+    } else if let Some(name) = element.attribute("macro") {
+        Some(TextSource::Macro(name.into()))
+    } else if let Some(name) = element.attribute("term") {
+        Some(TextSource::Term {
+            name: name.into(),
+            form: TermForm::parse(element.attribute("form")),
+            plural: element.attribute("plural") == Some("true"),
+        })
+    } else {
+        element
+            .attribute("value")
+            .map(|value| TextSource::Value(value.into()))
+    }
+}
+
+fn parse_date(element: &xml::Element) -> Option<Date> {
+    let localized = match element.attribute("form") {
+        Some("text") => Some(DateForm::Text),
+        Some("numeric") => Some(DateForm::Numeric),
+        _ => None,
+    };
+
+    let date_parts = match element.attribute("date-parts") {
+        Some("year-month") => DatePartsFilter::YearMonth,
+        Some("year") => DatePartsFilter::Year,
+        _ => DatePartsFilter::YearMonthDay,
+    };
+
+    Some(Date {
+        variable: element.attribute("variable")?.into(),
+        localized,
+        date_parts,
+        layout: parse_date_layout(element),
+        display: parse_display(element),
+    })
+}
+
+fn parse_label(element: &xml::Element) -> Option<Label> {
+    Some(Label {
+        variable: element.attribute("variable").unwrap_or_default().into(),
+        form: TermForm::parse(element.attribute("form")),
+        plural: match element.attribute("plural") {
+            Some("always") => LabelPlural::Always,
+            Some("never") => LabelPlural::Never,
+            _ => LabelPlural::Contextual,
+        },
+        display: parse_display(element),
+    })
+}
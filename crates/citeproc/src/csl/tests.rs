@@ -0,0 +1,308 @@
+use base_db::{deps::Project, semantics::bib::Semantics};
+use expect_test::{expect, Expect};
+use parser::parse_bibtex;
+use rowan::ast::AstNode;
+use rustc_hash::FxHashSet;
+use syntax::bibtex;
+
+use super::{Style, StyleError};
+use crate::Format;
+
+const IEEE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<style xmlns="http://purl.org/net/xbiblio/csl" class="in-text" version="1.0" default-locale="en-US">
+  <info><title>IEEE (excerpt)</title></info>
+  <macro name="author">
+    <names variable="author">
+      <name and="text" initialize-with=". " delimiter=", "/>
+      <label form="short" prefix=", "/>
+      <substitute>
+        <names variable="editor"/>
+      </substitute>
+    </names>
+  </macro>
+  <macro name="title">
+    <choose>
+      <if type="book">
+        <text variable="title" font-style="italic"/>
+      </if>
+      <else>
+        <text variable="title" quotes="true"/>
+      </else>
+    </choose>
+  </macro>
+  <macro name="issued">
+    <date variable="issued">
+      <date-part name="month" form="short" suffix=" "/>
+      <date-part name="year"/>
+    </date>
+  </macro>
+  <bibliography et-al-min="7" et-al-use-first="1">
+    <layout suffix=".">
+      <group delimiter=", ">
+        <text macro="author"/>
+        <text macro="title"/>
+        <text variable="container-title" font-style="italic"/>
+        <group delimiter=" ">
+          <text term="volume" form="short"/>
+          <number variable="volume"/>
+        </group>
+        <group delimiter=" ">
+          <text term="issue" form="short"/>
+          <number variable="issue"/>
+        </group>
+        <group delimiter=" ">
+          <label variable="page" form="short"/>
+          <text variable="page"/>
+        </group>
+        <text variable="publisher"/>
+        <text macro="issued"/>
+        <text variable="DOI" prefix="doi: "/>
+      </group>
+    </layout>
+  </bibliography>
+</style>"#;
+
+const AUTHOR_DATE: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<style xmlns="http://purl.org/net/xbiblio/csl" class="in-text" version="1.0"
+  initialize-with=". " name-as-sort-order="all" and="symbol" demote-non-dropping-particle="never">
+  <macro name="author">
+    <names variable="author">
+      <name delimiter-precedes-last="always"/>
+      <substitute>
+        <names variable="editor">
+          <name/>
+          <label form="short" prefix=" (" suffix=")" text-case="capitalize-first"/>
+        </names>
+        <text variable="title"/>
+      </substitute>
+    </names>
+  </macro>
+  <macro name="publisher">
+    <group delimiter=": ">
+      <text variable="publisher-place"/>
+      <text variable="publisher"/>
+    </group>
+  </macro>
+  <citation>
+    <layout>
+      <text macro="author"/>
+    </layout>
+  </citation>
+  <bibliography et-al-min="4" et-al-use-first="3">
+    <layout>
+      <text macro="author" suffix=" "/>
+      <date variable="issued" form="text" date-parts="year" prefix="(" suffix="). "/>
+      <choose>
+        <if type="chapter paper-conference" match="any">
+          <text variable="title" suffix=". "/>
+          <group delimiter=" " suffix=". ">
+            <text term="in" text-case="capitalize-first"/>
+            <text variable="container-title" font-style="italic"/>
+          </group>
+        </if>
+        <else-if variable="container-title">
+          <text variable="title" suffix=". "/>
+          <group delimiter=", " suffix=". ">
+            <text variable="container-title" font-style="italic"/>
+            <text variable="volume" font-style="italic"/>
+            <text variable="page"/>
+          </group>
+        </else-if>
+        <else>
+          <text variable="title" font-style="italic" suffix=". "/>
+          <number variable="edition" form="ordinal" suffix=" ed. "/>
+        </else>
+      </choose>
+      <text macro="publisher" suffix="."/>
+    </layout>
+  </bibliography>
+</style>"#;
+
+fn render(style: &Style, input: &str) -> String {
+    let green = parse_bibtex(input);
+    let root = bibtex::SyntaxNode::new_root(green);
+    let mut semantics = Semantics::default();
+    semantics.process_root(&root);
+    let root = bibtex::Root::cast(root).unwrap();
+    let entry = root.entries().next().unwrap();
+    let project = Project {
+        documents: FxHashSet::default(),
+    };
+
+    crate::render(&entry, &semantics, &project, Some(style), Format::Markdown).unwrap()
+}
+
+fn check(style: &str, input: &str, expect: Expect) {
+    let style = Style::parse(style, None, None).unwrap();
+    expect.assert_eq(&render(&style, input));
+}
+
+#[test]
+fn test_ieee_article() {
+    check(
+        IEEE,
+        r#"
+@article{rivest,
+    author = {Rivest, Ronald L. and Shamir, Adi and Adleman, Leonard},
+    title = {A Method for Obtaining Digital Signatures and Public-Key Cryptosystems},
+    journal = {Commun. ACM},
+    volume = {21},
+    number = {2},
+    pages = {120--126},
+    year = {1978},
+    month = feb,
+    doi = {10.1145/359340.359342},
+}"#,
+        expect![[
+            r#"R. L. Rivest, A. Shamir, and L. Adleman, "A Method for Obtaining Digital Signatures and Public-Key Cryptosystems", *Commun. ACM*, vol. 21, no. 2, pp. 120–126, Feb. 1978, doi: [10.1145/359340.359342](https://doi.org/10.1145/359340.359342)."#
+        ]],
+    );
+}
+
+#[test]
+fn test_ieee_book_editor() {
+    check(
+        IEEE,
+        r#"
+@book{handbook,
+    editor = {Knuth, Donald E.},
+    title = {A Handbook},
+    publisher = {Addison-Wesley},
+    date = {1990-05},
+}"#,
+        expect![[r#"D. E. Knuth, ed., *A Handbook*, Addison-Wesley, May 1990."#]],
+    );
+}
+
+#[test]
+fn test_author_date_article() {
+    check(
+        AUTHOR_DATE,
+        r#"
+@article{article,
+    author = {Doe, John and Roe, Richard},
+    title = {An Article},
+    journaltitle = {Journal of Things},
+    volume = {12},
+    pages = {1-10},
+    date = {2020-03-04},
+}"#,
+        expect![[r#"Doe, J., & Roe, R. (2020). An Article. *Journal of Things*, *12*, 1–10."#]],
+    );
+}
+
+#[test]
+fn test_author_date_et_al() {
+    check(
+        AUTHOR_DATE,
+        r#"
+@inproceedings{paper,
+    author = {Doe, John and Roe, Richard and Poe, Edgar and Moe, Mary},
+    title = {A Paper},
+    booktitle = {Proceedings of the Conference},
+    year = {2001},
+}"#,
+        expect![[
+            r#"Doe, J., Roe, R., Poe, E., et al. (2001). A Paper. In *Proceedings of the Conference*."#
+        ]],
+    );
+}
+
+#[test]
+fn test_author_date_substitute() {
+    check(
+        AUTHOR_DATE,
+        r#"
+@book{book,
+    editor = {Doe, Jane and Roe, Richard},
+    title = {A Book},
+    edition = {2},
+    location = {Berlin},
+    publisher = {Springer},
+    year = {2010},
+}"#,
+        expect![[r#"Doe, J. & Roe, R. (Eds.) (2010). *A Book*. 2nd ed. Berlin: Springer."#]],
+    );
+}
+
+#[test]
+fn test_author_date_missing_variables() {
+    check(
+        AUTHOR_DATE,
+        r#"
+@book{book,
+    title = {Anonymous Book},
+}"#,
+        expect![[r#"Anonymous Book"#]],
+    );
+}
+
+#[test]
+fn test_locale_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("locales-de-DE.xml"),
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<locale xmlns="http://purl.org/net/xbiblio/csl" version="1.0" xml:lang="de-DE">
+  <terms>
+    <term name="and">und</term>
+    <term name="month-02" form="short">Feb.</term>
+    <term name="volume" form="short">Bd.</term>
+    <term name="issue" form="short">Nr.</term>
+    <term name="page" form="short"><single>S.</single><multiple>S.</multiple></term>
+  </terms>
+</locale>"#,
+    )
+    .unwrap();
+
+    let style_path = dir.path().join("ieee.csl");
+    std::fs::write(&style_path, IEEE).unwrap();
+
+    let style = Style::load(&style_path, Some("de"), None).unwrap();
+    let output = render(
+        &style,
+        r#"
+@article{article,
+    author = {Doe, John and Roe, Richard},
+    title = {Ein Artikel},
+    journal = {Zeitschrift},
+    volume = {3},
+    number = {1},
+    pages = {5-7},
+    year = {1999},
+    month = {2},
+}"#,
+    );
+
+    expect![[
+        r#"J. Doe und R. Roe, "Ein Artikel", *Zeitschrift*, Bd. 3, Nr. 1, S. 5–7, Feb. 1999."#
+    ]]
+    .assert_eq(&output);
+}
+
+#[test]
+fn test_style_locale() {
+    let style = IEEE.replace(
+        "<info>",
+        r#"<locale xml:lang="en"><terms><term name="and">as well as</term></terms></locale><info>"#,
+    );
+
+    check(
+        &style,
+        r#"
+@misc{misc,
+    author = {Doe, John and Roe, Richard},
+    title = {Something},
+}"#,
+        expect![[r#"J. Doe as well as R. Roe, "Something"."#]],
+    );
+}
+
+#[test]
+fn test_invalid_style() {
+    let error = Style::parse("<style>\n<citation>\n</style>", None, None).unwrap_err();
+    assert!(matches!(error, StyleError::InvalidXml(3)));
+
+    let error = Style::parse("<locale/>", None, None).unwrap_err();
+    assert!(matches!(error, StyleError::NotAStyle));
+}
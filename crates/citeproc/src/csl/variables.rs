@@ -0,0 +1,237 @@
+use bibtex_utils::field::{
+    author::AuthorField,
+    date::{DateField, DateFieldData},
+    number::NumberField,
+    text::TextField,
+};
+use chrono::Datelike;
+use human_name::Name;
+use rustc_hash::FxHashMap;
+
+use crate::entry::{EntryData, EntryKind};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DateValue {
+    Parts {
+        year: i32,
+        month: Option<u32>,
+        day: Option<u32>,
+    },
+    Literal(String),
+}
+
+/// The variables of an entry as defined by the CSL specification.
+#[derive(Debug, Clone, Default)]
+pub struct Variables {
+    pub kind: &'static str,
+    pub text: FxHashMap<&'static str, String>,
+    pub names: FxHashMap<&'static str, Vec<Name>>,
+    pub dates: FxHashMap<&'static str, DateValue>,
+}
+
+impl Variables {
+    /// Maps the fields of the biblatex data model to the variables of CSL.
+    pub fn new(entry: &EntryData) -> Self {
+        let text = |field| entry.text.get(&field).map(|data| data.text.clone());
+        let number = |field| entry.number.get(&field).map(|data| data.to_string());
+        let with_subtitle = |title: Option<String>, subtitle| match (title, text(subtitle)) {
+            (Some(title), Some(subtitle)) => Some(format!("{title}: {subtitle}")),
+            (title, _) => title,
+        };
+
+        let journal = text(TextField::Journal).or_else(|| text(TextField::JournalTitle));
+        let is_journal_article = entry.kind == EntryKind::Article && journal.is_some();
+        let mut variables = Self {
+            kind: csl_type(entry.kind, is_journal_article),
+            ..Self::default()
+        };
+
+        let container = with_subtitle(journal, TextField::JournalSubtitle)
+            .or_else(|| with_subtitle(text(TextField::BookTitle), TextField::BookSubtitle))
+            .or_else(|| with_subtitle(text(TextField::MainTitle), TextField::MainSubtitle));
+
+        let issue = match entry.kind {
+            EntryKind::Article | EntryKind::Periodical | EntryKind::SuppPeriodical => {
+                number(NumberField::Number).or_else(|| text(TextField::Issue))
+            }
+            _ => text(TextField::Issue),
+        };
+
+        let genre = text(TextField::Type).or_else(|| match entry.kind {
+            EntryKind::PhdThesis => Some("PhD thesis".into()),
+            EntryKind::MasterThesis => Some("Master's thesis".into()),
+            EntryKind::TechReport => Some("Technical report".into()),
+            _ => None,
+        });
+
+        let values = [
+            (
+                "title",
+                with_subtitle(text(TextField::Title), TextField::Subtitle),
+            ),
+            ("container-title", container),
+            ("collection-title", text(TextField::Series)),
+            ("publisher", text(TextField::Publisher)),
+            ("publisher-place", text(TextField::Location)),
+            ("event", text(TextField::EventTitle)),
+            ("event-place", text(TextField::Venue)),
+            ("volume", number(NumberField::Volume)),
+            ("number-of-volumes", number(NumberField::Volumes)),
+            ("issue", issue),
+            ("number", number(NumberField::Number)),
+            ("page", number(NumberField::Pages)),
+            ("number-of-pages", number(NumberField::PageTotal)),
+            ("edition", number(NumberField::Edition)),
+            ("chapter-number", text(TextField::Chapter)),
+            ("genre", genre),
+            ("version", text(TextField::Version)),
+            ("status", text(TextField::Pubstate)),
+            ("language", text(TextField::Language)),
+            ("note", text(TextField::Note)),
+            ("abstract", text(TextField::Abstract)),
+            ("DOI", text(TextField::Doi)),
+            ("ISBN", text(TextField::Isbn)),
+            ("ISSN", text(TextField::Issn)),
+            ("URL", text(TextField::Url)),
+        ];
+
+        for (name, value) in values {
+            if let Some(value) = value.filter(|value| !value.trim().is_empty()) {
+                variables.text.insert(name, value);
+            }
+        }
+
+        let names = [
+            ("author", AuthorField::Author),
+            ("editor", AuthorField::Editor),
+            ("translator", AuthorField::Translator),
+        ];
+
+        for (name, field) in names {
+            if let Some(data) = entry
+                .author
+                .get(&field)
+                .filter(|data| !data.authors.is_empty())
+            {
+                variables.names.insert(name, data.authors.clone());
+            }
+        }
+
+        let issued = entry
+            .date
+            .get(&DateField::Date)
+            .and_then(date_value)
+            .or_else(|| {
+                let year = entry.date.get(&DateField::Year)?;
+                let month = entry
+                    .date
+                    .get(&DateField::Month)
+                    .and_then(|month| match month {
+                        DateFieldData::Month(month) => Some(month.number_from_month()),
+                        DateFieldData::Year(month) => u32::try_from(*month)
+                            .ok()
+                            .filter(|month| (1..=12).contains(month)),
+                        _ => None,
+                    });
+
+                match date_value(year)? {
+                    DateValue::Parts { year, .. } => Some(DateValue::Parts {
+                        year,
+                        month,
+                        day: None,
+                    }),
+                    literal => Some(literal),
+                }
+            });
+
+        let dates = [
+            ("issued", issued),
+            (
+                "accessed",
+                entry.date.get(&DateField::UrlDate).and_then(date_value),
+            ),
+            (
+                "event-date",
+                entry.date.get(&DateField::EventDate).and_then(date_value),
+            ),
+        ];
+
+        for (name, value) in dates {
+            if let Some(value) = value {
+                variables.dates.insert(name, value);
+            }
+        }
+
+        variables
+    }
+
+    pub fn is_empty(&self, name: &str) -> bool {
+        !self.text.contains_key(name)
+            && !self.names.contains_key(name)
+            && !self.dates.contains_key(name)
+    }
+}
+
+fn date_value(data: &DateFieldData) -> Option<DateValue> {
+    Some(match data {
+        DateFieldData::Date(date) => DateValue::Parts {
+            year: date.year(),
+            month: Some(date.month()),
+            day: Some(date.day()),
+        },
+        DateFieldData::Year(year) => DateValue::Parts {
+            year: *year,
+            month: None,
+            day: None,
+        },
+        DateFieldData::Month(_) => return None,
+        DateFieldData::Other(text) => {
+            let mut parts = text.trim().splitn(3, '-');
+            let year = parts.next().filter(|year| year.len() == 4);
+            let month = parts.next().map(str::parse::<u32>);
+            match (year.map(str::parse), month, parts.next()) {
+                (Some(Ok(year)), Some(Ok(month)), None) if (1..=12).contains(&month) => {
+                    DateValue::Parts {
+                        year,
+                        month: Some(month),
+                        day: None,
+                    }
+                }
+                _ => DateValue::Literal(text.clone()),
+            }
+        }
+    })
+}
+
+/// Maps the entry types of biblatex to the item types of CSL.
+fn csl_type(kind: EntryKind, is_journal_article: bool) -> &'static str {
+    match kind {
+        EntryKind::Article if is_journal_article => "article-journal",
+        EntryKind::Article | EntryKind::Misc | EntryKind::Set | EntryKind::Unknown => "article",
+        EntryKind::Book
+        | EntryKind::MVBook
+        | EntryKind::Collection
+        | EntryKind::MVCollection
+        | EntryKind::Proceedings
+        | EntryKind::MVProceedings
+        | EntryKind::Reference
+        | EntryKind::MVReference
+        | EntryKind::Manual => "book",
+        EntryKind::InBook
+        | EntryKind::BookInBook
+        | EntryKind::SuppBook
+        | EntryKind::InCollection
+        | EntryKind::SuppCollection
+        | EntryKind::InReference => "chapter",
+        EntryKind::InProceedings | EntryKind::Conference => "paper-conference",
+        EntryKind::Booklet => "pamphlet",
+        EntryKind::DataSet => "dataset",
+        EntryKind::Online | EntryKind::Electronic | EntryKind::Www => "webpage",
+        EntryKind::Patent => "patent",
+        EntryKind::Periodical => "periodical",
+        EntryKind::SuppPeriodical => "article-journal",
+        EntryKind::Report | EntryKind::TechReport => "report",
+        EntryKind::Software => "software",
+        EntryKind::Thesis | EntryKind::MasterThesis | EntryKind::PhdThesis => "thesis",
+    }
+}
@@ -0,0 +1,235 @@
+/// An element of an XML document.
+///
+/// CSL does not use mixed content, so the text of an element is stored as a whole.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    pub text: String,
+}
+
+impl Element {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct XmlError {
+    pub line: usize,
+}
+
+/// Parses the subset of XML that is used by CSL styles and locales.
+///
+/// Namespaces are not resolved and document type declarations are skipped.
+pub fn parse(input: &str) -> Result<Element, XmlError> {
+    let mut reader = Reader {
+        input: input.strip_prefix('\u{feff}').unwrap_or(input),
+        pos: 0,
+    };
+
+    reader.skip_misc()?;
+    let root = reader.element()?;
+    reader.skip_misc()?;
+    if reader.pos < reader.input.len() {
+        return Err(reader.error());
+    }
+
+    Ok(root)
+}
+
+struct Reader<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn error(&self) -> XmlError {
+        let line = self.input[..self.pos].matches('\n').count() + 1;
+        XmlError { line }
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        let found = self.rest().starts_with(text);
+        if found {
+            self.pos += text.len();
+        }
+
+        found
+    }
+
+    fn expect(&mut self, text: &str) -> Result<(), XmlError> {
+        if self.eat(text) {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips everything until after the given delimiter.
+    fn skip_past(&mut self, delimiter: &str) -> Result<&'a str, XmlError> {
+        let Some(end) = self.rest().find(delimiter) else {
+            self.pos = self.input.len();
+            return Err(self.error());
+        };
+
+        let skipped = &self.rest()[..end];
+        self.pos += end + delimiter.len();
+        Ok(skipped)
+    }
+
+    /// Skips whitespace, comments, processing instructions and document type declarations.
+    fn skip_misc(&mut self) -> Result<(), XmlError> {
+        loop {
+            self.skip_whitespace();
+            if self.eat("<!--") {
+                self.skip_past("-->")?;
+            } else if self.eat("<?") {
+                self.skip_past("?>")?;
+            } else if self.eat("<!DOCTYPE") {
+                self.skip_past(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str, XmlError> {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/' | '<'))
+            .unwrap_or(rest.len());
+
+        if end == 0 {
+            return Err(self.error());
+        }
+
+        self.pos += end;
+        Ok(&rest[..end])
+    }
+
+    fn element(&mut self) -> Result<Element, XmlError> {
+        self.expect("<")?;
+        let mut element = Element {
+            name: self.name()?.into(),
+            ..Element::default()
+        };
+
+        loop {
+            self.skip_whitespace();
+            if self.eat("/>") {
+                return Ok(element);
+            }
+
+            if self.eat(">") {
+                break;
+            }
+
+            let key = self.name()?;
+            self.skip_whitespace();
+            self.expect("=")?;
+            self.skip_whitespace();
+            let quote = if self.eat("\"") {
+                "\""
+            } else {
+                self.expect("'")?;
+                "'"
+            };
+
+            let value = unescape(self.skip_past(quote)?);
+            element.attributes.push((key.into(), value));
+        }
+
+        loop {
+            if self.eat("</") {
+                let name = self.name()?;
+                self.skip_whitespace();
+                if name != element.name {
+                    return Err(self.error());
+                }
+
+                self.expect(">")?;
+                return Ok(element);
+            } else if self.eat("<!--") {
+                self.skip_past("-->")?;
+            } else if self.eat("<![CDATA[") {
+                let text = self.skip_past("]]>")?;
+                element.text.push_str(text);
+            } else if self.eat("<?") {
+                self.skip_past("?>")?;
+            } else if self.rest().starts_with('<') {
+                element.children.push(self.element()?);
+            } else if self.rest().is_empty() {
+                return Err(self.error());
+            } else {
+                let end = self.rest().find('<').unwrap_or(self.rest().len());
+                let text = &self.rest()[..end];
+                self.pos += end;
+                element.text.push_str(&unescape(text));
+            }
+        }
+    }
+}
+
+/// Replaces the predefined entities and character references. Unknown entities are kept as is.
+fn unescape(text: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+
+        let entity = &rest[1..end];
+        let c = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+
+        match c {
+            Some(c) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
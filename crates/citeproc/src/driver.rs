@@ -1,4 +1,3 @@
-use base_db::semantics::bib::Semantics;
 use bibtex_utils::field::{
     author::AuthorField,
     date::DateField,
@@ -7,7 +6,6 @@ use bibtex_utils::field::{
 };
 use isocountry::CountryCode;
 use itertools::Itertools;
-use syntax::bibtex;
 use titlecase::titlecase;
 use url::Url;
 
@@ -22,8 +20,7 @@ pub struct Driver {
 }
 
 impl Driver {
-    pub fn process(&mut self, entry: &bibtex::Entry, semantics: &Semantics) {
-        let entry = EntryData::from_entry(entry, semantics);
+    pub fn process(&mut self, entry: EntryData) {
         match entry.kind {
             EntryKind::Article
             | EntryKind::DataSet
@@ -1,4 +1,4 @@
-use base_db::semantics::bib::Semantics;
+use base_db::{deps::Project, semantics::bib::Semantics};
 use bibtex_utils::field::{
     author::{AuthorField, AuthorFieldData},
     date::{DateField, DateFieldData},
@@ -6,8 +6,9 @@ use bibtex_utils::field::{
     text::{TextField, TextFieldData},
     FieldParseCache,
 };
-use rustc_hash::FxHashMap;
-use syntax::bibtex::{Entry, Field, HasName, HasType, HasValue, Value};
+use rowan::ast::AstNode;
+use rustc_hash::{FxHashMap, FxHashSet};
+use syntax::bibtex::{Entry, Field, HasName, HasType, HasValue, Root, Value};
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum EntryKind {
@@ -120,6 +121,174 @@ impl EntryData {
 
         data
     }
+
+    /// Collects the fields of an entry including the fields that it inherits from the entries
+    /// referenced by its `xdata` and `crossref` fields.
+    ///
+    /// The referenced entries are looked up in the document of the entry first and then in the project.
+    pub fn resolve(entry: &Entry, semantics: &Semantics, project: &Project) -> Self {
+        let mut sources = Vec::new();
+        if let Some(root) = entry.syntax().ancestors().last().and_then(Root::cast) {
+            sources.push((root, semantics));
+        }
+
+        for document in &project.documents {
+            let Some(data) = document.data.as_bib() else {
+                continue;
+            };
+
+            if let Some(root) = Root::cast(data.root_node()) {
+                sources.push((root, &data.semantics));
+            }
+        }
+
+        let mut visited = FxHashSet::default();
+        Self::resolve_entry(entry, semantics, &sources, &mut visited)
+    }
+
+    fn resolve_entry(
+        entry: &Entry,
+        semantics: &Semantics,
+        sources: &[(Root, &Semantics)],
+        visited: &mut FxHashSet<String>,
+    ) -> Self {
+        let mut data = Self::from_entry(entry, semantics);
+        let Some(name) = entry.name_token() else {
+            return data;
+        };
+
+        visited.insert(name.text().into());
+
+        // Fields from `xdata` entries are treated as if they were part of the entry itself
+        // which is why they take precedence over the fields of the `crossref` parent.
+        for key in referenced_keys(entry, semantics, "xdata") {
+            if let Some(parent) = Self::resolve_parent(&key, sources, visited) {
+                data.inherit(parent, None);
+            }
+        }
+
+        let crossref = referenced_keys(entry, semantics, "crossref")
+            .into_iter()
+            .next();
+        if let Some(parent) = crossref.and_then(|key| Self::resolve_parent(&key, sources, visited))
+        {
+            let titles = inherited_titles(parent.kind, data.kind);
+            data.inherit(parent, titles);
+        }
+
+        visited.remove(name.text());
+        data
+    }
+
+    fn resolve_parent(
+        key: &str,
+        sources: &[(Root, &Semantics)],
+        visited: &mut FxHashSet<String>,
+    ) -> Option<Self> {
+        let (entry, semantics) = sources.iter().find_map(|(root, semantics)| {
+            let entry = semantics.entries.iter().find(|entry| entry.has_key(key))?;
+            Some((root.find_entry(&entry.name.text)?, *semantics))
+        })?;
+
+        let name = entry.name_token()?;
+        if visited.contains(name.text()) {
+            return None;
+        }
+
+        Some(Self::resolve_entry(&entry, semantics, sources, visited))
+    }
+
+    /// Adds the fields of a parent entry that are not present in this entry.
+    ///
+    /// If `titles` is set, the title, subtitle and title addon of the parent are inherited as the
+    /// given fields instead. Fields of the parent with the same name as the targets take precedence
+    /// because they are meant for the children of BibTeX-style cross-references.
+    fn inherit(&mut self, mut parent: Self, titles: Option<[TextField; 3]>) {
+        let sources = [TextField::Title, TextField::Subtitle, TextField::TitleAddon];
+        let titles: Vec<_> = titles
+            .into_iter()
+            .flat_map(|targets| sources.into_iter().zip(targets))
+            .filter_map(|(source, target)| Some((target, parent.text.remove(&source)?)))
+            .collect();
+
+        // Fields like `ids` or `shorthand` are specific to the entry and cannot be inherited.
+        parent.text.remove(&TextField::Unknown);
+        for (field, value) in parent.text.into_iter().chain(titles) {
+            self.text.entry(field).or_insert(value);
+        }
+
+        for (field, value) in parent.author {
+            self.author.entry(field).or_insert(value);
+        }
+
+        for (field, value) in parent.date {
+            self.date.entry(field).or_insert(value);
+        }
+
+        for (field, value) in parent.number {
+            self.number.entry(field).or_insert(value);
+        }
+    }
+}
+
+/// Returns the keys of the entries that are referenced by the given field.
+fn referenced_keys(entry: &Entry, semantics: &Semantics, name: &str) -> Vec<String> {
+    entry
+        .fields()
+        .filter(|field| {
+            field
+                .name_token()
+                .is_some_and(|token| token.text().eq_ignore_ascii_case(name))
+        })
+        .filter_map(|field| TextFieldData::parse(&field.value()?, &semantics.expanded_defs))
+        .flat_map(|data| {
+            data.text
+                .split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(String::from)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Returns the fields that the titles of a `crossref` parent are inherited as
+/// according to the default inheritance rules of biblatex.
+///
+/// For the remaining combinations, the titles are inherited as is like in BibTeX.
+fn inherited_titles(parent: EntryKind, child: EntryKind) -> Option<[TextField; 3]> {
+    use EntryKind::*;
+
+    let main = [
+        TextField::MainTitle,
+        TextField::MainSubtitle,
+        TextField::MainTitleAddon,
+    ];
+
+    let book = [
+        TextField::BookTitle,
+        TextField::BookSubtitle,
+        TextField::BookTitleAddon,
+    ];
+
+    let journal = [
+        TextField::JournalTitle,
+        TextField::JournalSubtitle,
+        TextField::JournalTitleAddon,
+    ];
+
+    match (parent, child) {
+        (
+            MVBook | MVCollection | MVReference | MVProceedings,
+            Book | InBook | BookInBook | SuppBook | Collection | Reference | InCollection
+            | InReference | SuppCollection | Proceedings | InProceedings | Conference,
+        ) => Some(main),
+        (Book, InBook | BookInBook | SuppBook) => Some(book),
+        (Collection | Reference, InCollection | InReference | SuppCollection) => Some(book),
+        (Proceedings, InProceedings | Conference) => Some(book),
+        (Periodical, Article | SuppPeriodical) => Some(journal),
+        _ => None,
+    }
 }
 
 impl EntryData {
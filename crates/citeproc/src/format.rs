@@ -0,0 +1,198 @@
+use crate::output::Inline;
+
+/// The markup language of a rendered citation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub enum Format {
+    PlainText,
+    #[default]
+    Markdown,
+    Html,
+    Latex,
+}
+
+impl Format {
+    /// Appends an inline element to the output and escapes its text.
+    pub(crate) fn write(self, buf: &mut String, inline: &Inline) {
+        match self {
+            Self::PlainText => PlainText.write(buf, inline),
+            Self::Markdown => Markdown.write(buf, inline),
+            Self::Html => Html.write(buf, inline),
+            Self::Latex => Latex.write(buf, inline),
+        }
+    }
+}
+
+trait Backend {
+    fn text(&self, buf: &mut String, text: &str);
+
+    fn italic(&self, buf: &mut String, text: &str);
+
+    fn quoted(&self, buf: &mut String, text: &str);
+
+    fn link(&self, buf: &mut String, url: &str, alt: &str);
+
+    fn write(&self, buf: &mut String, inline: &Inline) {
+        match inline {
+            Inline::Regular(text) => self.text(buf, text),
+            Inline::Italic(text) => self.italic(buf, text),
+            Inline::Quoted(text) => self.quoted(buf, text),
+            Inline::Link { url, alt } => self.link(buf, url, alt),
+        }
+    }
+}
+
+struct PlainText;
+
+impl Backend for PlainText {
+    fn text(&self, buf: &mut String, text: &str) {
+        buf.push_str(text);
+    }
+
+    fn italic(&self, buf: &mut String, text: &str) {
+        buf.push_str(text);
+    }
+
+    fn quoted(&self, buf: &mut String, text: &str) {
+        buf.push('"');
+        buf.push_str(text);
+        buf.push('"');
+    }
+
+    fn link(&self, buf: &mut String, _url: &str, alt: &str) {
+        buf.push_str(alt);
+    }
+}
+
+struct Markdown;
+
+impl Backend for Markdown {
+    fn text(&self, buf: &mut String, text: &str) {
+        for c in text.chars() {
+            if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>') {
+                buf.push('\\');
+            }
+
+            buf.push(c);
+        }
+    }
+
+    fn italic(&self, buf: &mut String, text: &str) {
+        buf.push('*');
+        self.text(buf, text);
+        buf.push('*');
+    }
+
+    fn quoted(&self, buf: &mut String, text: &str) {
+        buf.push('"');
+        self.text(buf, text);
+        buf.push('"');
+    }
+
+    fn link(&self, buf: &mut String, url: &str, alt: &str) {
+        buf.push('[');
+        self.text(buf, alt);
+        buf.push_str("](");
+        for c in url.chars() {
+            match c {
+                '(' => buf.push_str("%28"),
+                ')' => buf.push_str("%29"),
+                ' ' => buf.push_str("%20"),
+                c => buf.push(c),
+            }
+        }
+
+        buf.push(')');
+    }
+}
+
+struct Html;
+
+impl Backend for Html {
+    fn text(&self, buf: &mut String, text: &str) {
+        for c in text.chars() {
+            match c {
+                '&' => buf.push_str("&amp;"),
+                '<' => buf.push_str("&lt;"),
+                '>' => buf.push_str("&gt;"),
+                '"' => buf.push_str("&quot;"),
+                c => buf.push(c),
+            }
+        }
+    }
+
+    fn italic(&self, buf: &mut String, text: &str) {
+        buf.push_str("<i>");
+        self.text(buf, text);
+        buf.push_str("</i>");
+    }
+
+    fn quoted(&self, buf: &mut String, text: &str) {
+        buf.push_str("&ldquo;");
+        self.text(buf, text);
+        buf.push_str("&rdquo;");
+    }
+
+    fn link(&self, buf: &mut String, url: &str, alt: &str) {
+        buf.push_str("<a href=\"");
+        self.text(buf, url);
+        buf.push_str("\">");
+        self.text(buf, alt);
+        buf.push_str("</a>");
+    }
+}
+
+struct Latex;
+
+impl Backend for Latex {
+    fn text(&self, buf: &mut String, text: &str) {
+        for c in text.chars() {
+            match c {
+                '\\' => buf.push_str("\\textbackslash{}"),
+                '~' => buf.push_str("\\textasciitilde{}"),
+                '^' => buf.push_str("\\textasciicircum{}"),
+                '<' => buf.push_str("\\textless{}"),
+                '>' => buf.push_str("\\textgreater{}"),
+                '#' | '$' | '%' | '&' | '_' | '{' | '}' => {
+                    buf.push('\\');
+                    buf.push(c);
+                }
+                '–' => buf.push_str("--"),
+                '—' => buf.push_str("---"),
+                c => buf.push(c),
+            }
+        }
+    }
+
+    fn italic(&self, buf: &mut String, text: &str) {
+        buf.push_str("\\textit{");
+        self.text(buf, text);
+        buf.push('}');
+    }
+
+    fn quoted(&self, buf: &mut String, text: &str) {
+        buf.push_str("``");
+        self.text(buf, text);
+        buf.push_str("''");
+    }
+
+    /// Uses the commands of `hyperref`.
+    fn link(&self, buf: &mut String, url: &str, alt: &str) {
+        let escaped = url
+            .replace('#', "\\#")
+            .replace('%', "\\%")
+            .replace('{', "\\{")
+            .replace('}', "\\}");
+
+        if url == alt {
+            buf.push_str("\\url{");
+            buf.push_str(&escaped);
+        } else {
+            buf.push_str("\\href{");
+            buf.push_str(&escaped);
+            buf.push_str("}{");
+            self.text(buf, alt);
+        }
+
+        buf.push('}');
+    }
+}
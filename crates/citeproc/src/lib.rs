@@ -1,36 +1,153 @@
+mod csl;
 mod driver;
 mod entry;
+mod format;
 mod output;
 
-use base_db::semantics::bib::Semantics;
+use std::{
+    borrow::Cow,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use base_db::{
+    deps::{self, Project, ProjectRoot},
+    semantics::bib::Semantics,
+    Document, Workspace,
+};
+use once_cell::sync::Lazy;
+use rustc_hash::FxHashMap;
 use syntax::bibtex;
 use unicode_normalization::UnicodeNormalization;
 
-use self::{driver::Driver, output::Inline};
+use self::{driver::Driver, entry::EntryData};
+
+pub use self::{
+    csl::{Style, StyleError},
+    format::Format,
+};
 
+/// Renders a BibTeX entry including the fields that it inherits through `crossref` and `xdata`.
+///
+/// The referenced entries are looked up in the document of the entry and the bibliographies of the project.
+/// Without a CSL style, the built-in style is used.
 #[must_use]
-pub fn render(entry: &bibtex::Entry, semantics: &Semantics) -> Option<String> {
+pub fn render(
+    entry: &bibtex::Entry,
+    semantics: &Semantics,
+    project: &Project,
+    style: Option<&Style>,
+    format: Format,
+) -> Option<String> {
+    let entry = EntryData::resolve(entry, semantics, project);
     let mut output = String::new();
-    let mut driver = Driver::default();
-    driver.process(entry, semantics);
-    driver.finish().for_each(|(inline, punct)| {
-        let text = match inline {
-            Inline::Regular(text) => text,
-            Inline::Italic(text) => format!("*{text}*"),
-            Inline::Quoted(text) => format!("\"{text}\""),
-            Inline::Link { url, alt } => format!("[{alt}]({url})"),
-        };
-        output.push_str(&text);
+    let items: Vec<_> = match style {
+        Some(style) => style.render(&entry).collect(),
+        None => {
+            let mut driver = Driver::default();
+            driver.process(entry);
+            driver.finish().collect()
+        }
+    };
+
+    for (inline, punct) in items {
+        format.write(&mut output, &inline);
         output.push_str(punct.as_str());
-    });
+    }
 
+    // CSL styles end the entry with the suffix of their layout.
+    output.truncate(output.trim_end().len());
     if output.is_empty() {
         None
     } else {
-        output.push('.');
+        if style.is_none() {
+            output.push('.');
+        }
+
         Some(output.nfc().collect())
     }
 }
 
+/// Loads the CSL style that is configured for the project of the given document.
+///
+/// Returns `None` if the built-in style is used or the style cannot be loaded.
+pub fn find_style(workspace: &Workspace, document: &Document) -> Option<Arc<Style>> {
+    let start = deps::root(workspace, document);
+    let root = workspace.graphs().get(&start.uri).map_or_else(
+        || Cow::Owned(ProjectRoot::walk_and_find(workspace, &start.dir)),
+        |graph| Cow::Borrowed(&graph.root),
+    );
+
+    let config = &root.citation;
+    let dir = root.compile_dir.to_file_path().ok()?;
+    let path = dir.join(config.style.as_ref()?);
+    let locales_dir = config
+        .locales_dir
+        .as_ref()
+        .map(|locales_dir| dir.join(locales_dir));
+    load_style(path, config.locale.clone(), locales_dir)
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct StyleKey {
+    path: PathBuf,
+    locale: Option<String>,
+    locales_dir: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+struct CachedStyle {
+    modified: Option<SystemTime>,
+    style: Option<Arc<Style>>,
+}
+
+/// The loaded styles. A style is read again once the modification time of its file changes.
+static STYLES: Lazy<Mutex<FxHashMap<StyleKey, CachedStyle>>> = Lazy::new(Default::default);
+
+fn load_style(
+    path: PathBuf,
+    locale: Option<String>,
+    locales_dir: Option<PathBuf>,
+) -> Option<Arc<Style>> {
+    let modified = std::fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .ok();
+
+    let key = StyleKey {
+        path,
+        locale,
+        locales_dir,
+    };
+
+    let mut styles = STYLES.lock().unwrap();
+    if let Some(cached) = styles
+        .get(&key)
+        .filter(|cached| cached.modified == modified)
+    {
+        return cached.style.clone();
+    }
+
+    let style = Style::load(&key.path, key.locale.as_deref(), key.locales_dir.as_deref())
+        .map(Arc::new)
+        .map_err(|why| {
+            log::warn!(
+                "Unable to load citation style {}: {why}",
+                key.path.display()
+            )
+        })
+        .ok();
+
+    styles.insert(
+        key,
+        CachedStyle {
+            modified,
+            style: style.clone(),
+        },
+    );
+
+    style
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,17 +1,28 @@
-use base_db::semantics::bib::Semantics;
+use base_db::{deps::Project, semantics::bib::Semantics};
 use expect_test::{expect, Expect};
 use parser::parse_bibtex;
 use rowan::ast::AstNode;
+use rustc_hash::FxHashSet;
 use syntax::bibtex;
 
+use crate::Format;
+
 fn check(input: &str, expect: Expect) {
+    check_format(input, Format::Markdown, expect);
+}
+
+fn check_format(input: &str, format: Format, expect: Expect) {
     let green = parse_bibtex(input);
     let root = bibtex::SyntaxNode::new_root(green);
     let mut semantics = Semantics::default();
     semantics.process_root(&root);
     let root = bibtex::Root::cast(root).unwrap();
     let entry = root.entries().next().unwrap();
-    let output = super::render(&entry, &semantics).unwrap();
+    let project = Project {
+        documents: FxHashSet::default(),
+    };
+
+    let output = super::render(&entry, &semantics, &project, None, format).unwrap();
     expect.assert_eq(&output);
 }
 
@@ -396,3 +407,193 @@ fn test_patent_almendro_1998() {
         ]],
     );
 }
+
+#[test]
+fn test_inproceedings_crossref() {
+    check(
+        r#"
+@inproceedings{knuth:inproc,
+    author = {Knuth, Donald E.},
+    title = {The Art of Computer Programming},
+    pages = {1--10},
+    crossref = {knuth:proc},
+}
+
+@proceedings{knuth:proc,
+    title = {Proceedings of the Conference},
+    editor = {Lamport, Leslie},
+    publisher = {Addison-Wesley},
+    year = {1990},
+    ids = {knuth:other},
+}"#,
+        expect![[
+            r#"D. Knuth: "The Art of Computer Programming". *Proceedings of the Conference*. Ed. by L. Lamport. Addison-Wesley, 1990, 1-10."#
+        ]],
+    );
+}
+
+#[test]
+fn test_article_crossref_periodical() {
+    check(
+        r#"
+@article{article,
+    author = {Doe, John},
+    title = {An Article},
+    crossref = {journal},
+}
+
+@periodical{journal,
+    title = {Journal of Things},
+    year = {2020},
+}"#,
+        expect![[r#"J. Doe: "An Article". *Journal of Things* (2020)."#]],
+    );
+}
+
+#[test]
+fn test_bibtex_crossref() {
+    check(
+        r#"
+@inproceedings{paper,
+    author = {Doe, John},
+    title = {A Paper},
+    crossref = {conf},
+}
+
+@proceedings{conf,
+    booktitle = {Proceedings of the Conference},
+    title = {Conference Proceedings},
+    publisher = {ACM},
+    year = {2001},
+}"#,
+        expect![[r#"J. Doe: "A Paper". *Proceedings of the Conference*. ACM, 2001."#]],
+    );
+}
+
+#[test]
+fn test_xdata() {
+    check(
+        r#"
+@book{book,
+    author = {Doe, John},
+    title = {A Book},
+    xdata = {publisher, , year},
+}
+
+@xdata{publisher,
+    publisher = {Springer},
+    location = {Berlin},
+}
+
+@xdata{year,
+    year = {2010},
+    publisher = {Elsevier},
+}"#,
+        expect![[r#"J. Doe: "A Book". Berlin: Springer, 2010."#]],
+    );
+}
+
+#[test]
+fn test_crossref_cycle() {
+    check(
+        r#"
+@inbook{a,
+    author = {Doe, John},
+    title = {A Chapter},
+    crossref = {b},
+}
+
+@book{b,
+    title = {A Book},
+    year = {2000},
+    crossref = {a},
+}"#,
+        expect![[r#"J. Doe: "A Chapter". *A Book*. 2000."#]],
+    );
+}
+
+const FORMAT_INPUT: &str = r#"
+@article{foo,
+    author = {Doe, John},
+    title = {Cats \& Dogs},
+    journal = {Journal of 100\% <Pets>},
+    year = {2020},
+    pages = {1--2},
+    doi = {10.1000/a_b},
+}"#;
+
+#[test]
+fn test_format_plain_text() {
+    check_format(
+        FORMAT_INPUT,
+        Format::PlainText,
+        expect![[
+            r#"J. Doe: "Cats & Dogs". Journal of 100% <Pets> (2020): 1-2. DOI: 10.1000/a_b."#
+        ]],
+    );
+}
+
+#[test]
+fn test_format_markdown() {
+    check_format(
+        FORMAT_INPUT,
+        Format::Markdown,
+        expect![[
+            r#"J. Doe: "Cats & Dogs". *Journal of 100% \<Pets\>* (2020): 1-2. DOI: [10.1000/a\_b](https://doi.org/10.1000/a_b)."#
+        ]],
+    );
+}
+
+#[test]
+fn test_format_html() {
+    check_format(
+        FORMAT_INPUT,
+        Format::Html,
+        expect![[
+            r#"J. Doe: &ldquo;Cats &amp; Dogs&rdquo;. <i>Journal of 100% &lt;Pets&gt;</i> (2020): 1-2. DOI: <a href="https://doi.org/10.1000/a_b">10.1000/a_b</a>."#
+        ]],
+    );
+}
+
+#[test]
+fn test_format_latex() {
+    check_format(
+        FORMAT_INPUT,
+        Format::Latex,
+        expect![[
+            r#"J. Doe: ``Cats \& Dogs''. \textit{Journal of 100\% \textless{}Pets\textgreater{}} (2020): 1-2. DOI: \href{https://doi.org/10.1000/a_b}{10.1000/a\_b}."#
+        ]],
+    );
+}
+
+#[test]
+fn test_style_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("style.csl");
+    let write_style = |variable: &str, modified: u64| {
+        let text = format!(
+            r#"<style><bibliography><layout><text variable="{variable}"/></layout></bibliography></style>"#
+        );
+
+        std::fs::write(&path, text).unwrap();
+        let modified = std::time::UNIX_EPOCH + std::time::Duration::from_secs(modified);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    };
+
+    write_style("title", 1);
+    let first = super::load_style(path.clone(), None, None).unwrap();
+    let second = super::load_style(path.clone(), None, None).unwrap();
+    assert!(std::sync::Arc::ptr_eq(&first, &second));
+
+    write_style("publisher", 2);
+    let third = super::load_style(path.clone(), None, None).unwrap();
+    assert!(!std::sync::Arc::ptr_eq(&first, &third));
+
+    std::fs::remove_file(&path).unwrap();
+    assert!(super::load_style(path, None, None).is_none());
+}
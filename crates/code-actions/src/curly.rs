@@ -0,0 +1,51 @@
+use diagnostics::Diagnostic;
+use rowan::{TextRange, TextSize};
+use syntax::latex;
+
+use crate::{CodeAction, CodeActionParams, TextEdit};
+
+pub(super) fn find_actions(
+    params: &CodeActionParams,
+    range: TextRange,
+    diagnostic: &Diagnostic,
+    results: &mut Vec<CodeAction>,
+) -> Option<()> {
+    let offset = params
+        .feature
+        .document
+        .data
+        .as_tex()
+        .and_then(|data| find_group_end(&data.root_node(), range.start()))
+        .unwrap_or(range.start());
+
+    results.push(CodeAction {
+        title: String::from("Insert missing \"}\""),
+        diagnostic: diagnostic.clone(),
+        edits: vec![TextEdit::insert(offset, String::from("}"))],
+        is_preferred: true,
+    });
+
+    Some(())
+}
+
+/// Skips the trailing trivia of the unclosed group so that the bracket ends up next to its content.
+fn find_group_end(root: &latex::SyntaxNode, offset: TextSize) -> Option<TextSize> {
+    let token = root.token_at_offset(offset).left_biased()?;
+    let group = token
+        .parent_ancestors()
+        .find(|node| node.text_range().end() == offset && node.kind() != latex::ROOT)?;
+
+    let mut token = group.last_token();
+    while let Some(current) = token {
+        if !matches!(
+            current.kind(),
+            latex::LINE_BREAK | latex::WHITESPACE | latex::COMMENT
+        ) {
+            return Some(current.text_range().end());
+        }
+
+        token = current.prev_token();
+    }
+
+    None
+}
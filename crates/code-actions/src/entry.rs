@@ -0,0 +1,39 @@
+use diagnostics::Diagnostic;
+use rowan::{TextRange, TextSize};
+
+use crate::{CodeAction, CodeActionParams, TextEdit};
+
+pub(super) fn find_actions(
+    params: &CodeActionParams,
+    range: TextRange,
+    diagnostic: &Diagnostic,
+    results: &mut Vec<CodeAction>,
+) -> Option<()> {
+    let document = params.feature.document;
+    let data = document.data.as_bib()?;
+    let entry = data
+        .semantics
+        .entries
+        .iter()
+        .find(|entry| entry.name.range == range)?;
+
+    // Also remove the whitespace up to the next line break to avoid leaving an empty line behind.
+    let rest = &document.text[usize::from(entry.full_range.end())..];
+    let trailing = rest
+        .char_indices()
+        .find(|(_, c)| !c.is_whitespace() || *c == '\n')
+        .map_or(rest.len(), |(i, c)| if c == '\n' { i + 1 } else { i });
+
+    let end = entry.full_range.end() + TextSize::try_from(trailing).ok()?;
+    results.push(CodeAction {
+        title: format!("Remove unused entry \"{}\"", entry.name.text),
+        diagnostic: diagnostic.clone(),
+        edits: vec![TextEdit::delete(TextRange::new(
+            entry.full_range.start(),
+            end,
+        ))],
+        is_preferred: false,
+    });
+
+    Some(())
+}
@@ -0,0 +1,33 @@
+use diagnostics::Diagnostic;
+use rowan::{ast::AstNode, TextRange};
+use syntax::latex;
+
+use crate::{CodeAction, CodeActionParams, TextEdit};
+
+pub(super) fn find_actions(
+    params: &CodeActionParams,
+    range: TextRange,
+    diagnostic: &Diagnostic,
+    results: &mut Vec<CodeAction>,
+) -> Option<()> {
+    let data = params.feature.document.data.as_tex()?;
+    let (begin, end) = data
+        .root_node()
+        .descendants()
+        .filter_map(latex::Environment::cast)
+        .filter_map(|env| Some((env.begin()?.name()?.key()?, env.end()?.name()?.key()?)))
+        .find(|(begin, _)| latex::small_range(begin) == range)?;
+
+    let name = begin.to_string();
+    results.push(CodeAction {
+        title: format!("Rename \\end{{{}}} to \\end{{{}}}", end.to_string(), name),
+        diagnostic: diagnostic.clone(),
+        edits: vec![TextEdit {
+            range: latex::small_range(&end),
+            text: name,
+        }],
+        is_preferred: true,
+    });
+
+    Some(())
+}
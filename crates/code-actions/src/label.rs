@@ -0,0 +1,66 @@
+use diagnostics::Diagnostic;
+use rowan::{ast::AstNode, TextRange, TextSize};
+use syntax::latex;
+
+use crate::{CodeAction, CodeActionParams, TextEdit};
+
+pub(super) fn find_actions(
+    params: &CodeActionParams,
+    range: TextRange,
+    diagnostic: &Diagnostic,
+    results: &mut Vec<CodeAction>,
+) -> Option<()> {
+    let data = params.feature.document.data.as_tex()?;
+    let name = data
+        .semantics
+        .labels
+        .iter()
+        .find(|label| label.name.range == range)
+        .map(|label| &label.name.text)?;
+
+    let root = data.root_node();
+    let token = root.token_at_offset(range.start()).right_biased()?;
+
+    // Attach the new label to the innermost section or environment that contains the reference.
+    let offset = token
+        .parent_ancestors()
+        .find_map(|node| find_label_target(&node, range))?;
+
+    results.push(CodeAction {
+        title: format!("Create label \"{name}\""),
+        diagnostic: diagnostic.clone(),
+        edits: vec![TextEdit::insert(offset, format!("\\label{{{name}}}"))],
+        is_preferred: true,
+    });
+
+    Some(())
+}
+
+/// Finds the position after the title of a section or the caption or beginning of an environment.
+///
+/// Titles and captions that contain the reference itself are skipped because the label would
+/// only refer to itself. The `document` environment is not a target either.
+fn find_label_target(node: &latex::SyntaxNode, reference: TextRange) -> Option<TextSize> {
+    if let Some(section) = latex::Section::cast(node.clone()) {
+        let name = latex::small_range(&section.name()?);
+        return (!name.contains_range(reference)).then_some(name.end());
+    }
+
+    let environment = latex::Environment::cast(node.clone())?;
+    let begin = environment.begin()?;
+    if begin.name()?.key()?.to_string() == "document" {
+        return None;
+    }
+
+    match environment
+        .syntax()
+        .children()
+        .find_map(latex::Caption::cast)
+    {
+        Some(caption) => {
+            let caption = latex::small_range(&caption);
+            (!caption.contains_range(reference)).then_some(caption.end())
+        }
+        None => Some(latex::small_range(&begin).end()),
+    }
+}
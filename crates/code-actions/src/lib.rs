@@ -0,0 +1,78 @@
+mod curly;
+mod entry;
+mod environment;
+mod label;
+
+use base_db::FeatureParams;
+use diagnostics::{BibError, Diagnostic, TexError};
+use rowan::TextRange;
+
+#[derive(Debug)]
+pub struct CodeActionParams<'a> {
+    pub feature: FeatureParams<'a>,
+    pub range: TextRange,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CodeAction {
+    pub title: String,
+    pub diagnostic: Diagnostic,
+    pub edits: Vec<TextEdit>,
+    pub is_preferred: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TextEdit {
+    pub range: TextRange,
+    pub text: String,
+}
+
+impl TextEdit {
+    pub fn insert(offset: rowan::TextSize, text: String) -> Self {
+        let range = TextRange::empty(offset);
+        Self { range, text }
+    }
+
+    pub fn delete(range: TextRange) -> Self {
+        let text = String::new();
+        Self { range, text }
+    }
+}
+
+/// Computes quick fixes for the diagnostics that intersect the requested range.
+pub fn find_all(params: &CodeActionParams) -> Vec<CodeAction> {
+    let mut results = Vec::new();
+    for diagnostic in &params.diagnostics {
+        let range = match diagnostic {
+            Diagnostic::Tex(range, _) | Diagnostic::Bib(range, _) => *range,
+            Diagnostic::Build(_, _) | Diagnostic::Blg(_, _) | Diagnostic::Chktex(_) => continue,
+        };
+
+        if range.intersect(params.range).is_none() {
+            continue;
+        }
+
+        match diagnostic {
+            Diagnostic::Tex(range, TexError::UndefinedLabel) => {
+                label::find_actions(params, *range, diagnostic, &mut results);
+            }
+            Diagnostic::Tex(range, TexError::MismatchedEnvironment) => {
+                environment::find_actions(params, *range, diagnostic, &mut results);
+            }
+            Diagnostic::Tex(range, TexError::ExpectingRCurly)
+            | Diagnostic::Bib(range, BibError::ExpectingRCurly) => {
+                curly::find_actions(params, *range, diagnostic, &mut results);
+            }
+            Diagnostic::Bib(range, BibError::UnusedEntry) => {
+                entry::find_actions(params, *range, diagnostic, &mut results);
+            }
+            _ => {}
+        };
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,222 @@
+use expect_test::{expect, Expect};
+use rowan::TextRange;
+
+use crate::CodeActionParams;
+
+fn check(input: &str, expect: Expect) {
+    let fixture = test_utils::fixture::Fixture::parse(input);
+    let (feature, offset) = fixture.make_params().unwrap();
+
+    let mut manager = diagnostics::Manager::default();
+    for document in fixture.workspace.iter() {
+        manager.update_syntax(&fixture.workspace, document);
+    }
+
+    let diagnostics = manager
+        .get(&fixture.workspace)
+        .remove(&feature.document.uri)
+        .unwrap_or_default();
+
+    let text = feature.document.text.clone();
+    let params = CodeActionParams {
+        feature,
+        range: TextRange::empty(offset),
+        diagnostics,
+    };
+
+    let mut output = String::new();
+    for action in crate::find_all(&params) {
+        let mut new_text = text.clone();
+        for edit in action.edits.iter().rev() {
+            new_text.replace_range(std::ops::Range::<usize>::from(edit.range), &edit.text);
+        }
+
+        output.push_str(&format!("# {}\n{}\n", action.title, new_text));
+    }
+
+    expect.assert_eq(&output);
+}
+
+#[test]
+fn test_undefined_label_section() {
+    check(
+        r#"
+%! main.tex
+\section{Foo}
+See \ref{foo}.
+          |"#,
+        expect![[r##"
+            # Create label "foo"
+            \section{Foo}\label{foo}
+            See \ref{foo}.
+
+        "##]],
+    );
+}
+
+#[test]
+fn test_undefined_label_environment() {
+    check(
+        r#"
+%! main.tex
+\begin{theorem}
+\ref{foo}
+      |
+\end{theorem}"#,
+        expect![[r##"
+            # Create label "foo"
+            \begin{theorem}\label{foo}
+            \ref{foo}
+            \end{theorem}
+
+        "##]],
+    );
+}
+
+#[test]
+fn test_undefined_label_caption() {
+    check(
+        r#"
+%! main.tex
+\begin{figure}
+\caption{Foo}
+See \ref{foo}.
+          |
+\end{figure}"#,
+        expect![[r##"
+            # Create label "foo"
+            \begin{figure}
+            \caption{Foo}\label{foo}
+            See \ref{foo}.
+            \end{figure}
+
+        "##]],
+    );
+}
+
+#[test]
+fn test_undefined_label_in_title() {
+    check(
+        r#"
+%! main.tex
+\section{Foo}
+\subsection{See \ref{foo}}
+                     |"#,
+        expect![[r##"
+            # Create label "foo"
+            \section{Foo}\label{foo}
+            \subsection{See \ref{foo}}
+
+        "##]],
+    );
+}
+
+#[test]
+fn test_undefined_label_in_caption() {
+    check(
+        r#"
+%! main.tex
+\begin{figure}
+\caption{See \ref{foo}}
+                 |
+\end{figure}"#,
+        expect![[r##""##]],
+    );
+}
+
+#[test]
+fn test_undefined_label_document() {
+    check(
+        r#"
+%! main.tex
+\begin{document}
+\ref{foo}
+      |
+\end{document}"#,
+        expect![[r#""#]],
+    );
+}
+
+#[test]
+fn test_defined_label() {
+    check(
+        r#"
+%! main.tex
+\begin{document}
+\label{foo}
+\ref{foo}
+      |
+\end{document}"#,
+        expect![[r#""#]],
+    );
+}
+
+#[test]
+fn test_unused_entry() {
+    check(
+        r#"
+%! main.bib
+@article{foo,}
+          |
+@article{bar,}
+
+%! main.tex
+\bibliography{main}
+\cite{bar}"#,
+        expect![[r#"
+            # Remove unused entry "foo"
+            @article{bar,}
+
+        "#]],
+    );
+}
+
+#[test]
+fn test_mismatched_environment() {
+    check(
+        r#"
+%! main.tex
+\begin{foo}
+        |
+\end{bar}"#,
+        expect![[r#"
+            # Rename \end{bar} to \end{foo}
+            \begin{foo}
+            \end{foo}
+
+        "#]],
+    );
+}
+
+#[test]
+fn test_expecting_r_curly() {
+    check(
+        r#"
+%! main.tex
+\begin{document}
+\label{foo
+\end{document}
+|"#,
+        expect![[r##"
+            # Insert missing "}"
+            \begin{document}
+            \label{foo}
+            \end{document}
+
+        "##]],
+    );
+}
+
+#[test]
+fn test_outside_of_range() {
+    check(
+        r#"
+%! main.tex
+\ref{foo}
+|
+
+\begin{document}
+\end{document}"#,
+        expect![[r#""#]],
+    );
+}
@@ -25,6 +25,12 @@ pub enum BuildError {
     #[error("Document \"{0}\" does not exist on the local file system")]
     NotLocal(Url),
 
+    #[error("Build target \"{0}\" was not found")]
+    TargetNotFound(String),
+
+    #[error("Continuous builds are not supported by \"{0}\"")]
+    ContinuousUnsupported(String),
+
     #[error("Unable to run compiler: {0}")]
     Compile(#[from] std::io::Error),
 }
@@ -34,41 +40,189 @@ pub struct BuildCommand {
     program: String,
     args: Vec<String>,
     working_dir: PathBuf,
+
+    /// Identifies the project that is built, i.e. the root document or the output of a Tectonic project.
+    pub project: Url,
+
+    /// The build log that is written by the compiler.
+    pub log_path: Option<PathBuf>,
+
+    /// The PDF file that is produced by the build.
+    pub pdf_path: Option<PathBuf>,
 }
 
 impl BuildCommand {
-    pub fn new(workspace: &Workspace, uri: &Url) -> Result<Self, BuildError> {
+    /// Creates the command that builds the project of the given document.
+    ///
+    /// The target selects an output of a Tectonic project and defaults to the first one.
+    pub fn new(workspace: &Workspace, uri: &Url, target: Option<&str>) -> Result<Self, BuildError> {
+        Self::create(workspace, uri, target, false)
+    }
+
+    /// Creates a long-running command that rebuilds the project whenever one of its files changes.
+    ///
+    /// This uses `latexmk -pvc` or `tectonic -X watch`. Other build programs are not supported.
+    pub fn continuous(
+        workspace: &Workspace,
+        uri: &Url,
+        target: Option<&str>,
+    ) -> Result<Self, BuildError> {
+        Self::create(workspace, uri, target, true)
+    }
+
+    fn create(
+        workspace: &Workspace,
+        uri: &Url,
+        target: Option<&str>,
+        continuous: bool,
+    ) -> Result<Self, BuildError> {
         let Some(document) = workspace.lookup(uri) else {
             return Err(BuildError::NotFound(uri.clone()));
         };
 
-        let document = deps::parents(workspace, document)
-            .into_iter()
-            .next()
-            .unwrap_or(document);
+        let child = document;
+        let document = deps::root(workspace, child);
 
         let Some(path) = document.path.as_deref().and_then(Path::to_str) else {
             return Err(BuildError::NotLocal(document.uri.clone()));
         };
 
-        let config = &workspace.config().build;
-        let program = config.program.clone();
-        let args = replace_placeholders(&config.args, &[('f', path)]);
-
         let root = ProjectRoot::walk_and_find(workspace, &document.dir);
+        if !root.targets.is_empty() {
+            return Self::tectonic(&root, target, continuous);
+        }
+
+        let config = &root.build;
+        let mut program = config.program.clone();
+        let mut args = config.args.clone();
+
+        if root
+            .latexmkrc(workspace)
+            .is_some_and(|rcfile| rcfile.pdf_mode.is_some())
+        {
+            defer_to_latexmkrc(&program, &mut args);
+        }
+
+        let engine = [document, child]
+            .into_iter()
+            .filter_map(|document| document.data.as_tex())
+            .find_map(|data| data.semantics.magic_comments.program.as_deref());
+
+        if let Some(engine) = engine {
+            select_engine(&mut program, &mut args, engine);
+        }
+
+        if continuous {
+            if program_name(&program) != "latexmk" {
+                return Err(BuildError::ContinuousUnsupported(program));
+            }
+
+            // The viewer is opened by the forward search instead.
+            args.splice(0..0, ["-pvc".into(), "-view=none".into()]);
+        }
+
+        let args = replace_placeholders(&args, &[('f', path)]);
 
         let Ok(working_dir) = root.compile_dir.to_file_path() else {
             return Err(BuildError::NotLocal(document.uri.clone()));
         };
 
+        let stem = Path::new(path).file_stem().and_then(|stem| stem.to_str());
+        let log_path = root
+            .log_dir
+            .to_file_path()
+            .ok()
+            .zip(root.jobname.as_deref().or(stem))
+            .map(|(dir, stem)| dir.join(format!("{stem}.log")));
+
+        let pdf_name = config
+            .output_filename
+            .clone()
+            .or_else(|| stem.map(|stem| format!("{stem}.pdf")));
+
+        let pdf_path = root
+            .pdf_dir
+            .to_file_path()
+            .ok()
+            .zip(pdf_name)
+            .map(|(dir, name)| dir.join(name));
+
+        Ok(Self {
+            program,
+            args,
+            working_dir,
+            project: document.uri.clone(),
+            log_path,
+            pdf_path,
+        })
+    }
+
+    /// Builds an output of a Tectonic project with the V2 interface.
+    fn tectonic(
+        root: &ProjectRoot,
+        target: Option<&str>,
+        continuous: bool,
+    ) -> Result<Self, BuildError> {
+        let target = match target {
+            Some(name) => root
+                .targets
+                .iter()
+                .find(|target| target.name == name)
+                .ok_or_else(|| BuildError::TargetNotFound(name.into()))?,
+            None => &root.targets[0],
+        };
+
+        let Ok(working_dir) = root.compile_dir.to_file_path() else {
+            return Err(BuildError::NotLocal(root.compile_dir.clone()));
+        };
+
+        let Ok(out_dir) = target.out_dir.to_file_path() else {
+            return Err(BuildError::NotLocal(target.out_dir.clone()));
+        };
+
+        // A custom path to the executable is kept.
+        let program = Some(root.build.program.as_str())
+            .filter(|program| program_name(program) == "tectonic")
+            .unwrap_or("tectonic")
+            .to_string();
+
+        let args = if continuous {
+            vec![
+                "-X".into(),
+                "watch".into(),
+                "-x".into(),
+                format!(
+                    "build --keep-logs --keep-intermediates --target {}",
+                    target.name
+                ),
+            ]
+        } else {
+            vec![
+                "-X".into(),
+                "build".into(),
+                "--keep-logs".into(),
+                "--keep-intermediates".into(),
+                "--outdir".into(),
+                out_dir.to_string_lossy().into_owned(),
+                "--target".into(),
+                target.name.clone(),
+            ]
+        };
+
+        let log_path = Some(out_dir.join(format!("{}.log", target.name)));
+        let pdf_path = target.pdf_name.as_ref().map(|name| out_dir.join(name));
+
         Ok(Self {
             program,
             args,
             working_dir,
+            project: target.out_dir.clone(),
+            log_path,
+            pdf_path,
         })
     }
 
-    pub fn spawn(self, sender: Sender<String>) -> Result<Child, BuildError> {
+    pub fn spawn(&self, sender: Sender<String>) -> Result<Child, BuildError> {
         log::debug!(
             "Spawning compiler {} {:#?} in directory {}",
             self.program,
@@ -129,6 +283,66 @@ impl BuildCommand {
     }
 }
 
+/// The `latexmk` flags that select the TeX engine.
+const LATEXMK_ENGINE_FLAGS: &[&str] = &[
+    "-pdf",
+    "-pdflua",
+    "-pdfxe",
+    "-lualatex",
+    "-xelatex",
+    "-pdflatex",
+    "-dvi",
+    "-ps",
+    "-pdfdvi",
+    "-pdfps",
+];
+
+/// Applies the engine of a `% !TEX program` magic comment to the configured build command.
+///
+/// If `latexmk` is used, then the engine flag is replaced. If the build program is a TeX engine,
+/// then it is replaced by the requested engine. Other build programs are left untouched.
+fn select_engine(program: &mut String, args: &mut Vec<String>, engine: &str) {
+    let engine = engine.to_lowercase();
+    let program_name = program_name(program);
+
+    if program_name == "latexmk" {
+        let flag = match engine.as_str() {
+            "pdflatex" => "-pdf",
+            "lualatex" => "-lualatex",
+            "xelatex" => "-xelatex",
+            "latex" => "-dvi",
+            _ => {
+                log::warn!("Unsupported TeX engine in magic comment: {engine}");
+                return;
+            }
+        };
+
+        args.retain(|arg| !LATEXMK_ENGINE_FLAGS.contains(&arg.as_str()));
+        args.insert(0, flag.into());
+    } else if matches!(program_name, "pdflatex" | "lualatex" | "xelatex" | "latex")
+        && matches!(
+            engine.as_str(),
+            "pdflatex" | "lualatex" | "xelatex" | "latex"
+        )
+    {
+        *program = engine;
+    }
+}
+
+/// Removes the engine flags of `latexmk` so that the `$pdf_mode` of the `latexmkrc` file is used.
+fn defer_to_latexmkrc(program: &str, args: &mut Vec<String>) {
+    if program_name(program) == "latexmk" {
+        args.retain(|arg| !LATEXMK_ENGINE_FLAGS.contains(&arg.as_str()));
+    }
+}
+
+fn program_name(program: &str) -> &str {
+    Path::new(program)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default()
+}
+
 fn track_output(
     output: impl Read + Send + 'static,
     sender: Sender<String>,
@@ -142,3 +356,166 @@ fn track_output(
         })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use test_utils::fixture::Fixture;
+
+    use super::{defer_to_latexmkrc, select_engine, BuildCommand};
+
+    #[test]
+    fn test_select_engine_latexmk() {
+        let mut program = String::from("latexmk");
+        let mut args = vec!["-pdf".into(), "-synctex=1".into(), "%f".into()];
+        select_engine(&mut program, &mut args, "LuaLaTeX");
+        assert_eq!(program, "latexmk");
+        assert_eq!(args, vec!["-lualatex", "-synctex=1", "%f"]);
+    }
+
+    #[test]
+    fn test_select_engine_program() {
+        let mut program = String::from("pdflatex");
+        let mut args = vec!["%f".into()];
+        select_engine(&mut program, &mut args, "xelatex");
+        assert_eq!(program, "xelatex");
+        assert_eq!(args, vec!["%f"]);
+    }
+
+    #[test]
+    fn test_select_engine_unknown_program() {
+        let mut program = String::from("tectonic");
+        let mut args = vec!["%f".into()];
+        select_engine(&mut program, &mut args, "lualatex");
+        assert_eq!(program, "tectonic");
+        assert_eq!(args, vec!["%f"]);
+    }
+
+    #[test]
+    fn test_defer_to_latexmkrc() {
+        let mut args = vec!["-pdf".into(), "-synctex=1".into(), "%f".into()];
+        defer_to_latexmkrc("latexmk", &mut args);
+        assert_eq!(args, vec!["-synctex=1", "%f"]);
+    }
+
+    #[test]
+    fn test_latexmkrc() {
+        let fixture = Fixture::parse(
+            r#"
+%! .latexmkrc
+$pdf_mode = 4;
+@default_files = ('thesis.tex');
+
+%! main.tex
+\begin{document}
+\input{chapter}
+\end{document}
+
+%! thesis.tex
+\begin{document}
+\input{chapter}
+\end{document}
+
+%! chapter.tex"#,
+        );
+
+        let uri = &fixture.documents[3].uri;
+        let command = BuildCommand::new(&fixture.workspace, uri, None).unwrap();
+        assert_eq!(command.program, "latexmk");
+        assert_eq!(
+            command.args,
+            vec![
+                "-interaction=nonstopmode",
+                "-synctex=1",
+                "/texlab/thesis.tex"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_latexmkrc_jobname() {
+        let fixture = Fixture::parse(
+            r#"
+%! .latexmkrc
+$jobname = "thesis";
+$aux_dir = "build";
+
+%! main.tex
+\begin{document}
+\end{document}"#,
+        );
+
+        let uri = &fixture.documents[1].uri;
+        let command = BuildCommand::new(&fixture.workspace, uri, None).unwrap();
+        assert_eq!(
+            command.log_path.as_deref(),
+            Some(std::path::Path::new("/texlab/build/thesis.log"))
+        );
+
+        assert_eq!(
+            command.pdf_path.as_deref(),
+            Some(std::path::Path::new("/texlab/thesis.pdf"))
+        );
+    }
+
+    #[test]
+    fn test_tectonic_target() {
+        let fixture = Fixture::parse(
+            r#"
+%! Tectonic.toml
+[[output]]
+name = "print"
+type = "pdf"
+
+[[output]]
+name = "slides"
+type = "pdf"
+index = "slides.tex"
+
+%! src/index.tex"#,
+        );
+
+        let uri = &fixture.documents[1].uri;
+        let command = BuildCommand::new(&fixture.workspace, uri, Some("slides")).unwrap();
+        assert_eq!(command.program, "tectonic");
+        assert_eq!(
+            command.args,
+            vec![
+                "-X",
+                "build",
+                "--keep-logs",
+                "--keep-intermediates",
+                "--outdir",
+                "/texlab/build/slides/",
+                "--target",
+                "slides"
+            ]
+        );
+
+        assert!(BuildCommand::new(&fixture.workspace, uri, Some("web")).is_err());
+    }
+
+    #[test]
+    fn test_continuous_latexmk() {
+        let fixture = Fixture::parse(
+            r#"
+%! main.tex
+\begin{document}
+\end{document}"#,
+        );
+
+        let uri = &fixture.documents[0].uri;
+        let command = BuildCommand::continuous(&fixture.workspace, uri, None).unwrap();
+        assert_eq!(command.program, "latexmk");
+        assert_eq!(
+            command.args,
+            vec![
+                "-pvc",
+                "-view=none",
+                "-pdf",
+                "-interaction=nonstopmode",
+                "-synctex=1",
+                "/texlab/main.tex"
+            ]
+        );
+    }
+}
@@ -56,10 +56,7 @@ impl ForwardSearch {
             .lookup(uri)
             .ok_or_else(|| ForwardSearchError::TexNotFound(uri.clone()))?;
 
-        let parent = deps::parents(workspace, child)
-            .into_iter()
-            .next()
-            .unwrap_or(child);
+        let parent = deps::root(workspace, child);
 
         log::debug!("[FwdSearch] root_document={}", parent.uri,);
 
@@ -83,7 +80,10 @@ impl ForwardSearch {
         Ok(Self { program, args })
     }
 
-    fn find_pdf(workspace: &Workspace, document: &Document) -> Result<PathBuf, ForwardSearchError> {
+    pub fn find_pdf(
+        workspace: &Workspace,
+        document: &Document,
+    ) -> Result<PathBuf, ForwardSearchError> {
         let root = ProjectRoot::walk_and_find(workspace, &document.dir);
 
         log::debug!("[FwdSearch] root={root:#?}");
@@ -93,7 +93,7 @@ impl ForwardSearch {
             .to_file_path()
             .map_err(|()| ForwardSearchError::InvalidPath(document.uri.clone()))?;
 
-        let pdf_name_override = workspace.config().build.output_filename.clone();
+        let pdf_name_override = root.build.output_filename.clone();
         log::debug!("[FwdSearch] pdf_name_override={pdf_name_override:?}");
 
         let pdf_name = pdf_name_override
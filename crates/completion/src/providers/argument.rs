@@ -3,7 +3,7 @@ use rowan::{ast::AstNode, TokenAtOffset};
 use syntax::latex;
 
 use crate::{
-    util::{included_packages, is_inside_latex_curly, CompletionBuilder},
+    util::{is_inside_latex_curly, CompletionBuilder},
     ArgumentData, CompletionItem, CompletionItemData, CompletionParams,
 };
 
@@ -24,7 +24,7 @@ pub fn complete_arguments<'a>(
     let command_name = command.name()?;
     let command_name = &command_name.text()[1..];
 
-    for package in included_packages(&params.feature) {
+    for package in params.feature.project.included_packages() {
         for package_command in package
             .commands
             .iter()
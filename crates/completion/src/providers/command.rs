@@ -3,7 +3,7 @@ use rowan::{TextRange, TextSize};
 use syntax::{bibtex, latex};
 
 use crate::{
-    util::{included_packages, CompletionBuilder, ProviderContext},
+    util::{CompletionBuilder, ProviderContext},
     CommandData, CompletionItem, CompletionItemData, CompletionParams,
 };
 
@@ -57,7 +57,7 @@ impl<'a, 'b> Processor<'a, 'b> {
     }
 
     pub fn add_library(&mut self) -> Option<()> {
-        for package in included_packages(&self.0.params.feature) {
+        for package in self.0.params.feature.project.included_packages() {
             let commands_with_score = package.commands.iter().filter_map(|command| {
                 let matcher = &self.0.builder.matcher;
                 let score = matcher.score(&command.name, &self.0.cursor.text)?;
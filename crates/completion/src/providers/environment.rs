@@ -3,7 +3,7 @@ use rowan::ast::AstNode;
 use syntax::latex;
 
 use crate::{
-    util::{find_curly_group_word, included_packages, CompletionBuilder, ProviderContext},
+    util::{find_curly_group_word, CompletionBuilder, ProviderContext},
     CompletionItem, CompletionItemData, CompletionParams, EnvironmentData,
 };
 
@@ -45,7 +45,7 @@ struct Processor<'a, 'b> {
 
 impl<'a, 'b> Processor<'a, 'b> {
     fn add_library(&mut self) {
-        for package in included_packages(&self.inner.params.feature) {
+        for package in self.inner.params.feature.project.included_packages() {
             let envs_with_score = package.environments.iter().filter_map(|env| {
                 let matcher = &self.inner.builder.matcher;
                 let score = matcher.score(env, &self.inner.cursor.text)?;
@@ -113,10 +113,7 @@ fn current_dir(
     graphics_path: Option<&str>,
 ) -> Option<PathBuf> {
     let workspace = &params.workspace;
-    let parent = deps::parents(workspace, params.document)
-        .iter()
-        .next()
-        .map_or(params.document, Clone::clone);
+    let parent = deps::root(workspace, params.document);
 
     let root = ProjectRoot::walk_and_find(workspace, &parent.dir);
 
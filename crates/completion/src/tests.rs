@@ -368,6 +368,7 @@ fn citation() {
                             full_range: 88..107,
                             keywords: "bar:2005 @article",
                             category: Article,
+                            aliases: [],
                         },
                     },
                 ),
@@ -384,6 +385,7 @@ fn citation() {
                             full_range: 0..86,
                             keywords: "foo:2019 @article Foo Bar Baz Qux 2019",
                             category: Article,
+                            aliases: [],
                         },
                     },
                 ),
@@ -418,6 +420,7 @@ fn citation_open_brace() {
                             full_range: 0..14,
                             keywords: "foo @article",
                             category: Article,
+                            aliases: [],
                         },
                     },
                 ),
@@ -453,6 +456,7 @@ fn citation_open_brace_multiple() {
                             full_range: 0..14,
                             keywords: "foo @article",
                             category: Article,
+                            aliases: [],
                         },
                     },
                 ),
@@ -487,6 +491,7 @@ fn citation_acronym() {
                             full_range: 0..14,
                             keywords: "foo @article",
                             category: Article,
+                            aliases: [],
                         },
                     },
                 ),
@@ -1,29 +1,53 @@
 use base_db::{
     semantics::bib,
     util::queries::{self, Object},
+    DocumentData,
 };
 
 use crate::DefinitionContext;
 
-use super::DefinitionResult;
+use super::{DefinitionResult, DefinitionTarget};
 
 pub(super) fn goto_definition(context: &mut DefinitionContext) -> Option<()> {
     let feature = &context.params.feature;
-    let data = feature.document.data.as_tex()?;
+    let offset = context.params.offset;
 
-    let citation = queries::object_at_cursor(
-        &data.semantics.citations,
-        context.params.offset,
-        queries::SearchMode::Full,
-    )?;
+    let (name, origin_selection_range) = match &feature.document.data {
+        DocumentData::Tex(data) => {
+            let citation = queries::object_at_cursor(
+                &data.semantics.citations,
+                offset,
+                queries::SearchMode::Full,
+            )?;
+
+            (citation.object.name_text(), citation.object.name_range())
+        }
+        DocumentData::Bib(data) => {
+            let link = queries::object_at_cursor(
+                &data.semantics.links,
+                offset,
+                queries::SearchMode::Name,
+            )?;
+
+            (link.object.name_text(), link.object.name_range())
+        }
+        _ => return None,
+    };
+
+    for (document, entry) in
+        bib::Entry::find_all(&feature.project).filter(|(_, entry)| entry.has_key(name))
+    {
+        let target_selection_range = entry
+            .aliases
+            .iter()
+            .find(|alias| alias.text == name)
+            .map_or(entry.name.range, |alias| alias.range);
 
-    let name = citation.object.name_text();
-    for (document, entry) in queries::objects_with_name::<bib::Entry>(&feature.project, name) {
         context.results.insert(DefinitionResult {
-            origin_selection_range: citation.object.name_range(),
-            target: document,
+            origin_selection_range,
+            target: DefinitionTarget::Document(document),
             target_range: entry.full_range,
-            target_selection_range: entry.name.range,
+            target_selection_range,
         });
     }
 
@@ -4,7 +4,7 @@ use syntax::latex;
 
 use crate::DefinitionContext;
 
-use super::DefinitionResult;
+use super::{DefinitionResult, DefinitionTarget};
 
 pub(super) fn goto_definition(context: &mut DefinitionContext) -> Option<()> {
     let feature = &context.params.feature;
@@ -30,7 +30,7 @@ pub(super) fn goto_definition(context: &mut DefinitionContext) -> Option<()> {
             .filter(|(_, command)| command.text() == name.text())
             .map(|(target_range, command)| DefinitionResult {
                 origin_selection_range,
-                target: document,
+                target: DefinitionTarget::Document(document),
                 target_range,
                 target_selection_range: command.text_range(),
             });
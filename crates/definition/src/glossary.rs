@@ -0,0 +1,33 @@
+use base_db::{
+    semantics::tex,
+    util::queries::{self, Object, ObjectKind},
+};
+
+use crate::DefinitionContext;
+
+use super::{DefinitionResult, DefinitionTarget};
+
+pub(super) fn goto_definition(context: &mut DefinitionContext) -> Option<()> {
+    let feature = &context.params.feature;
+    let data = feature.document.data.as_tex()?;
+    let reference = queries::object_at_cursor(
+        &data.semantics.glossary_entries,
+        context.params.offset,
+        queries::SearchMode::Full,
+    )?;
+
+    let name = reference.object.name_text();
+    for (document, entry) in
+        queries::objects_with_name::<tex::GlossaryEntry>(&feature.project, name)
+            .filter(|(_, entry)| entry.kind() == ObjectKind::Definition)
+    {
+        context.results.insert(DefinitionResult {
+            origin_selection_range: reference.object.name_range(),
+            target: DefinitionTarget::Document(document),
+            target_range: entry.full_range,
+            target_selection_range: entry.name.range,
+        });
+    }
+
+    Some(())
+}
@@ -0,0 +1,25 @@
+use base_db::{deps, semantics::tex::LinkKind};
+use rowan::TextRange;
+use url::Url;
+
+use crate::DefinitionContext;
+
+use super::{DefinitionResult, DefinitionTarget};
+
+pub(super) fn goto_definition(context: &mut DefinitionContext) -> Option<()> {
+    let feature = &context.params.feature;
+    let data = feature.document.data.as_tex()?;
+    let link = data.semantics.links.iter().find(|link| {
+        link.kind == LinkKind::Graphics && link.path.range.contains_inclusive(context.params.offset)
+    })?;
+
+    let path = deps::resolve_graphics(feature.workspace, feature.document, link)?;
+    context.results.insert(DefinitionResult {
+        origin_selection_range: link.path.range,
+        target: DefinitionTarget::File(Url::from_file_path(path).ok()?),
+        target_range: TextRange::default(),
+        target_selection_range: TextRange::default(),
+    });
+
+    Some(())
+}
@@ -3,7 +3,7 @@ use rowan::TextRange;
 
 use crate::DefinitionContext;
 
-use super::DefinitionResult;
+use super::{DefinitionResult, DefinitionTarget};
 
 pub(super) fn goto_definition(context: &mut DefinitionContext) -> Option<()> {
     let feature = &context.params.feature;
@@ -23,7 +23,9 @@ pub(super) fn goto_definition(context: &mut DefinitionContext) -> Option<()> {
             if origin_selection_range.contains_inclusive(context.params.offset) {
                 Some(DefinitionResult {
                     origin_selection_range,
-                    target: feature.workspace.lookup(&edge.target).unwrap(),
+                    target: DefinitionTarget::Document(
+                        feature.workspace.lookup(&edge.target).unwrap(),
+                    ),
                     target_range: TextRange::default(),
                     target_selection_range: TextRange::default(),
                 })
@@ -8,7 +8,7 @@ use base_db::{
 
 use crate::DefinitionContext;
 
-use super::DefinitionResult;
+use super::{DefinitionResult, DefinitionTarget};
 
 pub(super) fn goto_definition(context: &mut DefinitionContext) -> Option<()> {
     let feature = &context.params.feature;
@@ -28,7 +28,7 @@ pub(super) fn goto_definition(context: &mut DefinitionContext) -> Option<()> {
 
         context.results.insert(DefinitionResult {
             origin_selection_range: reference.object.name_range(),
-            target: document,
+            target: DefinitionTarget::Document(document),
             target_range,
             target_selection_range,
         });
@@ -1,5 +1,7 @@
 mod citation;
 mod command;
+mod glossary;
+mod graphics;
 mod include;
 mod label;
 mod string_ref;
@@ -7,6 +9,7 @@ mod string_ref;
 use base_db::{Document, FeatureParams};
 use rowan::{TextRange, TextSize};
 use rustc_hash::FxHashSet;
+use url::Url;
 
 #[derive(Debug)]
 pub struct DefinitionParams<'a> {
@@ -17,11 +20,18 @@ pub struct DefinitionParams<'a> {
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct DefinitionResult<'a> {
     pub origin_selection_range: TextRange,
-    pub target: &'a Document,
+    pub target: DefinitionTarget<'a>,
     pub target_range: TextRange,
     pub target_selection_range: TextRange,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum DefinitionTarget<'a> {
+    Document(&'a Document),
+    /// A file that is not part of the workspace like an image.
+    File(Url),
+}
+
 #[derive(Debug)]
 struct DefinitionContext<'a, 'b> {
     params: &'b DefinitionParams<'a>,
@@ -36,8 +46,10 @@ pub fn goto_definition<'a>(params: &DefinitionParams<'a>) -> FxHashSet<Definitio
 
     command::goto_definition(&mut context);
     include::goto_definition(&mut context);
+    graphics::goto_definition(&mut context);
     citation::goto_definition(&mut context);
     label::goto_definition(&mut context);
+    glossary::goto_definition(&mut context);
     string_ref::goto_definition(&mut context);
     context.results
 }
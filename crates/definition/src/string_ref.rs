@@ -3,7 +3,7 @@ use syntax::bibtex;
 
 use crate::DefinitionContext;
 
-use super::DefinitionResult;
+use super::{DefinitionResult, DefinitionTarget};
 
 pub(super) fn goto_definition(context: &mut DefinitionContext) -> Option<()> {
     let feature = &context.params.feature;
@@ -26,7 +26,7 @@ pub(super) fn goto_definition(context: &mut DefinitionContext) -> Option<()> {
     {
         context.results.insert(DefinitionResult {
             origin_selection_range,
-            target: feature.document,
+            target: DefinitionTarget::Document(feature.document),
             target_range: string.full_range,
             target_selection_range: string.name.range,
         });
@@ -1,7 +1,7 @@
 use rowan::TextRange;
 use rustc_hash::FxHashSet;
 
-use crate::{DefinitionParams, DefinitionResult};
+use crate::{DefinitionParams, DefinitionResult, DefinitionTarget};
 
 fn check(input: &str) {
     let fixture = test_utils::fixture::Fixture::parse(input);
@@ -22,7 +22,9 @@ fn check(input: &str) {
             {
                 expected.insert(DefinitionResult {
                     origin_selection_range,
-                    target: fixture.workspace.lookup(&document.uri).unwrap(),
+                    target: DefinitionTarget::Document(
+                        fixture.workspace.lookup(&document.uri).unwrap(),
+                    ),
                     target_range: *ranges.next().unwrap(),
                     target_selection_range,
                 });
@@ -102,6 +104,60 @@ fn test_entry() {
     )
 }
 
+#[test]
+fn test_entry_recorder() {
+    check(
+        r#"
+%! main.tex
+\newcommand{\bib}{\addbibresource}
+\bib{refs.bib}
+\cite{foo}
+      |
+      ^^^
+
+%! main.fls
+PWD /texlab
+INPUT main.tex
+INPUT refs.bib
+
+%! refs.bib
+@article{foo, bar = {baz}}
+         ^^^
+^^^^^^^^^^^^^^^^^^^^^^^^^^"#,
+    )
+}
+
+#[test]
+fn test_entry_crossref() {
+    check(
+        r#"
+%! main.bib
+@book{foo, title = {Foo}}
+      ^^^
+^^^^^^^^^^^^^^^^^^^^^^^^^
+@inbook{bar, crossref = {foo}}
+                          |
+                         ^^^"#,
+    )
+}
+
+#[test]
+fn test_entry_alias() {
+    check(
+        r#"
+%! main.tex
+\addbibresource{main.bib}
+\cite{baz}
+      |
+      ^^^
+
+%! main.bib
+@article{foo, ids = {bar, baz}}
+                          ^^^
+^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^"#,
+    )
+}
+
 #[test]
 fn test_string_simple() {
     check(
@@ -140,3 +196,44 @@ fn test_string_field() {
                 |"#,
     )
 }
+
+#[test]
+fn test_glossary_entry() {
+    check(
+        r#"
+%! main.tex
+\newglossaryentry{foo}{name=Foo, description=Bar}
+                  ^^^
+^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+\gls{foo}
+      |
+     ^^^"#,
+    )
+}
+
+#[test]
+fn test_acronym() {
+    check(
+        r#"
+%! main.tex
+\include{acronyms}
+\acrshort{foo}
+           |
+          ^^^
+
+%! acronyms.tex
+\newacronym{foo}{FOO}{Foo Bar}
+            ^^^
+^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^"#,
+    )
+}
+
+#[test]
+fn test_graphics_missing() {
+    check(
+        r#"
+%! main.tex
+\includegraphics{foo}
+                  |"#,
+    )
+}
@@ -0,0 +1,84 @@
+use base_db::{deps::Project, Document, Workspace};
+use line_index::LineCol;
+use multimap::MultiMap;
+use rowan::TextRange;
+use rustc_hash::FxHashMap;
+use syntax::blg::BlgError;
+use url::Url;
+
+use crate::types::Diagnostic;
+
+pub fn update(
+    workspace: &Workspace,
+    blg_document: &Document,
+    results: &mut FxHashMap<Url, MultiMap<Url, Diagnostic>>,
+) -> Option<()> {
+    let mut errors = MultiMap::default();
+
+    let data = blg_document.data.as_blg()?;
+
+    let project = Project::from_child(workspace, blg_document);
+    let mut bib_documents: Vec<&Document> = project
+        .documents
+        .into_iter()
+        .filter(|document| document.data.as_bib().is_some())
+        .collect();
+
+    bib_documents.sort_by(|a, b| a.uri.cmp(&b.uri));
+
+    for error in &data.errors {
+        let candidates = bib_documents.iter().copied().filter(|document| {
+            error.file.as_deref().map_or(true, |file| {
+                document
+                    .path
+                    .as_deref()
+                    .is_some_and(|path| path.ends_with(file))
+            })
+        });
+
+        let Some((bib_document, range)) = find_location(candidates, error) else {
+            continue;
+        };
+
+        let diagnostic = Diagnostic::Blg(range, error.clone());
+        errors.insert(bib_document.uri.clone(), diagnostic);
+    }
+
+    results.insert(blg_document.uri.clone(), errors);
+    Some(())
+}
+
+/// Finds the name of the entry that caused the error.
+///
+/// If the log does not mention the entry, then the entry is looked up by the line number.
+fn find_location<'a>(
+    mut candidates: impl Iterator<Item = &'a Document>,
+    error: &BlgError,
+) -> Option<(&'a Document, TextRange)> {
+    if let Some(name) = &error.entry {
+        return candidates.find_map(|document| {
+            let data = document.data.as_bib()?;
+            let entry = data
+                .semantics
+                .entries
+                .iter()
+                .find(|entry| &entry.name.text == name)?;
+
+            Some((document, entry.name.range))
+        });
+    }
+
+    let line = error.line?;
+    let document = candidates.next()?;
+    let offset = document.line_index.offset(LineCol { line, col: 0 })?;
+    let range = document
+        .data
+        .as_bib()?
+        .semantics
+        .entries
+        .iter()
+        .find(|entry| entry.full_range.contains_inclusive(offset))
+        .map_or_else(|| TextRange::empty(offset), |entry| entry.name.range);
+
+    Some((document, range))
+}
@@ -1,4 +1,7 @@
-use base_db::{deps, Document, Workspace};
+use base_db::{
+    deps::{self, EdgeData},
+    Document, Workspace,
+};
 use line_index::LineCol;
 use multimap::MultiMap;
 use rowan::{TextLen, TextRange, TextSize};
@@ -13,19 +16,56 @@ pub fn update(
     log_document: &Document,
     results: &mut FxHashMap<Url, MultiMap<Url, Diagnostic>>,
 ) -> Option<()> {
-    let mut errors = MultiMap::default();
-
     let data = log_document.data.as_log()?;
 
-    let parents = deps::parents(workspace, log_document);
-    let root_document = parents.iter().next()?;
+    // The log file is an artifact of the root document that has the same file name.
+    let root_document = workspace
+        .graphs()
+        .values()
+        .flat_map(|graph| &graph.edges)
+        .filter(|edge| edge.target == log_document.uri && edge.data == EdgeData::Artifact)
+        .filter_map(|edge| workspace.lookup(&edge.source))
+        .min_by(|a, b| a.uri.cmp(&b.uri))
+        .or_else(|| {
+            deps::parents(workspace, log_document)
+                .into_iter()
+                .min_by(|a, b| a.uri.cmp(&b.uri))
+        })?;
+
+    let errors = map_errors(workspace, root_document, &data.errors)?;
+    results.insert(log_document.uri.clone(), errors);
+    Some(())
+}
+
+/// Maps the errors that have been parsed from the output of a running build.
+///
+/// The diagnostics are stored under the URI of the log file so that they are replaced
+/// once the log file itself is loaded.
+pub fn update_live(
+    workspace: &Workspace,
+    log_uri: Url,
+    root_document: &Document,
+    errors: &[BuildError],
+    results: &mut FxHashMap<Url, MultiMap<Url, Diagnostic>>,
+) -> Option<()> {
+    let errors = map_errors(workspace, root_document, errors)?;
+    results.insert(log_uri, errors);
+    Some(())
+}
+
+fn map_errors(
+    workspace: &Workspace,
+    root_document: &Document,
+    errors: &[BuildError],
+) -> Option<MultiMap<Url, Diagnostic>> {
+    let mut results = MultiMap::default();
 
     let base_path = root_document
         .path
         .as_deref()
         .and_then(|path| path.parent())?;
 
-    for error in &data.errors {
+    for error in errors {
         let full_path = base_path.join(&error.relative_path);
         let Ok(full_path_uri) = Url::from_file_path(&full_path) else {
             continue;
@@ -33,29 +73,56 @@ pub fn update(
 
         let tex_document = workspace.lookup(&full_path_uri).unwrap_or(root_document);
 
-        let range = find_range_of_hint(tex_document, error).unwrap_or_else(|| {
-            let line = error.line.unwrap_or(0);
-            let offset = tex_document
-                .line_index
-                .offset(LineCol { line, col: 0 })
-                .unwrap_or(TextSize::from(0));
+        let range = find_range_of_column(tex_document, error)
+            .or_else(|| find_range_of_hint(tex_document, error))
+            .unwrap_or_else(|| {
+                let line = error.line.unwrap_or(0);
+                let offset = tex_document
+                    .line_index
+                    .offset(LineCol { line, col: 0 })
+                    .unwrap_or(TextSize::from(0));
 
-            TextRange::empty(offset)
-        });
+                TextRange::empty(offset)
+            });
 
         let diagnostic = Diagnostic::Build(range, error.clone());
-        errors.insert(tex_document.uri.clone(), diagnostic);
+        results.insert(tex_document.uri.clone(), diagnostic);
     }
 
-    results.insert(log_document.uri.clone(), errors);
-    Some(())
+    Some(results)
+}
+
+/// Finds the range of the last word before the position where TeX stopped reading the line.
+fn find_range_of_column(document: &Document, error: &BuildError) -> Option<TextRange> {
+    let (line_start, line_text) = find_line(document, error.line?)?;
+    let column = error.column? as usize;
+    let end = line_text
+        .char_indices()
+        .nth(column)
+        .map_or(line_text.len(), |(index, _)| index);
+
+    let before = line_text[..end].trim_end();
+    let word = error
+        .hint
+        .as_deref()
+        .and_then(|hint| hint.split_whitespace().last())
+        .filter(|word| before.ends_with(word))
+        .unwrap_or_default();
+
+    let word_end = line_start + TextSize::try_from(before.len()).ok()?;
+    Some(TextRange::new(word_end - word.text_len(), word_end))
 }
 
 fn find_range_of_hint(document: &Document, error: &BuildError) -> Option<TextRange> {
-    let line = error.line?;
     let hint = error.hint.as_deref()?;
-    let line_index = &document.line_index;
+    let (line_start, line_text) = find_line(document, error.line?)?;
+    let hint_start = line_start + TextSize::try_from(line_text.find(hint)?).unwrap();
+    let hint_end = hint_start + hint.text_len();
+    Some(TextRange::new(hint_start, hint_end))
+}
 
+fn find_line(document: &Document, line: u32) -> Option<(TextSize, &str)> {
+    let line_index = &document.line_index;
     let line_start = line_index.offset(LineCol { line, col: 0 })?;
     let line_end = line_index
         .offset(LineCol {
@@ -65,7 +132,5 @@ fn find_range_of_hint(document: &Document, error: &BuildError) -> Option<TextRan
         .unwrap_or_else(|| document.text.text_len());
 
     let line_text = &document.text[line_start.into()..line_end.into()];
-    let hint_start = line_start + TextSize::try_from(line_text.find(hint)?).unwrap();
-    let hint_end = hint_start + hint.text_len();
-    Some(TextRange::new(hint_start, hint_end))
+    Some((line_start, line_text.trim_end_matches(['\r', '\n'])))
 }
@@ -26,10 +26,7 @@ impl Command {
     pub fn new(workspace: &Workspace, document: &Document) -> Option<Self> {
         document.data.as_tex()?;
 
-        let parent = deps::parents(workspace, document)
-            .into_iter()
-            .next()
-            .unwrap_or(document);
+        let parent = deps::root(workspace, document);
 
         if parent.path.is_none() {
             log::warn!("Calling ChkTeX on non-local files is not supported yet.");
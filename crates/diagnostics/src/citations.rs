@@ -1,10 +1,10 @@
 use base_db::{
     deps::Project,
-    semantics::{bib::Entry, tex::Citation},
+    semantics::bib::{self, Entry},
     util::queries::{self, Object},
     Document, Workspace,
 };
-use rustc_hash::{FxHashMap, FxHashSet};
+use rustc_hash::FxHashMap;
 use url::Url;
 
 use crate::types::{BibError, Diagnostic, TexError};
@@ -18,10 +18,7 @@ pub fn detect_undefined_citations<'a>(
 ) -> Option<()> {
     let data = document.data.as_tex()?;
 
-    let entries: FxHashSet<&str> = Entry::find_all(project)
-        .map(|(_, entry)| entry.name_text())
-        .collect();
-
+    let entries = bib::entry_keys(project);
     for citation in &data.semantics.citations {
         let name = citation.name_text();
         if name != "*" && !entries.contains(name) {
@@ -48,12 +45,9 @@ pub fn detect_unused_entries<'a>(
         return None;
     }
 
-    let citations: FxHashSet<&str> = Citation::find_all(project)
-        .map(|(_, citation)| citation.name_text())
-        .collect();
-
+    let used_keys = bib::used_keys(project);
     for entry in &data.semantics.entries {
-        if !citations.contains(entry.name.text.as_str()) {
+        if !entry.is_used(&used_keys) {
             let diagnostic = Diagnostic::Bib(entry.name.range, BibError::UnusedEntry);
             results
                 .entry(document.uri.clone())
@@ -65,6 +59,30 @@ pub fn detect_unused_entries<'a>(
     Some(())
 }
 
+pub fn detect_undefined_crossrefs<'a>(
+    project: &Project<'a>,
+    document: &'a Document,
+    results: &mut FxHashMap<Url, Vec<Diagnostic>>,
+) -> Option<()> {
+    let data = document.data.as_bib()?;
+    if data.semantics.links.is_empty() {
+        return None;
+    }
+
+    let entries = bib::entry_keys(project);
+    for link in &data.semantics.links {
+        if !entries.contains(link.name_text()) {
+            let diagnostic = Diagnostic::Bib(link.name.range, BibError::UndefinedCrossref);
+            results
+                .entry(document.uri.clone())
+                .or_default()
+                .push(diagnostic);
+        }
+    }
+
+    Some(())
+}
+
 pub fn detect_duplicate_entries(
     workspace: &Workspace,
     results: &mut FxHashMap<Url, Vec<Diagnostic>>,
@@ -0,0 +1,170 @@
+use base_db::{
+    data::{BibtexEntryType, BibtexFieldType},
+    Document,
+};
+use bibtex_utils::field::{
+    author::{AuthorField, AuthorFieldData},
+    date::{DateField, DateFieldData},
+    number::{NumberField, NumberFieldData},
+    text::TextFieldData,
+    FieldParseCache,
+};
+use multimap::MultiMap;
+use rowan::{ast::AstNode, TextRange};
+use rustc_hash::FxHashMap;
+use syntax::bibtex::{self, HasName, HasType, HasValue};
+use url::Url;
+
+use crate::types::{BibError, Diagnostic};
+
+/// Checks the fields of every BibTeX entry against the data model of biblatex.
+pub fn update(document: &Document, results: &mut MultiMap<Url, Diagnostic>) -> Option<()> {
+    let data = document.data.as_bib()?;
+    let cache = &data.semantics.expanded_defs;
+    for entry in data.root_node().children().filter_map(bibtex::Entry::cast) {
+        for diagnostic in analyze_entry(&document.uri, &entry, cache) {
+            results.insert(document.uri.clone(), diagnostic);
+        }
+    }
+
+    Some(())
+}
+
+fn analyze_entry(uri: &Url, entry: &bibtex::Entry, cache: &FieldParseCache) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut fields: FxHashMap<String, TextRange> = FxHashMap::default();
+    for field in entry.fields() {
+        let Some(name) = field.name_token() else {
+            continue;
+        };
+
+        let range = name.text_range();
+        let name = name.text().to_ascii_lowercase();
+        if let Some(first) = fields.get(&name) {
+            let others = vec![(uri.clone(), *first)];
+            diagnostics.push(Diagnostic::Bib(range, BibError::DuplicateField(others)));
+        } else {
+            fields.insert(name.clone(), range);
+        }
+
+        if BibtexFieldType::find(&name).is_none() {
+            diagnostics.push(Diagnostic::Bib(range, BibError::UnknownField));
+        } else if let Some(value) = field.value() {
+            if let Some(error) = check_value(&name, &value, cache) {
+                diagnostics.push(Diagnostic::Bib(value.syntax().text_range(), error));
+            }
+        }
+    }
+
+    let Some(key) = entry.name_token() else {
+        return diagnostics;
+    };
+
+    // Fields can be inherited from other entries which are not resolved here.
+    if fields.contains_key("crossref") || fields.contains_key("xdata") {
+        return diagnostics;
+    }
+
+    let Some(entry_type) = entry
+        .type_token()
+        .and_then(|token| BibtexEntryType::find(token.text()))
+    else {
+        return diagnostics;
+    };
+
+    for alternatives in entry_type.required_fields {
+        if !alternatives.iter().any(|name| fields.contains_key(*name)) {
+            let error = BibError::MissingRequiredField(alternatives.join("/"));
+            diagnostics.push(Diagnostic::Bib(key.text_range(), error));
+        }
+    }
+
+    diagnostics
+}
+
+fn check_value(name: &str, value: &bibtex::Value, cache: &FieldParseCache) -> Option<BibError> {
+    let TextFieldData { text } = TextFieldData::parse(value, cache)?;
+    if text.trim().is_empty() {
+        return None;
+    }
+
+    if let Some(field) = DateField::parse(name) {
+        let is_valid = match (field, DateFieldData::parse(value, cache)?) {
+            (DateField::Month, DateFieldData::Month(_)) => true,
+            (DateField::Month, DateFieldData::Year(month)) => (1..=12).contains(&month),
+            (DateField::Month, _) => false,
+            (DateField::Year, DateFieldData::Year(_)) => true,
+            (DateField::Year, _) => false,
+            (_, DateFieldData::Date(_) | DateFieldData::Year(_)) => true,
+            (_, DateFieldData::Month(_)) => false,
+            (_, DateFieldData::Other(text)) => is_date_range(text.trim()),
+        };
+
+        (!is_valid).then_some(BibError::InvalidDate)
+    } else if AuthorField::parse(name).is_some() {
+        (!is_name_list(&text)).then_some(BibError::InvalidNameList)
+    } else if let Some(NumberField::Volume | NumberField::Volumes | NumberField::PageTotal) =
+        NumberField::parse(name)
+    {
+        let data = NumberFieldData::parse(value, cache)?;
+        matches!(data, NumberFieldData::Other(_)).then_some(BibError::InvalidNumber)
+    } else {
+        None
+    }
+}
+
+/// Checks for the ISO 8601-2 dates that biblatex supports like `2020-05/2021` or `2020-05-12T10:00`.
+fn is_date_range(text: &str) -> bool {
+    let is_open = |date: &str| date.is_empty() || date == "..";
+    match text.split_once('/') {
+        Some((start, end)) => {
+            !(is_open(start) && is_open(end))
+                && (is_open(start) || is_date(start))
+                && (is_open(end) || is_date(end))
+        }
+        None => is_date(text),
+    }
+}
+
+fn is_date(text: &str) -> bool {
+    let text = text.trim_end_matches(['?', '~', '%']);
+    let (date, time) = text.split_once('T').unwrap_or((text, "00"));
+    let date = date.strip_prefix('-').unwrap_or(date);
+
+    let is_digits = |part: &str, len: usize| {
+        part.len() == len && part.chars().all(|c| c.is_ascii_digit() || c == 'X')
+    };
+
+    let mut parts = date.split('-');
+    let year = parts.next().is_some_and(|year| is_digits(year, 4));
+    let rest = parts.all(|part| is_digits(part, 2));
+    let time = !time.is_empty()
+        && time
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, ':' | '.' | '+' | '-' | 'Z'));
+
+    year && rest && date.split('-').count() <= 3 && time
+}
+
+/// Checks a list of names separated by `and`. Names that consist of a single word
+/// like organizations or `others` are accepted without parsing them.
+fn is_name_list(text: &str) -> bool {
+    let mut names = vec![Vec::new()];
+    for word in text.split_whitespace() {
+        if word.eq_ignore_ascii_case("and") {
+            names.push(Vec::new());
+        } else {
+            names.last_mut().unwrap().push(word);
+        }
+    }
+
+    names.iter().all(|words| {
+        let name = words.join(" ");
+        let commas = name.matches(',').count();
+        !words.is_empty()
+            && !name.starts_with(',')
+            && !name.ends_with(',')
+            && commas <= 2
+            && (words.len() == 1 || AuthorFieldData::parse_text(&name).is_some())
+    })
+}
@@ -0,0 +1,73 @@
+use base_db::{
+    deps::Project,
+    semantics::tex::{GlossaryEntry, GlossaryEntryKind},
+    util::queries::{Object, ObjectKind},
+    Document,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use url::Url;
+
+use crate::types::{Diagnostic, TexError};
+
+pub fn detect_undefined_glossary_entries<'a>(
+    project: &Project<'a>,
+    document: &'a Document,
+    results: &mut FxHashMap<Url, Vec<Diagnostic>>,
+) -> Option<()> {
+    let data = document.data.as_tex()?;
+
+    // The entries of bib2gls are defined in `.bib` files that are only read when building.
+    if project
+        .documents
+        .iter()
+        .filter_map(|document| document.data.as_tex())
+        .flat_map(|data| data.semantics.commands.iter())
+        .any(|command| command.text == "GlsXtrLoadResources")
+    {
+        return None;
+    }
+
+    let definitions: FxHashSet<&str> = GlossaryEntry::find_all(project)
+        .filter(|(_, entry)| entry.kind() == ObjectKind::Definition)
+        .map(|(_, entry)| entry.name_text())
+        .collect();
+
+    for entry in &data.semantics.glossary_entries {
+        if entry.kind == GlossaryEntryKind::Reference && !definitions.contains(entry.name_text()) {
+            let diagnostic = Diagnostic::Tex(entry.name.range, TexError::UndefinedGlossaryEntry);
+            results
+                .entry(document.uri.clone())
+                .or_default()
+                .push(diagnostic);
+        }
+    }
+
+    Some(())
+}
+
+pub fn detect_unused_acronyms<'a>(
+    project: &Project<'a>,
+    document: &'a Document,
+    results: &mut FxHashMap<Url, Vec<Diagnostic>>,
+) -> Option<()> {
+    let data = document.data.as_tex()?;
+
+    let references: FxHashSet<&str> = GlossaryEntry::find_all(project)
+        .filter(|(_, entry)| entry.kind() == ObjectKind::Reference)
+        .map(|(_, entry)| entry.name_text())
+        .collect();
+
+    for entry in &data.semantics.glossary_entries {
+        if entry.kind == GlossaryEntryKind::AcronymDefinition
+            && !references.contains(entry.name_text())
+        {
+            let diagnostic = Diagnostic::Tex(entry.name.range, TexError::UnusedAcronym);
+            results
+                .entry(document.uri.clone())
+                .or_default()
+                .push(diagnostic);
+        }
+    }
+
+    Some(())
+}
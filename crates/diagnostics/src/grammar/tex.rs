@@ -1,5 +1,6 @@
-use base_db::{Config, Document, TexDocumentData};
+use base_db::{Document, TexDocumentData};
 use multimap::MultiMap;
+use parser::SyntaxConfig;
 use rowan::{ast::AstNode, NodeOrToken, TextRange};
 use syntax::latex;
 use url::Url;
@@ -8,7 +9,7 @@ use crate::types::{Diagnostic, TexError};
 
 pub fn update(
     document: &Document,
-    config: &Config,
+    config: &SyntaxConfig,
     results: &mut MultiMap<Url, Diagnostic>,
 ) -> Option<()> {
     let data = document.data.as_tex()?;
@@ -33,13 +34,13 @@ pub fn update(
 
 struct Analyzer<'a> {
     data: &'a TexDocumentData,
-    config: &'a Config,
+    config: &'a SyntaxConfig,
     diagnostics: Vec<Diagnostic>,
 }
 
 impl<'a> Analyzer<'a> {
     fn analyze_root(&mut self) {
-        let verbatim_envs = &self.config.syntax.verbatim_environments;
+        let verbatim_envs = &self.config.verbatim_environments;
 
         let mut traversal = self.data.root_node().preorder();
         while let Some(event) = traversal.next() {
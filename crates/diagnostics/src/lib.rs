@@ -1,8 +1,12 @@
+mod blg;
 mod build_log;
 pub mod chktex;
 mod citations;
+mod fields;
+mod glossary;
 mod grammar;
 mod labels;
+mod links;
 mod manager;
 mod types;
 
@@ -0,0 +1,80 @@
+use base_db::{
+    deps::{EdgeData, GraphicsDirs},
+    semantics::tex::LinkKind,
+    Document, Workspace,
+};
+use itertools::Itertools;
+use rustc_hash::{FxHashMap, FxHashSet};
+use url::Url;
+
+use crate::types::{Diagnostic, TexError};
+
+pub fn detect_unresolved_links(
+    workspace: &Workspace,
+    results: &mut FxHashMap<Url, Vec<Diagnostic>>,
+) {
+    let graphs = workspace.graphs().values();
+
+    // A document can be part of multiple projects with different source directories.
+    // We only report a link if none of them is able to resolve it.
+    let resolved: FxHashSet<_> = graphs
+        .clone()
+        .flat_map(|graph| graph.edges.iter())
+        .filter_map(|edge| match &edge.data {
+            EdgeData::DirectLink(data) => Some((&edge.source, &data.link)),
+            _ => None,
+        })
+        .collect();
+
+    let unresolved = graphs
+        .flat_map(|graph| graph.unresolved.iter())
+        .map(|unresolved| (&unresolved.source, &unresolved.link))
+        .filter(|key| !resolved.contains(key))
+        .unique()
+        .sorted_by_key(|(uri, link)| (*uri, link.path.range.start()));
+
+    for (uri, link) in unresolved {
+        let diagnostic = Diagnostic::Tex(link.path.range, TexError::UnresolvedLink);
+        results.entry(uri.clone()).or_default().push(diagnostic);
+    }
+}
+
+pub fn detect_missing_graphics(
+    workspace: &Workspace,
+    document: &Document,
+    previous: &FxHashMap<(GraphicsDirs, String), bool>,
+    cache: &mut FxHashMap<(GraphicsDirs, String), bool>,
+    results: &mut FxHashMap<Url, Vec<Diagnostic>>,
+) -> Option<()> {
+    let data = document.data.as_tex()?;
+
+    let mut links = data
+        .semantics
+        .links
+        .iter()
+        .filter(|link| link.kind == LinkKind::Graphics)
+        .peekable();
+
+    links.peek()?;
+    let dirs = GraphicsDirs::new(workspace, document);
+
+    for link in links {
+        let key = (dirs.clone(), link.path.text.clone());
+        let exists = match previous.get(&key) {
+            Some(exists) => *exists,
+            None => dirs.resolve(workspace, link).is_some(),
+        };
+
+        cache.insert(key, exists);
+
+        if !exists {
+            let diagnostic = Diagnostic::Tex(link.path.range, TexError::MissingGraphics);
+            results
+                .entry(document.uri.clone())
+                .or_default()
+                .push(diagnostic);
+        }
+    }
+
+    Some(())
+}
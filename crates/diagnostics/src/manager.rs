@@ -1,6 +1,11 @@
-use base_db::{deps::Project, util::filter_regex_patterns, Document, Owner, Workspace};
+use base_db::{
+    deps::{GraphicsDirs, Project, ProjectRoot},
+    util::filter_regex_patterns,
+    Document, Owner, Workspace,
+};
 use multimap::MultiMap;
 use rustc_hash::{FxHashMap, FxHashSet};
+use syntax::BuildError;
 use url::Url;
 
 use crate::types::Diagnostic;
@@ -11,6 +16,9 @@ pub struct Manager {
     grammar: MultiMap<Url, Diagnostic>,
     chktex: FxHashMap<Url, Vec<Diagnostic>>,
     build_log: FxHashMap<Url, MultiMap<Url, Diagnostic>>,
+    /// The log files whose diagnostics were reported by a running build before the log was loaded.
+    pending_logs: FxHashSet<Url>,
+    graphics: FxHashMap<(GraphicsDirs, String), bool>,
 }
 
 impl Manager {
@@ -21,11 +29,33 @@ impl Manager {
         }
 
         self.grammar.remove(&document.uri);
-        super::grammar::tex::update(document, workspace.config(), &mut self.grammar);
+        let root = ProjectRoot::walk_and_find(workspace, &document.dir);
+        super::grammar::tex::update(document, &root.syntax, &mut self.grammar);
         super::grammar::bib::update(document, &mut self.grammar);
+        super::fields::update(document, &mut self.grammar);
 
         self.build_log.remove(&document.uri);
+        self.pending_logs.remove(&document.uri);
         super::build_log::update(workspace, document, &mut self.build_log);
+        super::blg::update(workspace, document, &mut self.build_log);
+    }
+
+    /// Updates the diagnostics of a project with the errors reported by a running build.
+    pub fn update_build_output(
+        &mut self,
+        workspace: &Workspace,
+        log_uri: Url,
+        root_document: &Document,
+        errors: &[BuildError],
+    ) {
+        self.pending_logs.insert(log_uri.clone());
+        super::build_log::update_live(
+            workspace,
+            log_uri,
+            root_document,
+            errors,
+            &mut self.build_log,
+        );
     }
 
     /// Updates the ChkTeX diagnostics for the given document.
@@ -42,11 +72,17 @@ impl Manager {
 
         self.grammar.retain(|uri, _| uris.contains(uri));
         self.chktex.retain(|uri, _| uris.contains(uri));
-        self.build_log.retain(|uri, _| uris.contains(uri));
+        self.build_log
+            .retain(|uri, _| uris.contains(uri) || self.pending_logs.contains(uri));
+    }
+
+    /// Forgets which images exist on disk, e.g. after files have been created or removed.
+    pub fn invalidate_graphics(&mut self) {
+        self.graphics.clear();
     }
 
     /// Returns all filtered diagnostics for the given workspace.
-    pub fn get(&self, workspace: &Workspace) -> FxHashMap<Url, Vec<Diagnostic>> {
+    pub fn get(&mut self, workspace: &Workspace) -> FxHashMap<Url, Vec<Diagnostic>> {
         let mut results: FxHashMap<Url, Vec<Diagnostic>> = FxHashMap::default();
         for (uri, diagnostics) in &self.grammar {
             results
@@ -74,6 +110,8 @@ impl Manager {
             }
         }
 
+        // Only keep the images that are still referenced to avoid growing the cache while typing.
+        let graphics = std::mem::take(&mut self.graphics);
         for document in workspace
             .iter()
             .filter(|document| Self::is_relevant(document))
@@ -81,11 +119,22 @@ impl Manager {
             let project = Project::from_child(workspace, document);
             super::citations::detect_undefined_citations(&project, document, &mut results);
             super::citations::detect_unused_entries(&project, document, &mut results);
+            super::citations::detect_undefined_crossrefs(&project, document, &mut results);
+            super::glossary::detect_undefined_glossary_entries(&project, document, &mut results);
+            super::glossary::detect_unused_acronyms(&project, document, &mut results);
+            super::links::detect_missing_graphics(
+                workspace,
+                document,
+                &graphics,
+                &mut self.graphics,
+                &mut results,
+            );
         }
 
         super::citations::detect_duplicate_entries(workspace, &mut results);
         super::labels::detect_duplicate_labels(workspace, &mut results);
         super::labels::detect_undefined_and_unused_labels(workspace, &mut results);
+        super::links::detect_unresolved_links(workspace, &mut results);
 
         let config = &workspace.config().diagnostics;
 
@@ -65,6 +65,30 @@ fn test_bib_entry_missing_r_delim() {
                             14..14,
                             ExpectingRCurly,
                         ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "author",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "title",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "journaltitle/journal",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "year/date",
+                            ),
+                        ),
                     ],
                 ),
             ]
@@ -118,6 +142,34 @@ fn test_bib_field_missing_eq() {
                             23..23,
                             ExpectingEq,
                         ),
+                        Bib(
+                            18..23,
+                            UnknownField,
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "author",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "title",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "journaltitle/journal",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "year/date",
+                            ),
+                        ),
                     ],
                 ),
             ]
@@ -148,6 +200,34 @@ fn test_bib_field_missing_value() {
                             25..25,
                             ExpectingFieldValue,
                         ),
+                        Bib(
+                            18..23,
+                            UnknownField,
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "author",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "title",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "journaltitle/journal",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "year/date",
+                            ),
+                        ),
                     ],
                 ),
             ]
@@ -155,6 +235,170 @@ fn test_bib_field_missing_value() {
     )
 }
 
+#[test]
+fn test_bib_field_missing_required() {
+    check(
+        r#"
+%! main.bib
+@article{foo,
+    author = {Foo Bar},
+    title = {Baz},
+    journal = {Qux},
+}
+
+@inproceedings{bar,
+    author = {Foo Bar},
+    date = {2020-05},
+}
+
+@book{baz,
+    crossref = {qux},
+}"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/main.bib",
+                    [
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "year/date",
+                            ),
+                        ),
+                        Bib(
+                            96..99,
+                            MissingRequiredField(
+                                "title",
+                            ),
+                        ),
+                        Bib(
+                            96..99,
+                            MissingRequiredField(
+                                "booktitle",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            UnusedEntry,
+                        ),
+                        Bib(
+                            96..99,
+                            UnusedEntry,
+                        ),
+                        Bib(
+                            156..159,
+                            UnusedEntry,
+                        ),
+                        Bib(
+                            177..180,
+                            UndefinedCrossref,
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_bib_field_duplicate_and_unknown() {
+    check(
+        r#"
+%! main.bib
+@misc{foo,
+    title = {Foo},
+    Title = {Bar},
+    timestamp = {2024-01-01},
+}"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/main.bib",
+                    [
+                        Bib(
+                            34..39,
+                            DuplicateField(
+                                [
+                                    (
+                                        Url {
+                                            scheme: "file",
+                                            cannot_be_a_base: false,
+                                            username: "",
+                                            password: None,
+                                            host: None,
+                                            port: None,
+                                            path: "/texlab/main.bib",
+                                            query: None,
+                                            fragment: None,
+                                        },
+                                        15..20,
+                                    ),
+                                ],
+                            ),
+                        ),
+                        Bib(
+                            53..62,
+                            UnknownField,
+                        ),
+                        Bib(
+                            6..9,
+                            UnusedEntry,
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_bib_field_invalid_value() {
+    check(
+        r#"
+%! main.bib
+@misc{foo,
+    title = {Foo},
+    author = {Foo Bar and and Baz},
+    editor = {Foo Bar and others and {World Health Organization}},
+    date = {May 2020},
+    urldate = {2020-05/2021},
+    month = jan,
+    year = {2020-05},
+    volume = {2a},
+    volumes = {3},
+}"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/main.bib",
+                    [
+                        Bib(
+                            43..64,
+                            InvalidNameList,
+                        ),
+                        Bib(
+                            144..154,
+                            InvalidDate,
+                        ),
+                        Bib(
+                            214..223,
+                            InvalidDate,
+                        ),
+                        Bib(
+                            238..242,
+                            InvalidNumber,
+                        ),
+                        Bib(
+                            6..9,
+                            UnusedEntry,
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    );
+}
+
 #[test]
 fn test_tex_unmatched_braces() {
     check(
@@ -296,6 +540,30 @@ fn test_citation_unused() {
                 (
                     "file:///texlab/main.bib",
                     [
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "author",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "title",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "journaltitle/journal",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "year/date",
+                            ),
+                        ),
                         Bib(
                             9..12,
                             UnusedEntry,
@@ -306,3 +574,478 @@ fn test_citation_unused() {
         "#]],
     )
 }
+
+#[test]
+fn test_citation_alias() {
+    check(
+        r#"
+%! main.tex
+\addbibresource{main.bib}
+\cite{bar}
+
+%! main.bib
+@misc{foo, title = {Foo}, ids = {bar}}
+"#,
+        expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn test_crossref_undefined() {
+    check(
+        r#"
+%! main.tex
+\addbibresource{main.bib}
+\cite{foo}
+
+%! main.bib
+@inbook{foo, crossref = {bar}, xdata = {baz, qux}, author = {Foo Bar}}
+
+@book{bar, title = {Bar}, author = {Foo Bar}, year = {2020}}
+
+@xdata{baz, publisher = {Baz}}
+"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/main.bib",
+                    [
+                        Bib(
+                            45..48,
+                            UndefinedCrossref,
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_glossary_entry_undefined() {
+    check(
+        r#"
+%! main.tex
+\newglossaryentry{foo}{name=Foo, description=Foo}
+\gls{foo}
+\gls{bar}
+     ^^^
+"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/main.tex",
+                    [
+                        Tex(
+                            65..68,
+                            UndefinedGlossaryEntry,
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    )
+}
+
+#[test]
+fn test_glossary_entry_loaded() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\loadglsentries{terms}
+\gls{foo}
+
+%! terms.tex
+\newglossaryentry{foo}{name=Foo, description=Foo}
+"#,
+        expect![[r#"
+            []
+        "#]],
+    )
+}
+
+#[test]
+fn test_glossary_entry_bib2gls() {
+    check(
+        r#"
+%! main.tex
+\GlsXtrLoadResources[src={terms}]
+\gls{foo}
+"#,
+        expect![[r#"
+            []
+        "#]],
+    )
+}
+
+#[test]
+fn test_acronym_unused() {
+    check(
+        r#"
+%! main.tex
+\newacronym{foo}{FOO}{Foo}
+            ^^^
+\newacronym{bar}{BAR}{Bar}
+\acrshort{bar}
+"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/main.tex",
+                    [
+                        Tex(
+                            12..15,
+                            UnusedAcronym,
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    )
+}
+
+#[test]
+fn test_link_unresolved() {
+    check(
+        r#"
+%! main.tex
+\include{foo}
+\input{bar}
+       ^^^
+\addbibresource{baz.bib}
+                ^^^^^^^
+
+%! foo.tex
+"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/main.tex",
+                    [
+                        Tex(
+                            21..24,
+                            UnresolvedLink,
+                        ),
+                        Tex(
+                            42..49,
+                            UnresolvedLink,
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    )
+}
+
+#[test]
+fn test_link_resolved_by_parent() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\include{chapters/foo}
+\include{chapters/bar}
+
+%! chapters/foo.tex
+\input{chapters/bar}
+
+%! chapters/bar.tex
+"#,
+        expect![[r#"
+            []
+        "#]],
+    )
+}
+
+#[test]
+fn test_link_macro_parameter() {
+    check(
+        r#"
+%! main.tex
+\newcommand{\chap}[1]{\input{chapters/#1}}
+\newcommand{\part}[1]{\include{#1}}
+"#,
+        expect![[r#"
+            []
+        "#]],
+    )
+}
+
+#[test]
+fn test_graphics_missing() {
+    check(
+        r#"
+%! main.tex
+\graphicspath{{figures/}}
+\newcommand{\figure}[1]{\includegraphics{#1}}
+\includegraphics{foo}
+                 ^^^
+"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/main.tex",
+                    [
+                        Tex(
+                            89..92,
+                            MissingGraphics,
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    )
+}
+
+fn open_graphics_document(dir: &std::path::Path, text: &str) -> base_db::Workspace {
+    let mut workspace = base_db::Workspace::default();
+    workspace.open(
+        url::Url::from_file_path(dir.join("main.tex")).unwrap(),
+        text.into(),
+        distro::Language::Tex,
+        base_db::Owner::Client,
+        line_index::LineCol { line: 0, col: 0 },
+    );
+
+    workspace
+}
+
+#[test]
+fn test_graphics_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let workspace = open_graphics_document(dir.path(), "\\includegraphics{foo}");
+
+    let mut manager = crate::Manager::default();
+    assert_eq!(manager.get(&workspace).values().flatten().count(), 1);
+
+    std::fs::write(dir.path().join("foo.png"), "").unwrap();
+    assert_eq!(manager.get(&workspace).values().flatten().count(), 1);
+
+    manager.invalidate_graphics();
+    assert_eq!(manager.get(&workspace).values().flatten().count(), 0);
+}
+
+fn check_graphics_found(text: &str, image: &str) {
+    let dir = tempfile::tempdir().unwrap();
+    let image = dir.path().join(image);
+    std::fs::create_dir_all(image.parent().unwrap()).unwrap();
+    std::fs::write(&image, "").unwrap();
+
+    let workspace = open_graphics_document(dir.path(), text);
+    let mut manager = crate::Manager::default();
+    assert_eq!(manager.get(&workspace).values().flatten().count(), 0);
+
+    let document = workspace.iter().next().unwrap();
+    let data = document.data.as_tex().unwrap();
+    let link = data.semantics.links.last().unwrap();
+    let path = base_db::deps::resolve_graphics(&workspace, document, link);
+    assert_eq!(path, Some(image));
+}
+
+#[test]
+fn test_graphics_found() {
+    check_graphics_found("\\includegraphics{foo.png}", "foo.png");
+}
+
+#[test]
+fn test_graphics_found_without_extension() {
+    check_graphics_found("\\includegraphics{foo}", "foo.pdf");
+}
+
+#[test]
+fn test_graphics_found_in_graphics_path() {
+    check_graphics_found(
+        "\\graphicspath{{figures/}}\n\\includegraphics{foo}",
+        "figures/foo.jpg",
+    );
+}
+
+#[test]
+fn test_build_log_undefined_command() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+\begin{document}
+Foo \foo bar
+\end{document}
+
+%! main.log
+(./main.tex
+! Undefined control sequence.
+l.3 Foo \foo
+             bar
+The control sequence at the end of the top line
+of your error message was never \def'ed.
+
+)
+"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/main.tex",
+                    [
+                        Build(
+                            45..49,
+                            BuildError {
+                                relative_path: "./main.tex",
+                                level: Error,
+                                category: UndefinedControlSequence,
+                                message: "Undefined control sequence.",
+                                help: Some(
+                                    "The control sequence at the end of the top line of your error message was never \\def'ed.",
+                                ),
+                                hint: Some(
+                                    "Foo \\foo",
+                                ),
+                                line: Some(
+                                    2,
+                                ),
+                                column: Some(
+                                    8,
+                                ),
+                            },
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    )
+}
+
+#[test]
+fn test_build_output_cleanup() {
+    let fixture = test_utils::fixture::Fixture::parse(
+        r#"
+%! main.tex
+\documentclass{article}
+\begin{document}
+Foo \foo bar
+\end{document}"#,
+    );
+
+    let error = syntax::BuildError {
+        relative_path: "./main.tex".into(),
+        level: syntax::BuildErrorLevel::Error,
+        category: syntax::BuildErrorCategory::UndefinedControlSequence,
+        message: "Undefined control sequence.".into(),
+        help: None,
+        hint: Some("Foo \\foo".into()),
+        line: Some(2),
+        column: None,
+    };
+
+    let workspace = &fixture.workspace;
+    let document = &fixture.documents[0];
+    let log_uri = document.uri.join("main.log").unwrap();
+
+    let mut manager = crate::Manager::default();
+    manager.update_build_output(
+        workspace,
+        log_uri,
+        workspace.lookup(&document.uri).unwrap(),
+        &[error],
+    );
+    manager.cleanup(workspace);
+
+    let results = manager.get(workspace);
+    assert_eq!(results[&document.uri].len(), 1);
+}
+
+#[test]
+fn test_blg() {
+    check(
+        r#"
+%! main.tex
+\bibliography{main}
+\cite{foo}
+\cite{bar}
+
+%! main.bib
+@article{foo,
+    title = {Foo},
+}
+
+@article{bar,
+    title = {Bar}
+    author = {Baz}
+}
+
+%! main.blg
+Warning--empty journal in foo
+I was expecting a `,' or a `}'---line 7 of file main.bib
+ :     author = {Baz}
+ :     ^^^^^^
+I'm skipping whatever remains of this entry
+Warning--I didn't find a database entry for "qux"
+"#,
+        expect![[r#"
+            [
+                (
+                    "file:///texlab/main.bib",
+                    [
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "author",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "journaltitle/journal",
+                            ),
+                        ),
+                        Bib(
+                            9..12,
+                            MissingRequiredField(
+                                "year/date",
+                            ),
+                        ),
+                        Bib(
+                            45..48,
+                            MissingRequiredField(
+                                "journaltitle/journal",
+                            ),
+                        ),
+                        Bib(
+                            45..48,
+                            MissingRequiredField(
+                                "year/date",
+                            ),
+                        ),
+                        Blg(
+                            9..12,
+                            BlgError {
+                                level: Warning,
+                                message: "empty journal in foo",
+                                file: None,
+                                line: None,
+                                entry: Some(
+                                    "foo",
+                                ),
+                            },
+                        ),
+                        Blg(
+                            45..48,
+                            BlgError {
+                                level: Error,
+                                message: "I was expecting a `,' or a `}'",
+                                file: Some(
+                                    "main.bib",
+                                ),
+                                line: Some(
+                                    6,
+                                ),
+                                entry: None,
+                            },
+                        ),
+                    ],
+                ),
+            ]
+        "#]],
+    )
+}
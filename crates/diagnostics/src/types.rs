@@ -1,6 +1,6 @@
 use line_index::LineCol;
 use rowan::TextRange;
-use syntax::BuildError;
+use syntax::{blg::BlgError, BuildError};
 use url::Url;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -11,6 +11,10 @@ pub enum TexError {
     UnusedLabel,
     UndefinedLabel,
     UndefinedCitation,
+    UndefinedGlossaryEntry,
+    UnusedAcronym,
+    UnresolvedLink,
+    MissingGraphics,
     DuplicateLabel(Vec<(Url, TextRange)>),
 }
 
@@ -23,6 +27,14 @@ pub enum BibError {
     ExpectingFieldValue,
     UnusedEntry,
     DuplicateEntry(Vec<(Url, TextRange)>),
+    /// The alternatives of the missing field separated by `/`.
+    MissingRequiredField(String),
+    DuplicateField(Vec<(Url, TextRange)>),
+    UnknownField,
+    InvalidDate,
+    InvalidNameList,
+    InvalidNumber,
+    UndefinedCrossref,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -46,6 +58,7 @@ pub enum Diagnostic {
     Tex(TextRange, TexError),
     Bib(TextRange, BibError),
     Build(TextRange, BuildError),
+    Blg(TextRange, BlgError),
     Chktex(ChktexError),
 }
 
@@ -59,6 +72,10 @@ impl Diagnostic {
                 TexError::UnusedLabel => "Unused label",
                 TexError::UndefinedLabel => "Undefined reference",
                 TexError::UndefinedCitation => "Undefined reference",
+                TexError::UndefinedGlossaryEntry => "Undefined glossary entry",
+                TexError::UnusedAcronym => "Unused acronym",
+                TexError::UnresolvedLink => "File not found",
+                TexError::MissingGraphics => "Image file not found",
                 TexError::DuplicateLabel(_) => "Duplicate label",
             },
             Diagnostic::Bib(_, error) => match error {
@@ -69,8 +86,16 @@ impl Diagnostic {
                 BibError::ExpectingFieldValue => "Expecting a field value",
                 BibError::UnusedEntry => "Unused entry",
                 BibError::DuplicateEntry(_) => "Duplicate entry key",
+                BibError::MissingRequiredField(_) => "Missing required field",
+                BibError::DuplicateField(_) => "Duplicate field",
+                BibError::UnknownField => "Unknown field",
+                BibError::InvalidDate => "Invalid date",
+                BibError::InvalidNameList => "Invalid name list",
+                BibError::InvalidNumber => "Invalid number",
+                BibError::UndefinedCrossref => "Undefined cross-referenced entry",
             },
             Diagnostic::Build(_, error) => &error.message,
+            Diagnostic::Blg(_, error) => &error.message,
             Diagnostic::Chktex(error) => &error.message,
         }
     }
@@ -6,6 +6,8 @@ pub enum Language {
     Bib,
     Aux,
     Log,
+    Fls,
+    Blg,
     Root,
     Latexmkrc,
     Tectonic,
@@ -14,7 +16,10 @@ pub enum Language {
 impl Language {
     pub fn from_path(path: &Path) -> Option<Self> {
         let name = path.file_name()?;
-        if name.eq_ignore_ascii_case(".texlabroot") || name.eq_ignore_ascii_case("texlabroot") {
+        if name.eq_ignore_ascii_case(".texlabroot")
+            || name.eq_ignore_ascii_case("texlabroot")
+            || name.eq_ignore_ascii_case("texlab.toml")
+        {
             return Some(Self::Root);
         }
 
@@ -32,6 +37,8 @@ impl Language {
             "bib" | "bibtex" => Some(Self::Bib),
             "aux" => Some(Self::Aux),
             "log" => Some(Self::Log),
+            "fls" => Some(Self::Fls),
+            "blg" => Some(Self::Blg),
             _ => None,
         }
     }
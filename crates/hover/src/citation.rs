@@ -5,7 +5,11 @@ use syntax::bibtex;
 use crate::{Hover, HoverData, HoverParams};
 
 pub(super) fn find_hover<'a>(params: &HoverParams<'a>) -> Option<Hover<'a>> {
-    let HoverParams { feature, offset } = params;
+    let HoverParams {
+        feature,
+        offset,
+        citation_format,
+    } = params;
 
     let (name, range) = match &feature.document.data {
         DocumentData::Tex(data) => {
@@ -27,13 +31,26 @@ pub(super) fn find_hover<'a>(params: &HoverParams<'a>) -> Option<Hover<'a>> {
         _ => return None,
     };
 
-    let text = feature.project.documents.iter().find_map(|document| {
+    let (entry, data) = feature.project.documents.iter().find_map(|document| {
         let data = document.data.as_bib()?;
+        let entry = data
+            .semantics
+            .entries
+            .iter()
+            .find(|entry| entry.has_key(name))?;
         let root = bibtex::Root::cast(data.root_node())?;
-        let entry = root.find_entry(name)?;
-        citeproc::render(&entry, &data.semantics)
+        Some((root.find_entry(&entry.name.text)?, data))
     })?;
 
+    let style = citeproc::find_style(feature.workspace, feature.document);
+    let text = citeproc::render(
+        &entry,
+        &data.semantics,
+        &feature.project,
+        style.as_deref(),
+        *citation_format,
+    )?;
+
     let data = HoverData::Citation(text);
     Some(Hover { range, data })
 }
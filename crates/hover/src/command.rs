@@ -0,0 +1,68 @@
+use rowan::TextRange;
+use syntax::latex;
+
+use crate::{Hover, HoverData, HoverParams};
+
+pub(super) fn find_hover<'a>(params: &HoverParams<'a>) -> Option<Hover<'a>> {
+    let data = params.feature.document.data.as_tex()?;
+    let token = data
+        .root_node()
+        .token_at_offset(params.offset)
+        .find(|token| token.kind() == latex::COMMAND_NAME)?;
+
+    let name = &token.text()[1..];
+    let range = token.text_range();
+    find_user_definition(params, name, range).or_else(|| find_package_command(params, name, range))
+}
+
+fn find_user_definition<'a>(
+    params: &HoverParams<'a>,
+    name: &str,
+    range: TextRange,
+) -> Option<Hover<'a>> {
+    let (document, definition) = params
+        .feature
+        .project
+        .documents
+        .iter()
+        .filter_map(|document| Some((document, document.data.as_tex()?)))
+        .flat_map(|(document, data)| {
+            data.semantics
+                .command_definitions
+                .iter()
+                .map(move |definition| (document, definition))
+        })
+        .find(|(_, definition)| definition.name.text == name)?;
+
+    let text = &document.text[definition.full_range];
+    Some(Hover {
+        range,
+        data: HoverData::Definition(text.into()),
+    })
+}
+
+fn find_package_command<'a>(
+    params: &HoverParams<'a>,
+    name: &str,
+    range: TextRange,
+) -> Option<Hover<'a>> {
+    let (package, command) = params
+        .feature
+        .project
+        .included_packages()
+        .flat_map(|package| {
+            package
+                .commands
+                .iter()
+                .map(move |command| (package, command))
+        })
+        .find(|(_, command)| command.name == name)?;
+
+    Some(Hover {
+        range,
+        data: HoverData::Command {
+            file_names: &package.file_names,
+            command,
+        },
+    })
+}
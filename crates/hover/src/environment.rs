@@ -0,0 +1,63 @@
+use rowan::ast::AstNode;
+use syntax::latex;
+
+use crate::{Hover, HoverData, HoverParams};
+
+pub(super) fn find_hover<'a>(params: &HoverParams<'a>) -> Option<Hover<'a>> {
+    let data = params.feature.document.data.as_tex()?;
+    let key = data
+        .root_node()
+        .token_at_offset(params.offset)
+        .filter(|token| token.kind() == latex::WORD)
+        .find_map(|token| token.parent_ancestors().find_map(latex::Key::cast))?;
+
+    let group = latex::CurlyGroupWord::cast(key.syntax().parent()?)?;
+    if !matches!(
+        group.syntax().parent()?.kind(),
+        latex::BEGIN | latex::END | latex::ENVIRONMENT_DEFINITION
+    ) {
+        return None;
+    }
+
+    let name = key.to_string();
+    let range = latex::small_range(&key);
+
+    let user_definition = params
+        .feature
+        .project
+        .documents
+        .iter()
+        .filter_map(|document| Some((document, document.data.as_tex()?)))
+        .flat_map(|(document, data)| {
+            data.semantics
+                .environment_definitions
+                .iter()
+                .map(move |definition| (document, definition))
+        })
+        .find(|(_, definition)| definition.name.text == name);
+
+    if let Some((document, definition)) = user_definition {
+        let text = &document.text[definition.full_range];
+        return Some(Hover {
+            range,
+            data: HoverData::Definition(text.into()),
+        });
+    }
+
+    let (package, name) = params
+        .feature
+        .project
+        .included_packages()
+        .find_map(|package| {
+            let name = package.environments.iter().find(|env| **env == name)?;
+            Some((package, *name))
+        })?;
+
+    Some(Hover {
+        range,
+        data: HoverData::Environment {
+            file_names: &package.file_names,
+            name,
+        },
+    })
+}
@@ -0,0 +1,29 @@
+use base_db::{
+    semantics::tex,
+    util::queries::{self, Object, ObjectKind},
+};
+
+use crate::{Hover, HoverData, HoverParams};
+
+pub(super) fn find_hover<'a>(params: &HoverParams<'a>) -> Option<Hover<'a>> {
+    let feature = &params.feature;
+    let data = feature.document.data.as_tex()?;
+    let cursor = queries::object_at_cursor(
+        &data.semantics.glossary_entries,
+        params.offset,
+        queries::SearchMode::Name,
+    )?;
+
+    let (_, definition) = tex::GlossaryEntry::find_all(&feature.project)
+        .filter(|(_, entry)| entry.kind() == ObjectKind::Definition)
+        .find(|(_, entry)| entry.name_text() == cursor.object.name_text())?;
+
+    if definition.title.is_none() && definition.description.is_none() {
+        return None;
+    }
+
+    Some(Hover {
+        range: cursor.range,
+        data: HoverData::GlossaryEntry(definition),
+    })
+}
@@ -0,0 +1,17 @@
+use base_db::{deps, semantics::tex::LinkKind};
+
+use crate::{Hover, HoverData, HoverParams};
+
+pub(super) fn find_hover<'a>(params: &HoverParams<'a>) -> Option<Hover<'a>> {
+    let feature = &params.feature;
+    let data = feature.document.data.as_tex()?;
+    let link = data.semantics.links.iter().find(|link| {
+        link.kind == LinkKind::Graphics && link.path.range.contains_inclusive(params.offset)
+    })?;
+
+    let path = deps::resolve_graphics(feature.workspace, feature.document, link)?;
+    Some(Hover {
+        range: link.path.range,
+        data: HoverData::Graphics(path),
+    })
+}
@@ -1,12 +1,19 @@
 mod citation;
+mod command;
 mod entry_type;
+mod environment;
 mod field_type;
+mod glossary;
+mod graphics;
 mod label;
 mod package;
 mod string_ref;
 
+use std::path::PathBuf;
+
 use base_db::{
     data::{BibtexEntryType, BibtexFieldType},
+    semantics::tex,
     util::RenderedLabel,
     FeatureParams,
 };
@@ -16,6 +23,8 @@ use rowan::{TextRange, TextSize};
 pub struct HoverParams<'a> {
     pub feature: FeatureParams<'a>,
     pub offset: TextSize,
+    /// The markup language that is used to render citations.
+    pub citation_format: citeproc::Format,
 }
 
 #[derive(Debug, Clone)]
@@ -31,16 +40,33 @@ pub enum HoverData<'db> {
     EntryType(BibtexEntryType<'db>),
     FieldType(BibtexFieldType<'db>),
     Label(RenderedLabel<'db>),
+    GlossaryEntry(&'db tex::GlossaryEntry),
     StringRef(String),
+    /// The source code of a user-defined command or environment.
+    Definition(String),
+    Command {
+        file_names: &'db [&'db str],
+        command: &'db completion_data::Command<'db>,
+    },
+    Environment {
+        file_names: &'db [&'db str],
+        name: &'db str,
+    },
+    /// The path of an image that is included with `\includegraphics`.
+    Graphics(PathBuf),
 }
 
 pub fn find<'a>(params: &HoverParams<'a>) -> Option<Hover<'a>> {
     citation::find_hover(params)
         .or_else(|| package::find_hover(params))
+        .or_else(|| graphics::find_hover(params))
         .or_else(|| entry_type::find_hover(params))
         .or_else(|| field_type::find_hover(params))
         .or_else(|| label::find_hover(params))
+        .or_else(|| glossary::find_hover(params))
         .or_else(|| string_ref::find_hover(params))
+        .or_else(|| command::find_hover(params))
+        .or_else(|| environment::find_hover(params))
 }
 
 #[cfg(test)]
@@ -5,7 +5,11 @@ use crate::HoverParams;
 fn check(input: &str, expect: Expect) {
     let fixture = test_utils::fixture::Fixture::parse(input);
     let (feature, offset) = fixture.make_params().unwrap();
-    let params = HoverParams { feature, offset };
+    let params = HoverParams {
+        feature,
+        offset,
+        citation_format: citeproc::Format::Markdown,
+    };
     let data = crate::find(&params).map(|hover| {
         assert_eq!(fixture.documents[0].ranges[0], hover.range);
         hover.data
@@ -48,6 +52,52 @@ fn test_latex_citation() {
     );
 }
 
+#[test]
+fn test_latex_citation_alias() {
+    check(
+        r#"
+%! main.tex
+\addbibresource{main.bib}
+\cite{bar}
+       |
+      ^^^
+%! main.bib
+@article{foo, ids = {bar}, author = {Foo Bar}, title = {Baz Qux}, year = 1337}"#,
+        expect![[r#"
+            Some(
+                Citation(
+                    "F. Bar: \"Baz Qux\". (1337).",
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_latex_citation_crossref() {
+    check(
+        r#"
+%! main.tex
+\addbibresource{main.bib}
+\addbibresource{proceedings.bib}
+\cite{foo}
+       |
+      ^^^
+%! main.bib
+@inproceedings{foo, author = {Foo Bar}, title = {Baz Qux}, crossref = {proc}}
+
+%! proceedings.bib
+@proceedings{proc, title = {Proceedings}, publisher = {Qux}, year = 1337}"#,
+        expect![[r#"
+            Some(
+                Citation(
+                    "F. Bar: \"Baz Qux\". *Proceedings*. Qux, 1337.",
+                ),
+            )
+        "#]],
+    );
+}
+
 #[test]
 fn test_bibtex_entry_key() {
     check(
@@ -100,6 +150,56 @@ fn test_bibtex_entry_type_known() {
                         documentation: Some(
                             "An article in a journal, magazine, newspaper, or other periodical which forms a \n self-contained unit with its own title. The title of the periodical is given in the \n journaltitle field. If the issue has its own title in addition to the main title of \n the periodical, it goes in the issuetitle field. Note that editor and related \n fields refer to the journal while translator and related fields refer to the article.\n\nRequired fields: `author`, `title`, `journaltitle`, `year/date`",
                         ),
+                        required_fields: [
+                            [
+                                "author",
+                            ],
+                            [
+                                "title",
+                            ],
+                            [
+                                "journaltitle",
+                                "journal",
+                            ],
+                            [
+                                "year",
+                                "date",
+                            ],
+                        ],
+                        optional_fields: [
+                            "translator",
+                            "annotator",
+                            "commentator",
+                            "subtitle",
+                            "titleaddon",
+                            "editor",
+                            "editora",
+                            "editorb",
+                            "editorc",
+                            "journalsubtitle",
+                            "issuetitle",
+                            "issuesubtitle",
+                            "language",
+                            "origlanguage",
+                            "series",
+                            "volume",
+                            "number",
+                            "eid",
+                            "issue",
+                            "month",
+                            "pages",
+                            "version",
+                            "note",
+                            "issn",
+                            "addendum",
+                            "pubstate",
+                            "doi",
+                            "eprint",
+                            "eprintclass",
+                            "eprinttype",
+                            "url",
+                            "urldate",
+                        ],
                     },
                 ),
             )
@@ -352,3 +452,263 @@ fn test_latex_label_ntheorem() {
         "#]],
     );
 }
+
+#[test]
+fn test_latex_label_jobname() {
+    check(
+        r#"
+%! main.tex
+\section{Foo}
+\label{sec:foo}
+\ref{sec:foo}
+       |
+     ^^^^^^^
+
+%! .latexmkrc
+$jobname = "thesis";
+$aux_dir = "build";
+
+%! build/thesis.aux
+\newlabel{sec:foo}{{1}{1}}"#,
+        expect![[r#"
+            Some(
+                Label(
+                    RenderedLabel {
+                        range: 0..43,
+                        number: Some(
+                            "1",
+                        ),
+                        object: Section {
+                            prefix: "Section",
+                            text: "Foo",
+                        },
+                    },
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_user_command() {
+    check(
+        r#"
+%! main.tex
+\newcommand{\foo}[1]{\textbf{#1}}
+\foo{bar}
+ |
+^^^^"#,
+        expect![[r#"
+            Some(
+                Definition(
+                    "\\newcommand{\\foo}[1]{\\textbf{#1}}",
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_user_math_operator() {
+    check(
+        r#"
+%! main.tex
+\DeclareMathOperator{\foo}{foo}
+$\foo$
+  |
+ ^^^^"#,
+        expect![[r#"
+            Some(
+                Definition(
+                    "\\DeclareMathOperator{\\foo}{foo}",
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_kernel_command() {
+    check(
+        r#"
+%! main.tex
+\section{Foo}
+  |
+^^^^^^^^"#,
+        expect![[r#"
+            Some(
+                Command {
+                    file_names: [],
+                    command: Command {
+                        name: "section",
+                        image: None,
+                        glyph: None,
+                        parameters: [],
+                    },
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_package_command() {
+    check(
+        r#"
+%! main.tex
+\usepackage{amsmath}
+\eqref{foo}
+  |
+^^^^^^"#,
+        expect![[r#"
+            Some(
+                Command {
+                    file_names: [
+                        "amsmath.sty",
+                    ],
+                    command: Command {
+                        name: "eqref",
+                        image: None,
+                        glyph: None,
+                        parameters: [],
+                    },
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_user_environment() {
+    check(
+        r#"
+%! main.tex
+\newenvironment{foo}{\begin{center}}{\end{center}}
+\begin{foo}
+\end{foo}
+      |
+     ^^^"#,
+        expect![[r#"
+            Some(
+                Definition(
+                    "\\newenvironment{foo}{\\begin{center}}{\\end{center}}",
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_kernel_environment() {
+    check(
+        r#"
+%! main.tex
+\begin{center}
+        |
+       ^^^^^^
+\end{center}"#,
+        expect![[r#"
+            Some(
+                Environment {
+                    file_names: [],
+                    name: "center",
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_glossary_entry() {
+    check(
+        r#"
+%! main.tex
+\newglossaryentry{foo}{name=Foo, description={A foo}}
+\gls{foo}
+     |
+     ^^^"#,
+        expect![[r#"
+            Some(
+                GlossaryEntry(
+                    GlossaryEntry {
+                        kind: Definition,
+                        name: Span(
+                            "foo",
+                            18..21,
+                        ),
+                        title: Some(
+                            "Foo",
+                        ),
+                        description: Some(
+                            "A foo",
+                        ),
+                        full_range: 0..53,
+                    },
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_acronym() {
+    check(
+        r#"
+%! main.tex
+\newacronym{foo}{FOO}{Foo Bar}
+\acrlong{foo}
+         |
+         ^^^"#,
+        expect![[r#"
+            Some(
+                GlossaryEntry(
+                    GlossaryEntry {
+                        kind: AcronymDefinition,
+                        name: Span(
+                            "foo",
+                            12..15,
+                        ),
+                        title: Some(
+                            "FOO",
+                        ),
+                        description: Some(
+                            "Foo Bar",
+                        ),
+                        full_range: 0..30,
+                    },
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_graphics() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("figures")).unwrap();
+    std::fs::write(dir.path().join("figures/foo.png"), "").unwrap();
+
+    let mut workspace = base_db::Workspace::default();
+    let uri = url::Url::from_file_path(dir.path().join("main.tex")).unwrap();
+    workspace.open(
+        uri.clone(),
+        "\\graphicspath{{figures/}}\n\\includegraphics{foo}".into(),
+        distro::Language::Tex,
+        base_db::Owner::Client,
+        line_index::LineCol { line: 0, col: 0 },
+    );
+
+    let document = workspace.lookup(&uri).unwrap();
+    let params = HoverParams {
+        feature: base_db::FeatureParams::new(&workspace, document),
+        offset: 44.into(),
+        citation_format: citeproc::Format::Markdown,
+    };
+
+    let hover = crate::find(&params).unwrap();
+    assert_eq!(hover.range, rowan::TextRange::new(43.into(), 46.into()));
+    assert!(matches!(
+        hover.data,
+        crate::HoverData::Graphics(path) if path == dir.path().join("figures/foo.png")
+    ));
+}
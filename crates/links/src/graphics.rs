@@ -0,0 +1,34 @@
+use base_db::{deps::GraphicsDirs, semantics::tex::LinkKind, FeatureParams};
+use url::Url;
+
+use crate::DocumentLink;
+
+pub(super) fn find_links(params: &FeatureParams, results: &mut Vec<DocumentLink>) -> Option<()> {
+    let document = params.document;
+    let data = document.data.as_tex()?;
+
+    let mut links = data
+        .semantics
+        .links
+        .iter()
+        .filter(|link| link.kind == LinkKind::Graphics)
+        .peekable();
+
+    links.peek()?;
+    let dirs = GraphicsDirs::new(params.workspace, document);
+
+    for link in links {
+        let Some(path) = dirs.resolve(params.workspace, link) else {
+            continue;
+        };
+
+        if let Ok(target) = Url::from_file_path(path) {
+            results.push(DocumentLink {
+                range: link.path.range,
+                target,
+            });
+        }
+    }
+
+    Some(())
+}
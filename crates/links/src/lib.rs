@@ -1,10 +1,20 @@
-use base_db::{DocumentLocation, FeatureParams};
+use base_db::FeatureParams;
+use rowan::TextRange;
+use url::Url;
 
+mod graphics;
 mod include;
 
-pub fn find_links<'a>(params: &FeatureParams<'a>) -> Vec<DocumentLocation<'a>> {
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DocumentLink {
+    pub range: TextRange,
+    pub target: Url,
+}
+
+pub fn find_links(params: &FeatureParams) -> Vec<DocumentLink> {
     let mut results = Vec::new();
     include::find_links(params, &mut results);
+    graphics::find_links(params, &mut results);
     results
 }
 
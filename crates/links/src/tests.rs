@@ -16,7 +16,7 @@ fn check(input: &str, expect: Expect) {
 
     let actual_targets = links
         .iter()
-        .map(|link| link.document.uri.as_str())
+        .map(|link| link.target.as_str())
         .collect::<Vec<_>>();
 
     expect.assert_debug_eq(&actual_targets);
@@ -55,3 +55,38 @@ fn test_document_import() {
         "#]],
     );
 }
+
+#[test]
+fn test_graphics_missing() {
+    check(
+        r#"
+%! main.tex
+\graphicspath{{figures/}}
+\includegraphics{foo}"#,
+        expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn test_magic_root() {
+    check(
+        r#"
+%! main.tex
+\documentclass{article}
+
+%! chapters/foo.tex
+% !TEX root = ../main.tex
+\input{chapters/bar}
+       ^^^^^^^^^^^^
+|
+
+%! chapters/bar.tex"#,
+        expect![[r#"
+            [
+                "file:///texlab/chapters/bar.tex",
+            ]
+        "#]],
+    );
+}
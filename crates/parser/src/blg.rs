@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+
+use syntax::{
+    blg::{BlgData, BlgError},
+    BuildErrorLevel,
+};
+
+/// Parses a `.blg` file written by BibTeX or Biber.
+///
+/// Line numbers are zero-based.
+pub fn parse_blg(input: &str) -> BlgData {
+    let mut errors = Vec::new();
+    let mut lines = input.lines().peekable();
+    while let Some(line) = lines.next() {
+        if let Some(error) = parse_biber_message(line) {
+            errors.push(error);
+        } else if let Some((message, location)) = line.split_once("---line ") {
+            let (line, file) = parse_location(location).unzip();
+            errors.push(BlgError {
+                level: BuildErrorLevel::Error,
+                message: message.trim().into(),
+                file,
+                line,
+                entry: None,
+            });
+        } else if let Some(message) = line.strip_prefix("Warning--") {
+            // Some warnings are followed by the location of the entry.
+            let (line, file) = lines
+                .next_if(|next| next.starts_with("--line "))
+                .and_then(|next| parse_location(&next["--line ".len()..]))
+                .unzip();
+
+            let entry = message
+                .rsplit_once(" in ")
+                .map(|(_, name)| name.trim())
+                .filter(|name| !name.is_empty() && !name.contains(char::is_whitespace))
+                .map(String::from);
+
+            errors.push(BlgError {
+                level: BuildErrorLevel::Warning,
+                message: message.trim().into(),
+                file,
+                line,
+                entry,
+            });
+        }
+    }
+
+    BlgData { errors }
+}
+
+/// Parses the `<n> of file <name>` part of a BibTeX message.
+fn parse_location(text: &str) -> Option<(u32, PathBuf)> {
+    let (line, file) = text.split_once(" of file ")?;
+    let line = line.trim().parse::<u32>().ok()?.saturating_sub(1);
+    Some((line, PathBuf::from(file.trim())))
+}
+
+/// Parses lines like `[42] Utils.pm:410> WARN - Duplicate entry key: 'foo' in file 'main.bib', skipping ...`.
+fn parse_biber_message(line: &str) -> Option<BlgError> {
+    let (_, message) = line.strip_prefix('[')?.split_once("> ")?;
+    let (level, message) = if let Some(message) = message.strip_prefix("WARN - ") {
+        (BuildErrorLevel::Warning, message)
+    } else if let Some(message) = message.strip_prefix("ERROR - ") {
+        (BuildErrorLevel::Error, message)
+    } else {
+        return None;
+    };
+
+    let mut error = BlgError {
+        level,
+        message: message.trim().into(),
+        file: None,
+        line: None,
+        entry: None,
+    };
+
+    if let Some(rest) = message.strip_prefix("Duplicate entry key: '") {
+        let (name, rest) = rest.split_once('\'')?;
+        error.entry = Some(name.into());
+        error.file = rest
+            .strip_prefix(" in file '")
+            .and_then(|rest| rest.split_once('\''))
+            .map(|(file, _)| PathBuf::from(file));
+    } else if let Some((_, rest)) = message.split_once("ntry '") {
+        // Entry 'foo' (main.bib): ...
+        let (name, rest) = rest.split_once('\'')?;
+        error.entry = Some(name.into());
+        error.file = rest
+            .strip_prefix(" (")
+            .and_then(|rest| rest.split_once(')'))
+            .map(|(file, _)| PathBuf::from(file));
+    } else if let Some(rest) = message.strip_prefix("BibTeX subsystem: ") {
+        // BibTeX subsystem: /tmp/biber_tmp_xyz/main.bib_1234.utf8, line 5, syntax error: ...
+        let (file, rest) = rest.split_once(", line ")?;
+        let line: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+        error.line = line.parse::<u32>().ok().map(|line| line.saturating_sub(1));
+        error.file = original_file_name(Path::new(file));
+    }
+
+    Some(error)
+}
+
+/// Biber parses a temporary copy of the database file called `<name>_<pid>.utf8`.
+fn original_file_name(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    let name = name.strip_suffix(".utf8").unwrap_or(name);
+    let name = match name.rsplit_once('_') {
+        Some((prefix, suffix)) if suffix.chars().all(|c| c.is_ascii_digit()) => prefix,
+        _ => name,
+    };
+
+    Some(PathBuf::from(name))
+}
+
+#[cfg(test)]
+mod tests;
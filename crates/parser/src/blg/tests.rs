@@ -0,0 +1,154 @@
+use expect_test::{expect, Expect};
+
+use crate::parse_blg;
+
+fn check(input: &str, expect: Expect) {
+    expect.assert_debug_eq(&parse_blg(input));
+}
+
+#[test]
+fn test_bibtex() {
+    check(
+        r#"This is BibTeX, Version 0.99d (TeX Live 2023)
+Capacity: max_strings=200000, hash_size=200000, hash_prime=170003
+The top-level auxiliary file: main.aux
+The style file: plain.bst
+Database file #1: main.bib
+I was expecting a `,' or a `}'---line 5 of file main.bib
+ :   title = {Foo}
+ :   ^^^^^
+I'm skipping whatever remains of this entry
+Repeated entry---line 9 of file main.bib
+ : @article{foo
+ :             ,
+I'm skipping whatever remains of this entry
+Warning--empty journal in bar
+Warning--I didn't find a database entry for "baz"
+Warning--entry type for "qux" isn't style-file defined
+--line 12 of file main.bib
+(There were 2 error messages)
+"#,
+        expect![[r#"
+            BlgData {
+                errors: [
+                    BlgError {
+                        level: Error,
+                        message: "I was expecting a `,' or a `}'",
+                        file: Some(
+                            "main.bib",
+                        ),
+                        line: Some(
+                            4,
+                        ),
+                        entry: None,
+                    },
+                    BlgError {
+                        level: Error,
+                        message: "Repeated entry",
+                        file: Some(
+                            "main.bib",
+                        ),
+                        line: Some(
+                            8,
+                        ),
+                        entry: None,
+                    },
+                    BlgError {
+                        level: Warning,
+                        message: "empty journal in bar",
+                        file: None,
+                        line: None,
+                        entry: Some(
+                            "bar",
+                        ),
+                    },
+                    BlgError {
+                        level: Warning,
+                        message: "I didn't find a database entry for \"baz\"",
+                        file: None,
+                        line: None,
+                        entry: None,
+                    },
+                    BlgError {
+                        level: Warning,
+                        message: "entry type for \"qux\" isn't style-file defined",
+                        file: Some(
+                            "main.bib",
+                        ),
+                        line: Some(
+                            11,
+                        ),
+                        entry: None,
+                    },
+                ],
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_biber() {
+    check(
+        r#"[0] Config.pm:307> INFO - This is Biber 2.19
+[0] Config.pm:310> INFO - Logfile is 'main.blg'
+[47] Biber.pm:419> INFO - Reading 'main.bcf'
+[129] bibtex.pm:1519> INFO - Found BibTeX data source 'main.bib'
+[130] Utils.pm:410> WARN - Duplicate entry key: 'foo' in file 'main.bib', skipping ...
+[131] Biber.pm:4621> WARN - Datamodel: article entry 'bar' (main.bib): Missing mandatory field 'journaltitle'
+[132] Biber.pm:4621> WARN - Entry 'baz' (main.bib): Invalid format '2020-13' of date field 'date' - ignoring
+[133] Utils.pm:410> ERROR - BibTeX subsystem: /tmp/biber_tmp_ab12/main.bib_5678.utf8, line 14, syntax error: found "title", expected end of entry ("}" or ")") (skipping to next "@")
+[140] Biber.pm:133> INFO - WARNINGS: 3
+[140] Biber.pm:137> INFO - ERRORS: 1
+"#,
+        expect![[r#"
+            BlgData {
+                errors: [
+                    BlgError {
+                        level: Warning,
+                        message: "Duplicate entry key: 'foo' in file 'main.bib', skipping ...",
+                        file: Some(
+                            "main.bib",
+                        ),
+                        line: None,
+                        entry: Some(
+                            "foo",
+                        ),
+                    },
+                    BlgError {
+                        level: Warning,
+                        message: "Datamodel: article entry 'bar' (main.bib): Missing mandatory field 'journaltitle'",
+                        file: Some(
+                            "main.bib",
+                        ),
+                        line: None,
+                        entry: Some(
+                            "bar",
+                        ),
+                    },
+                    BlgError {
+                        level: Warning,
+                        message: "Entry 'baz' (main.bib): Invalid format '2020-13' of date field 'date' - ignoring",
+                        file: Some(
+                            "main.bib",
+                        ),
+                        line: None,
+                        entry: Some(
+                            "baz",
+                        ),
+                    },
+                    BlgError {
+                        level: Error,
+                        message: "BibTeX subsystem: /tmp/biber_tmp_ab12/main.bib_5678.utf8, line 14, syntax error: found \"title\", expected end of entry (\"}\" or \")\") (skipping to next \"@\")",
+                        file: Some(
+                            "main.bib",
+                        ),
+                        line: Some(
+                            13,
+                        ),
+                        entry: None,
+                    },
+                ],
+            }
+        "#]],
+    );
+}
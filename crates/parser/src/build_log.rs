@@ -1,175 +1,403 @@
-use std::{cmp::Ordering, path::PathBuf};
+use std::path::{Path, PathBuf};
 
-use once_cell::sync::Lazy;
-use regex::{Match, Regex};
-use syntax::{BuildError, BuildErrorLevel, BuildLog};
+use syntax::{BuildError, BuildErrorCategory, BuildErrorLevel, BuildLog};
 
+/// TeX breaks the lines of the log file after this many characters.
 const MAX_LINE_LENGTH: usize = 79;
 
-static PACKAGE_MESSAGE_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new("^\\([a-zA-Z_\\-]+\\)\\s*(?P<msg>.*)$").unwrap());
-
-static FILE_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new("\\((?P<file>[^\r\n()]+\\.(tex|sty|cls))").unwrap());
-
-static TEX_ERROR_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new("(?m)^! ((?P<msg1>(.|\r|\n)*?)\r?\nl\\.(?P<line>\\d+)( (\\.\\.\\.)?(?P<hint>[^\r\n]+))?|(?P<msg2>[^\r\n]*))").unwrap()
-});
+pub fn parse_build_log(log: &str) -> BuildLog {
+    let mut parser = BuildLogParser::default();
+    for line in log.lines() {
+        parser.push_line(line);
+    }
 
-static WARNING_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new("(?P<msg>(LaTeX|Package [a-zA-Z_\\-]+) Warning: [^\r\n]*?(on input line (?P<line>\\d+))?\\.)[\r\n]").unwrap()
-});
+    parser.finish()
+}
 
-static BAD_BOX_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new("(?P<msg>(Ov|Und)erfull \\\\[hv]box[^\r\n]*lines? (?P<line>\\d+)[^\r\n]*)").unwrap()
-});
+/// Parses the output of the TeX engine line by line while it is running.
+///
+/// The parser can also process the output of `latexmk` that consists of several passes.
+/// Only the errors of the latest pass are kept because they supersede the previous ones.
+#[derive(Debug, Default)]
+pub struct BuildLogParser {
+    errors: Vec<BuildError>,
+    /// The groups opened by `(`. Groups that start with a file name
+    /// denote the files that TeX is currently reading.
+    stack: Vec<Option<PathBuf>>,
+    state: State,
+    buffer: String,
+    progress: BuildProgress,
+}
 
-pub fn parse_build_log(log: &str) -> BuildLog {
-    let log = prepare_log(log);
-    let mut ranges: Vec<FileRange> = FILE_REGEX
-        .find_iter(&log)
-        .map(|result| FileRange::create(&log, result))
-        .collect();
-    ranges.sort();
+/// The progress of a build that is derived from the output of the TeX engine.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct BuildProgress {
+    /// The number of TeX passes that have been started so far.
+    pub pass: u32,
+    /// The last page that has been shipped out during the current pass.
+    pub page: Option<u32>,
+    /// The number of pages of the previous pass which is an estimate for the current one.
+    pub total_pages: Option<u32>,
+}
 
-    let tex_errors = extract_matches(&log, &ranges, &TEX_ERROR_REGEX, BuildErrorLevel::Error);
-    let warnings = extract_matches(&log, &ranges, &WARNING_REGEX, BuildErrorLevel::Warning);
-    let bad_boxes = extract_matches(&log, &ranges, &BAD_BOX_REGEX, BuildErrorLevel::Warning);
+#[derive(Debug, Default)]
+enum State {
+    #[default]
+    Normal,
+    /// A message that may continue on the following lines.
+    Message(Message, Continuation),
+    /// The lines between an error message and the `l.<n>` line.
+    Context(Message),
+    /// The line after `l.<n>` containing the part of the source line that has not been read yet.
+    PostContext(Message),
+    /// The help text of an error that ends with an empty line.
+    Help(Message),
+    /// The contents of an overfull or underfull box.
+    BadBox,
+}
 
-    let errors = [tex_errors, warnings, bad_boxes].concat();
-    BuildLog { errors }
+#[derive(Debug)]
+enum Continuation {
+    /// Lines starting with `(<package>)`.
+    Package(String),
+    /// Indented lines.
+    Indented,
 }
 
-fn extract_matches(
-    log: &str,
-    ranges: &[FileRange],
-    regex: &Regex,
+#[derive(Debug)]
+struct Message {
+    path: Option<PathBuf>,
     level: BuildErrorLevel,
-) -> Vec<BuildError> {
-    let mut errors = Vec::new();
-    for result in regex.find_iter(log) {
-        let captures = regex.captures(&log[result.start()..result.end()]).unwrap();
-        let message = captures
-            .name("msg")
-            .or_else(|| captures.name("msg1"))
-            .or_else(|| captures.name("msg2"))
-            .unwrap()
-            .as_str()
-            .lines()
-            .next()
-            .unwrap_or_default()
-            .to_owned();
-
-        if let Some(range) = ranges.iter().find(|range| range.contains(result.start())) {
-            let line = captures
-                .name("line")
-                .map(|result| result.as_str().parse::<u32>().unwrap() - 1);
-
-            let hint: Option<String> = if line.is_some() {
-                captures
-                    .name("hint")
-                    .map(|r| String::from(r.as_str().trim()))
-            } else {
-                None
-            };
+    text: String,
+    help: Option<String>,
+    hint: Option<String>,
+    line: Option<u32>,
+    column: Option<u32>,
+}
+
+impl BuildLogParser {
+    /// Adds a line of output. Lines that have been wrapped by TeX are joined automatically.
+    pub fn push_line(&mut self, line: &str) {
+        self.buffer.push_str(line);
+
+        // The line after `l.<n>` is padded and may have the maximum length by accident.
+        let is_wrapped =
+            line.chars().count() == MAX_LINE_LENGTH && !matches!(self.state, State::PostContext(_));
 
-            errors.push(BuildError {
-                relative_path: range.path.clone(),
-                level,
-                message,
-                line,
-                hint,
-            });
+        if !is_wrapped {
+            let line = std::mem::take(&mut self.buffer);
+            self.process(&line);
         }
     }
-    errors
-}
 
-fn prepare_log(log: &str) -> String {
-    let mut old_lines = log.lines();
-    let mut new_lines: Vec<String> = Vec::new();
-    while let Some(line) = old_lines.next() {
-        if PACKAGE_MESSAGE_REGEX.is_match(line) {
-            let captures = PACKAGE_MESSAGE_REGEX.captures(line).unwrap();
-            if let Some(last_line) = new_lines.last_mut() {
-                last_line.push(' ');
-                last_line.push_str(captures.name("msg").unwrap().as_str());
+    /// Returns the errors of the current pass that have been completely parsed so far.
+    pub fn errors(&self) -> &[BuildError] {
+        &self.errors
+    }
+
+    pub fn progress(&self) -> BuildProgress {
+        self.progress
+    }
+
+    /// Processes the remaining output and returns the errors of the latest pass.
+    pub fn finish(mut self) -> BuildLog {
+        if !self.buffer.is_empty() {
+            let line = std::mem::take(&mut self.buffer);
+            self.process(&line);
+        }
+
+        match std::mem::take(&mut self.state) {
+            State::Message(message, _) => self.finish_message(message),
+            State::Context(message) | State::PostContext(message) | State::Help(message) => {
+                self.emit(message);
             }
-        } else if line.ends_with("...") {
-            let mut new_line = line[line.len() - 3..].to_owned();
-            if let Some(old_line) = old_lines.next() {
-                new_line.push_str(old_line);
+            State::Normal | State::BadBox => {}
+        };
+
+        BuildLog {
+            errors: self.errors,
+        }
+    }
+
+    fn process(&mut self, line: &str) {
+        match std::mem::take(&mut self.state) {
+            State::Normal => self.process_normal(line),
+            State::Message(mut message, continuation) => {
+                let rest = match &continuation {
+                    Continuation::Package(name) => line
+                        .strip_prefix('(')
+                        .and_then(|line| line.strip_prefix(name.as_str()))
+                        .and_then(|line| line.strip_prefix(')')),
+                    Continuation::Indented => Some(line).filter(|line| {
+                        line.starts_with(char::is_whitespace) && !line.trim().is_empty()
+                    }),
+                };
+
+                if let Some(rest) = rest {
+                    message.text.push(' ');
+                    message.text.push_str(rest.trim());
+                    self.state = State::Message(message, continuation);
+                } else {
+                    self.finish_message(message);
+                    self.process(line);
+                }
             }
-            new_lines.push(new_line);
-        } else if line.chars().count() == MAX_LINE_LENGTH {
-            let mut new_line = String::new();
-            new_line.push_str(line);
-            for old_line in old_lines.by_ref() {
-                new_line.push_str(old_line);
-                if old_line.chars().count() != MAX_LINE_LENGTH {
-                    break;
+            State::Context(mut message) => {
+                if let Some((line_number, context)) = parse_context_line(line) {
+                    message.line = Some(line_number.saturating_sub(1));
+                    message.column =
+                        (!context.starts_with("...")).then(|| context.chars().count() as u32);
+
+                    message.hint = Some(context.trim_start_matches("...").trim())
+                        .filter(|hint| !hint.is_empty())
+                        .map(String::from);
+
+                    self.state = State::PostContext(message);
+                } else if line.starts_with("! ") {
+                    self.emit(message);
+                    self.process(line);
+                } else {
+                    self.state = State::Context(message);
                 }
             }
-            new_lines.push(new_line);
+            State::PostContext(message) => {
+                self.state = State::Help(message);
+            }
+            State::Help(mut message) => {
+                if line.trim().is_empty() {
+                    self.emit(message);
+                } else if line.starts_with("! ") {
+                    self.emit(message);
+                    self.process(line);
+                } else {
+                    let help = message.help.get_or_insert_with(String::new);
+                    if !help.is_empty() {
+                        help.push(' ');
+                    }
+
+                    help.push_str(line.trim());
+                    self.state = State::Help(message);
+                }
+            }
+            State::BadBox => {
+                if !line.trim().is_empty() {
+                    self.state = State::BadBox;
+                }
+            }
+        };
+    }
+
+    fn process_normal(&mut self, line: &str) {
+        if let Some(text) = line.strip_prefix("! ") {
+            let message = self.start_message(BuildErrorLevel::Error, text);
+            let continuation = if text.starts_with("LaTeX Error:") {
+                Some(Continuation::Indented)
+            } else {
+                find_package_name(text, "Error:").map(Continuation::Package)
+            };
+
+            self.state = match continuation {
+                Some(continuation) => State::Message(message, continuation),
+                None => State::Context(message),
+            };
+        } else if line.starts_with("Overfull \\") || line.starts_with("Underfull \\") {
+            let mut message = self.start_message(BuildErrorLevel::Warning, line);
+            message.line = find_number(line, " at lines ")
+                .or_else(|| find_number(line, " at line "))
+                .map(|line| line.saturating_sub(1));
+
+            if message.line.is_some() {
+                self.emit(message);
+            }
+
+            self.state = State::BadBox;
+        } else if line.starts_with("LaTeX Warning:") {
+            let message = self.start_message(BuildErrorLevel::Warning, line);
+            self.state = State::Message(message, Continuation::Indented);
+        } else if let Some(name) = find_package_name(line, "Warning:") {
+            let message = self.start_message(BuildErrorLevel::Warning, line);
+            self.state = State::Message(message, Continuation::Package(name));
+        } else if is_engine_banner(line) {
+            self.start_pass();
         } else {
-            new_lines.push(line.to_owned());
+            self.process_files(line);
+            if let Some(page) = find_last_page(line) {
+                self.progress.page = Some(page);
+            }
         }
     }
-    new_lines.join("\n")
-}
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-struct FileRange {
-    pub path: PathBuf,
-    pub start: usize,
-    pub end: usize,
-}
+    fn start_pass(&mut self) {
+        let progress = &mut self.progress;
+        progress.pass += 1;
+        progress.total_pages = progress.page.take().or(progress.total_pages);
+        self.errors.clear();
+        self.stack.clear();
+    }
 
-impl FileRange {
-    fn create(log: &str, result: Match) -> Self {
-        let mut balance = 1;
-        let mut end = result.start() + 1;
-        let chars = log[result.start() + 1..].chars();
-        for c in chars {
-            if balance <= 0 {
-                break;
+    /// Keeps track of the files that are opened with `(<path>` and closed with `)`.
+    fn process_files(&mut self, line: &str) {
+        let mut rest = line;
+        while let Some(index) = rest.find(['(', ')']) {
+            if rest[index..].starts_with(')') {
+                self.stack.pop();
+                rest = &rest[index + 1..];
+                continue;
             }
 
-            if c == '(' {
-                balance += 1;
-            } else if c == ')' {
-                balance -= 1;
-            }
-            end += c.len_utf8();
+            rest = &rest[index + 1..];
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == '(' || c == ')')
+                .unwrap_or(rest.len());
+
+            let name = &rest[..end];
+            self.stack
+                .push(is_file_name(name).then(|| PathBuf::from(name)));
+            rest = &rest[end..];
         }
+    }
 
-        let captures = FILE_REGEX.captures(result.as_str()).unwrap();
-        let path = PathBuf::from(captures.name("file").unwrap().as_str());
-        Self {
+    fn start_message(&self, level: BuildErrorLevel, text: &str) -> Message {
+        let path = self.stack.iter().rev().find_map(|path| path.clone());
+        Message {
             path,
-            start: result.start(),
-            end,
+            level,
+            text: text.trim_end().into(),
+            help: None,
+            hint: None,
+            line: None,
+            column: None,
         }
     }
 
-    fn len(&self) -> usize {
-        self.end - self.start + 1
+    fn finish_message(&mut self, mut message: Message) {
+        match message.level {
+            BuildErrorLevel::Error => {
+                self.state = State::Context(message);
+            }
+            BuildErrorLevel::Warning => {
+                message.line =
+                    find_number(&message.text, "on input line ").map(|line| line.saturating_sub(1));
+
+                self.emit(message);
+            }
+        }
     }
 
-    fn contains(&self, index: usize) -> bool {
-        index >= self.start && index <= self.end
+    fn emit(&mut self, message: Message) {
+        let Some(relative_path) = message.path else {
+            return;
+        };
+
+        let category = categorize(&message.text);
+        self.errors.push(BuildError {
+            relative_path,
+            level: message.level,
+            category,
+            message: message.text,
+            help: message.help,
+            hint: message.hint,
+            line: message.line,
+            column: message.column,
+        });
     }
 }
 
-impl Ord for FileRange {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.len().cmp(&other.len())
-    }
+/// Splits a line like `l.42 \foo` into the line number and the part of the line that was read.
+fn parse_context_line(line: &str) -> Option<(u32, &str)> {
+    let rest = line.strip_prefix("l.")?;
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+
+    let line_number = rest[..end].parse().ok()?;
+    let context = rest[end..].strip_prefix(' ').unwrap_or(&rest[end..]);
+    Some((line_number, context))
+}
+
+/// Extracts the name from messages like `Package foo Warning: ...` or `Class bar Error: ...`.
+fn find_package_name(text: &str, kind: &str) -> Option<String> {
+    let rest = text
+        .strip_prefix("Package ")
+        .or_else(|| text.strip_prefix("Class "))?;
+
+    let (name, rest) = rest.split_once(' ')?;
+    rest.starts_with(kind).then(|| name.into())
+}
+
+/// Checks for the first line of a pass like `This is pdfTeX, Version ...` or `note: Running TeX ...` (Tectonic).
+fn is_engine_banner(line: &str) -> bool {
+    (line.starts_with("This is ") && line.contains("TeX, Version"))
+        || line.starts_with("note: Running TeX")
+        || line.starts_with("note: Rerunning TeX")
+}
+
+/// Finds the number of the last page that TeX reports with `[<n>` when shipping it out.
+fn find_last_page(line: &str) -> Option<u32> {
+    line.rmatch_indices('[').find_map(|(index, _)| {
+        let rest = &line[index + 1..];
+        let end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+
+        let is_marker = matches!(
+            rest[end..].chars().next(),
+            None | Some(']' | '{' | '<' | ' ')
+        );
+        if end > 0 && is_marker {
+            rest[..end].parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+fn find_number(text: &str, prefix: &str) -> Option<u32> {
+    let start = text.find(prefix)? + prefix.len();
+    let digits: String = text[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+
+    digits.parse().ok()
+}
+
+fn is_file_name(name: &str) -> bool {
+    let is_path = name.starts_with(['.', '/', '\\', '~'])
+        || (name.starts_with(|c: char| c.is_ascii_alphabetic()) && name[1..].starts_with(':'));
+
+    let has_extension = Path::new(name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            extension.starts_with(|c: char| c.is_ascii_alphabetic())
+                && extension.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+
+    is_path || has_extension
 }
 
-impl PartialOrd for FileRange {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+fn categorize(message: &str) -> BuildErrorCategory {
+    if message.starts_with("Undefined control sequence") {
+        BuildErrorCategory::UndefinedControlSequence
+    } else if message.starts_with("Overfull") {
+        BuildErrorCategory::OverfullBox
+    } else if message.starts_with("Underfull") {
+        BuildErrorCategory::UnderfullBox
+    } else if message.contains("' not found") || message.starts_with("I can't find file") {
+        BuildErrorCategory::MissingFile
+    } else if (message.contains("Citation") && message.contains("undefined"))
+        || message.contains("undefined citations")
+    {
+        BuildErrorCategory::UndefinedCitation
+    } else if (message.contains("Reference") && message.contains("undefined"))
+        || message.contains("undefined references")
+    {
+        BuildErrorCategory::UndefinedReference
+    } else if message.contains("multiply defined") || message.contains("multiply-defined") {
+        BuildErrorCategory::DuplicateLabel
+    } else {
+        BuildErrorCategory::Other
     }
 }
 
@@ -1,6 +1,6 @@
 use expect_test::{expect, Expect};
 
-use crate::parse_build_log;
+use crate::{parse_build_log, BuildLogParser, BuildProgress};
 
 fn check(input: &str, expect: Expect) {
     expect.assert_debug_eq(&parse_build_log(input));
@@ -98,20 +98,26 @@ PDF statistics:
                     BuildError {
                         relative_path: "./parent.tex",
                         level: Warning,
+                        category: OverfullBox,
                         message: "Overfull \\hbox (200.00162pt too wide) in paragraph at lines 8--9",
+                        help: None,
                         hint: None,
                         line: Some(
                             7,
                         ),
+                        column: None,
                     },
                     BuildError {
                         relative_path: "./parent.tex",
                         level: Warning,
+                        category: OverfullBox,
                         message: "Overfull \\vbox (3.19998pt too high) detected at line 23",
+                        help: None,
                         hint: None,
                         line: Some(
                             22,
                         ),
+                        column: None,
                     },
                 ],
             }
@@ -192,13 +198,20 @@ PDF statistics:
                     BuildError {
                         relative_path: "./child.tex",
                         level: Error,
+                        category: UndefinedControlSequence,
                         message: "Undefined control sequence.",
+                        help: Some(
+                            "The control sequence at the end of the top line of your error message was never \\def'ed. If you have misspelled it (e.g., `\\hobx'), type `I' and the correct spelling (e.g., `I\\hbox'). Otherwise just continue, and I'll forget about whatever was undefined.",
+                        ),
                         hint: Some(
                             "\\foo",
                         ),
                         line: Some(
                             0,
                         ),
+                        column: Some(
+                            4,
+                        ),
                     },
                 ],
             }
@@ -280,18 +293,24 @@ PDF statistics:
                     BuildError {
                         relative_path: "./parent.tex",
                         level: Warning,
+                        category: UndefinedCitation,
                         message: "LaTeX Warning: Citation `foo' on page 1 undefined on input line 6.",
+                        help: None,
                         hint: None,
                         line: Some(
                             5,
                         ),
+                        column: None,
                     },
                     BuildError {
                         relative_path: "./parent.tex",
                         level: Warning,
+                        category: UndefinedReference,
                         message: "LaTeX Warning: There were undefined references.",
+                        help: None,
                         hint: None,
                         line: None,
+                        column: None,
                     },
                 ],
             }
@@ -391,24 +410,36 @@ PDF statistics:
                     BuildError {
                         relative_path: "/TexLive/texmf-dist/tex/generic/babel/babel.sty",
                         level: Error,
+                        category: Other,
                         message: "Package babel Error: Unknown option `foo'. Either you misspelled it or the language definition file foo.ldf was not found.",
+                        help: Some(
+                            "Valid options are: shorthands=, KeepShorthandsActive, activeacute, activegrave, noconfigs, safe=, main=, math= headfoot=, strings=, config=, hyphenmap=, or a language name.",
+                        ),
                         hint: Some(
                             "\\ProcessOptions*",
                         ),
                         line: Some(
                             392,
                         ),
+                        column: Some(
+                            16,
+                        ),
                     },
                     BuildError {
                         relative_path: "/TexLive/texmf-dist/tex/generic/babel/babel.sty",
                         level: Error,
+                        category: Other,
                         message: "Package babel Error: You haven't specified a language option.",
+                        help: Some(
+                            "You need to specify a language, either as a global option or as an optional argument to the \\usepackage command; You shouldn't try to proceed from here, type x to quit.",
+                        ),
                         hint: Some(
                             "ry to proceed from here, type x to quit.}",
                         ),
                         line: Some(
                             425,
                         ),
+                        column: None,
                     },
                 ],
             }
@@ -810,23 +841,32 @@ PDF statistics:
                     BuildError {
                         relative_path: "./parent.tex",
                         level: Warning,
+                        category: Other,
                         message: "Package biblatex Warning: 'babel/polyglossia' detected but 'csquotes' missing. Loading 'csquotes' recommended.",
+                        help: None,
                         hint: None,
                         line: None,
+                        column: None,
                     },
                     BuildError {
                         relative_path: "./parent.tex",
                         level: Warning,
+                        category: UndefinedReference,
                         message: "LaTeX Warning: There were undefined references.",
+                        help: None,
                         hint: None,
                         line: None,
+                        column: None,
                     },
                     BuildError {
                         relative_path: "./parent.tex",
                         level: Warning,
+                        category: Other,
                         message: "Package biblatex Warning: Please (re)run Biber on the file: parent and rerun LaTeX afterwards.",
+                        help: None,
                         hint: None,
                         line: None,
+                        column: None,
                     },
                 ],
             }
@@ -965,62 +1005,104 @@ PDF statistics:
                     BuildError {
                         relative_path: "./parent.tex",
                         level: Error,
+                        category: UndefinedControlSequence,
                         message: "Undefined control sequence.",
+                        help: Some(
+                            "The control sequence at the end of the top line of your error message was never \\def'ed. If you have misspelled it (e.g., `\\hobx'), type `I' and the correct spelling (e.g., `I\\hbox'). Otherwise just continue, and I'll forget about whatever was undefined.",
+                        ),
                         hint: Some(
                             "\\foo",
                         ),
                         line: Some(
                             6,
                         ),
+                        column: Some(
+                            4,
+                        ),
                     },
                     BuildError {
                         relative_path: "./parent.tex",
                         level: Error,
+                        category: Other,
                         message: "Missing $ inserted.",
+                        help: Some(
+                            "I've inserted a begin-math/end-math symbol since I think you left one out. Proceed, with fingers crossed.",
+                        ),
                         hint: Some(
                             "\\bar",
                         ),
                         line: Some(
                             7,
                         ),
+                        column: Some(
+                            4,
+                        ),
                     },
                     BuildError {
                         relative_path: "./parent.tex",
                         level: Error,
+                        category: UndefinedControlSequence,
                         message: "Undefined control sequence.",
+                        help: Some(
+                            "The control sequence at the end of the top line of your error message was never \\def'ed. If you have misspelled it (e.g., `\\hobx'), type `I' and the correct spelling (e.g., `I\\hbox'). Otherwise just continue, and I'll forget about whatever was undefined.",
+                        ),
                         hint: Some(
                             "\\baz",
                         ),
                         line: Some(
                             8,
                         ),
+                        column: Some(
+                            4,
+                        ),
                     },
                     BuildError {
                         relative_path: "./parent.tex",
                         level: Error,
+                        category: Other,
                         message: "Missing { inserted.",
+                        help: Some(
+                            "A left brace was mandatory here, so I've put one in. You might want to delete and/or insert some corrections so that I will find a matching right brace soon. (If you're confused by all this, try typing `I}' now.)",
+                        ),
                         hint: None,
                         line: Some(
                             9,
                         ),
+                        column: Some(
+                            0,
+                        ),
                     },
                     BuildError {
                         relative_path: "./parent.tex",
                         level: Error,
+                        category: Other,
                         message: "Missing $ inserted.",
+                        help: Some(
+                            "I've inserted a begin-math/end-math symbol since I think you left one out. Proceed, with fingers crossed.",
+                        ),
                         hint: None,
                         line: Some(
                             9,
                         ),
+                        column: Some(
+                            0,
+                        ),
                     },
                     BuildError {
                         relative_path: "./parent.tex",
                         level: Error,
+                        category: Other,
                         message: "Missing } inserted.",
+                        help: Some(
+                            "I've inserted something that you may have forgotten. (See the <inserted text> above.) With luck, this will get me unwedged. But if you really didn't forget anything, try typing `2' now; then my insertion and my current dilemma will both disappear.",
+                        ),
                         hint: None,
                         line: Some(
                             9,
                         ),
+                        column: Some(
+                            0,
+                        ),
                     },
                 ],
             }
@@ -1213,16 +1295,250 @@ PDF statistics:
                     BuildError {
                         relative_path: "/some/folder/a.tex",
                         level: Error,
+                        category: UndefinedControlSequence,
                         message: "Undefined control sequence.",
+                        help: Some(
+                            "The control sequence at the end of the top line of your error message was never \\def'ed. If you have misspelled it (e.g., `\\hobx'), type `I' and the correct spelling (e.g., `I\\hbox'). Otherwise just continue, and I'll forget about whatever was undefined.",
+                        ),
                         hint: Some(
                             "\\lsdkfjlskdfj",
                         ),
                         line: Some(
                             3,
                         ),
+                        column: Some(
+                            17,
+                        ),
+                    },
+                ],
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_file_stack() {
+    check(
+        r#"(./main.tex (./chapters/intro.tex
+Overfull \hbox (12.0pt too wide) in paragraph at lines 3--4
+[]\OT1/cmr/m/n/10 (unbalanced text
+
+(/texlive/texmf-dist/tex/latex/base/size10.clo)
+LaTeX Warning: Reference `foo' on page 1 undefined on input line 12.
+
+) (./chapters/outro.tex) 
+! Undefined control sequence.
+l.7 Some text \foo
+                   and more text
+The control sequence at the end of the top line
+of your error message was never \def'ed.
+
+)"#,
+        expect![[r#"
+            BuildLog {
+                errors: [
+                    BuildError {
+                        relative_path: "./chapters/intro.tex",
+                        level: Warning,
+                        category: OverfullBox,
+                        message: "Overfull \\hbox (12.0pt too wide) in paragraph at lines 3--4",
+                        help: None,
+                        hint: None,
+                        line: Some(
+                            2,
+                        ),
+                        column: None,
+                    },
+                    BuildError {
+                        relative_path: "./chapters/intro.tex",
+                        level: Warning,
+                        category: UndefinedReference,
+                        message: "LaTeX Warning: Reference `foo' on page 1 undefined on input line 12.",
+                        help: None,
+                        hint: None,
+                        line: Some(
+                            11,
+                        ),
+                        column: None,
+                    },
+                    BuildError {
+                        relative_path: "./main.tex",
+                        level: Error,
+                        category: UndefinedControlSequence,
+                        message: "Undefined control sequence.",
+                        help: Some(
+                            "The control sequence at the end of the top line of your error message was never \\def'ed.",
+                        ),
+                        hint: Some(
+                            "Some text \\foo",
+                        ),
+                        line: Some(
+                            6,
+                        ),
+                        column: Some(
+                            14,
+                        ),
                     },
                 ],
             }
         "#]],
     );
 }
+
+#[test]
+fn test_missing_file() {
+    check(
+        r#"(./main.tex
+! LaTeX Error: File `foo.sty' not found.
+
+Type X to quit or <RETURN> to proceed,
+or enter new name. (Default extension: sty)
+
+Enter file name: 
+! Emergency stop.
+<read *> 
+         
+l.3 \begin
+          {document}
+*** (cannot \read from terminal in nonstop modes)
+"#,
+        expect![[r#"
+            BuildLog {
+                errors: [
+                    BuildError {
+                        relative_path: "./main.tex",
+                        level: Error,
+                        category: MissingFile,
+                        message: "LaTeX Error: File `foo.sty' not found.",
+                        help: None,
+                        hint: None,
+                        line: None,
+                        column: None,
+                    },
+                    BuildError {
+                        relative_path: "./main.tex",
+                        level: Error,
+                        category: Other,
+                        message: "Emergency stop.",
+                        help: Some(
+                            "*** (cannot \\read from terminal in nonstop modes)",
+                        ),
+                        hint: Some(
+                            "\\begin",
+                        ),
+                        line: Some(
+                            2,
+                        ),
+                        column: Some(
+                            6,
+                        ),
+                    },
+                ],
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_package_error() {
+    check(
+        r#"(./main.tex
+! Package pgfkeys Error: I do not know the key '/tikz/foo' and I am going to ig
+nore it. Perhaps you misspelled it.
+
+See the pgfkeys package documentation for explanation.
+Type  H <return>  for immediate help.
+ ...                                              
+                                                  
+l.5 \draw[foo]
+               (0,0) -- (1,1);
+This error message was generated by an \errmessage
+command, so I can't give any explicit help.
+Pretend that you're Hercule Poirot: Examine all clues,
+and deduce the truth by order and logic.
+
+)"#,
+        expect![[r#"
+            BuildLog {
+                errors: [
+                    BuildError {
+                        relative_path: "./main.tex",
+                        level: Error,
+                        category: Other,
+                        message: "Package pgfkeys Error: I do not know the key '/tikz/foo' and I am going to ignore it. Perhaps you misspelled it.",
+                        help: Some(
+                            "This error message was generated by an \\errmessage command, so I can't give any explicit help. Pretend that you're Hercule Poirot: Examine all clues, and deduce the truth by order and logic.",
+                        ),
+                        hint: Some(
+                            "\\draw[foo]",
+                        ),
+                        line: Some(
+                            4,
+                        ),
+                        column: Some(
+                            10,
+                        ),
+                    },
+                ],
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_incremental() {
+    let mut parser = BuildLogParser::default();
+    for line in [
+        "This is pdfTeX, Version 3.141592653-2.6-1.40.25 (TeX Live 2023) (preloaded format=pdflatex)",
+        "(./main.tex",
+        "! Undefined control sequence.",
+        "l.42 \\foo",
+        "",
+    ] {
+        parser.push_line(line);
+        assert!(parser.errors().is_empty());
+    }
+
+    parser.push_line("");
+    assert_eq!(parser.errors().len(), 1);
+    assert_eq!(parser.errors()[0].line, Some(41));
+}
+
+#[test]
+fn test_progress() {
+    let mut parser = BuildLogParser::default();
+    let output = [
+        "Latexmk: applying rule 'pdflatex'...",
+        "This is pdfTeX, Version 3.141592653-2.6-1.40.25 (TeX Live 2023) (preloaded format=pdflatex)",
+        "(./main.tex [1{/usr/share/texmf/fonts/map/pdftex/updmap/pdftex.map}] [2] [3]",
+        ") [4] (./main.aux) )",
+        "This is pdfTeX, Version 3.141592653-2.6-1.40.25 (TeX Live 2023) (preloaded format=pdflatex)",
+        "(./main.tex [1] [2",
+    ];
+
+    for line in output {
+        parser.push_line(line);
+    }
+
+    assert_eq!(
+        parser.progress(),
+        BuildProgress {
+            pass: 2,
+            page: Some(2),
+            total_pages: Some(4),
+        }
+    );
+}
+
+#[test]
+fn test_multiple_passes() {
+    let output = r#"This is pdfTeX, Version 3.141592653-2.6-1.40.25 (TeX Live 2023) (preloaded format=pdflatex)
+(./main.tex
+LaTeX Warning: Reference `foo' on page 1 undefined on input line 3.
+
+)
+This is pdfTeX, Version 3.141592653-2.6-1.40.25 (TeX Live 2023) (preloaded format=pdflatex)
+(./main.tex [1] )"#;
+
+    assert!(parse_build_log(output).errors.is_empty());
+}
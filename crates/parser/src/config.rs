@@ -1,6 +1,6 @@
 use rustc_hash::FxHashSet;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SyntaxConfig {
     pub follow_package_links: bool,
     pub math_environments: FxHashSet<String>,
@@ -0,0 +1,43 @@
+use std::path::{Component, Path, PathBuf};
+
+use rustc_hash::FxHashSet;
+use syntax::fls::FlsData;
+
+/// Parses a `.fls` file produced by `-recorder`.
+///
+/// Relative paths are resolved against the `PWD` line if present.
+/// Duplicate entries are removed while keeping the order of the file.
+pub fn parse_fls(input: &str) -> FlsData {
+    let mut data = FlsData::default();
+    let mut pwd: Option<PathBuf> = None;
+    let mut visited = FxHashSet::default();
+    for line in input.lines() {
+        let Some((kind, path)) = line.split_once(' ') else {
+            continue;
+        };
+
+        let path = normalize(Path::new(path.trim_end()));
+        let path = match &pwd {
+            Some(dir) => dir.join(path),
+            None => path,
+        };
+
+        match kind {
+            "PWD" => pwd = Some(path),
+            "INPUT" if visited.insert((kind, path.clone())) => data.inputs.push(path),
+            "OUTPUT" if visited.insert((kind, path.clone())) => data.outputs.push(path),
+            _ => {}
+        };
+    }
+
+    data
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| *component != Component::CurDir)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,72 @@
+use expect_test::{expect, Expect};
+
+use crate::parse_fls;
+
+fn check(input: &str, expect: Expect) {
+    expect.assert_debug_eq(&parse_fls(input));
+}
+
+#[test]
+fn test_pwd() {
+    check(
+        r#"PWD /home/user/project
+INPUT /usr/share/texlive/texmf-dist/web2c/texmf.cnf
+INPUT main.tex
+OUTPUT main.log
+INPUT ./chapters/intro.tex
+INPUT /usr/share/texlive/texmf-dist/tex/latex/base/article.cls
+OUTPUT main.aux
+INPUT main.tex
+OUTPUT main.pdf"#,
+        expect![[r#"
+            FlsData {
+                inputs: [
+                    "/usr/share/texlive/texmf-dist/web2c/texmf.cnf",
+                    "/home/user/project/main.tex",
+                    "/home/user/project/chapters/intro.tex",
+                    "/usr/share/texlive/texmf-dist/tex/latex/base/article.cls",
+                ],
+                outputs: [
+                    "/home/user/project/main.log",
+                    "/home/user/project/main.aux",
+                    "/home/user/project/main.pdf",
+                ],
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_without_pwd() {
+    check(
+        r#"INPUT ./main.tex
+OUTPUT build/main.aux
+INPUT
+FOO bar"#,
+        expect![[r#"
+            FlsData {
+                inputs: [
+                    "main.tex",
+                ],
+                outputs: [
+                    "build/main.aux",
+                ],
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_crlf() {
+    check(
+        "PWD C:/project\r\nINPUT main.tex\r\n",
+        expect![[r#"
+            FlsData {
+                inputs: [
+                    "C:/project/main.tex",
+                ],
+                outputs: [],
+            }
+        "#]],
+    );
+}
@@ -1,10 +1,13 @@
 mod lexer;
+mod reparse;
 
 use rowan::{GreenNode, GreenNodeBuilder};
 use syntax::latex::SyntaxKind::{self, *};
 
 use crate::SyntaxConfig;
 
+pub use self::reparse::reparse_latex;
+
 use self::lexer::{
     types::{CommandName, ParagraphLevel, SectionLevel, Token},
     Lexer,
@@ -128,6 +131,7 @@ impl<'a> Parser<'a> {
                 CommandName::PackageInclude => self.package_include(),
                 CommandName::ClassInclude => self.class_include(),
                 CommandName::LatexInclude => self.latex_include(),
+                CommandName::GlossaryInclude => self.glossary_include(),
                 CommandName::BiblatexInclude => self.biblatex_include(),
                 CommandName::BibtexInclude => self.bibtex_include(),
                 CommandName::GraphicsInclude => self.graphics_include(),
@@ -814,6 +818,11 @@ impl<'a> Parser<'a> {
         self.generic_include(LATEX_INCLUDE, false);
     }
 
+    /// Parses `\loadglsentries[type]{file}`, which inputs a file with glossary entries.
+    fn glossary_include(&mut self) {
+        self.generic_include(LATEX_INCLUDE, true);
+    }
+
     fn biblatex_include(&mut self) {
         self.generic_include(BIBLATEX_INCLUDE, true);
     }
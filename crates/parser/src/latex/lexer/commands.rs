@@ -20,6 +20,7 @@ pub fn classify(name: &str, config: &SyntaxConfig) -> CommandName {
         "usepackage" | "RequirePackage" => CommandName::PackageInclude,
         "documentclass" => CommandName::ClassInclude,
         "include" | "subfileinclude" | "input" | "subfile" => CommandName::LatexInclude,
+        "loadglsentries" => CommandName::GlossaryInclude,
         "addbibresource" => CommandName::BiblatexInclude,
         "bibliography" => CommandName::BibtexInclude,
         "includegraphics" => CommandName::GraphicsInclude,
@@ -108,6 +108,7 @@ pub enum CommandName {
     PackageInclude,
     ClassInclude,
     LatexInclude,
+    GlossaryInclude,
     BiblatexInclude,
     BibtexInclude,
     GraphicsInclude,
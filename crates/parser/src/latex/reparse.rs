@@ -0,0 +1,110 @@
+use rowan::{GreenNode, TextRange};
+use syntax::latex::{self, SyntaxKind::*};
+
+use crate::SyntaxConfig;
+
+use super::{
+    lexer::types::{CommandName, Token},
+    Parser,
+};
+
+/// Reparses only the innermost group or environment that encloses the change.
+///
+/// The node is reparsed in isolation, so only nodes whose structure does not depend on the
+/// surrounding context are considered. Returns `None` if no such node exists or if
+/// the reparsed text does not close the node anymore; callers should fall back to `parse_latex`.
+pub fn reparse_latex(
+    root: &latex::SyntaxNode,
+    delete: TextRange,
+    insert: &str,
+    config: &SyntaxConfig,
+) -> Option<GreenNode> {
+    let start = match root.covering_element(delete) {
+        rowan::NodeOrToken::Node(node) => node,
+        rowan::NodeOrToken::Token(token) => token.parent()?,
+    };
+
+    for node in start
+        .ancestors()
+        .filter(|node| matches!(node.kind(), CURLY_GROUP | ENVIRONMENT))
+    {
+        if !is_context_free(&node) {
+            continue;
+        }
+
+        let Some((open, close)) = find_delimiters(&node) else {
+            continue;
+        };
+
+        if delete.start() < open.end() || delete.end() > close.start() {
+            continue;
+        }
+
+        let offset = node.text_range().start();
+        let mut text = node.text().to_string();
+        text.replace_range(std::ops::Range::<usize>::from(delete - offset), insert);
+
+        if let Some(green) = reparse_node(node.kind(), &text, config) {
+            return Some(node.replace_with(green));
+        }
+    }
+
+    None
+}
+
+/// Only groups that the parser builds with `curly_group` can be reparsed in isolation.
+///
+/// The groups of command definitions and `\graphicspath` as well as everything inside
+/// environment definitions are built by specialised rules.
+fn is_context_free(node: &latex::SyntaxNode) -> bool {
+    let is_special_group = node.kind() == CURLY_GROUP
+        && node.parent().is_some_and(|parent| {
+            matches!(
+                parent.kind(),
+                NEW_COMMAND_DEFINITION | MATH_OPERATOR | GRAPHICS_PATH
+            )
+        });
+
+    !is_special_group
+        && !node
+            .ancestors()
+            .any(|ancestor| ancestor.kind() == ENVIRONMENT_DEFINITION)
+}
+
+fn reparse_node(kind: latex::SyntaxKind, text: &str, config: &SyntaxConfig) -> Option<GreenNode> {
+    let mut parser = Parser::new(text, config);
+    match (kind, parser.peek()?) {
+        (CURLY_GROUP, Token::LCurly) => parser.curly_group(),
+        (ENVIRONMENT, Token::CommandName(CommandName::BeginEnvironment)) => parser.environment(),
+        _ => return None,
+    };
+
+    if parser.peek().is_some() {
+        return None;
+    }
+
+    let green = parser.builder.finish();
+    find_delimiters(&latex::SyntaxNode::new_root(green.clone()))?;
+    Some(green)
+}
+
+/// Finds the ranges of the opening and closing tokens of a properly closed node.
+fn find_delimiters(node: &latex::SyntaxNode) -> Option<(TextRange, TextRange)> {
+    let open = node.first_token()?;
+    let close = std::iter::successors(node.last_token(), |token| token.prev_token())
+        .find(|token| !matches!(token.kind(), WHITESPACE | LINE_BREAK | COMMENT))?;
+
+    let owner = match node.kind() {
+        CURLY_GROUP if open.kind() == L_CURLY => close.parent()?,
+        ENVIRONMENT if open.kind() == COMMAND_NAME => {
+            let end = close.parent()?.parent()?;
+            (end.kind() == END).then_some(end)?.parent()?
+        }
+        _ => return None,
+    };
+
+    (close.kind() == R_CURLY && &owner == node).then(|| (open.text_range(), close.text_range()))
+}
+
+#[cfg(test)]
+mod tests;
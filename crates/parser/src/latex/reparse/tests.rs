@@ -0,0 +1,108 @@
+use rowan::{TextRange, TextSize};
+
+use crate::{parse_latex, reparse_latex, SyntaxConfig};
+
+/// Applies the edit `insert` at the first `|` in `input` (removing the marker).
+/// A second `|` marks the end of the deleted range.
+fn check(input: &str, insert: &str, is_incremental: bool) {
+    let config = SyntaxConfig::default();
+    let start = input.find('|').unwrap();
+    let old_text = input.replacen('|', "", 1);
+    let end = old_text.find('|').unwrap_or(start);
+    let old_text = old_text.replacen('|', "", 1);
+
+    let delete = TextRange::new(TextSize::from(start as u32), TextSize::from(end as u32));
+    let mut new_text = old_text.clone();
+    new_text.replace_range(start..end, insert);
+
+    let old_root = syntax::latex::SyntaxNode::new_root(parse_latex(&old_text, &config));
+    let actual = reparse_latex(&old_root, delete, insert, &config);
+    assert_eq!(actual.is_some(), is_incremental);
+
+    if let Some(actual) = actual {
+        assert_eq!(actual, parse_latex(&new_text, &config));
+    }
+}
+
+#[test]
+fn test_curly_group_insert() {
+    check(r#"\foo{bar|} \baz{qux}"#, r#" \ref{a}"#, true);
+}
+
+#[test]
+fn test_curly_group_delete() {
+    check(r#"\foo{b|a|r} \baz{qux}"#, "", true);
+}
+
+#[test]
+fn test_nested_curly_group() {
+    check(r#"{a {b {c|}} d}"#, "\n\n", true);
+}
+
+#[test]
+fn test_environment_body() {
+    check(
+        "\\begin{document}\n\\section{Foo}\n|\n\\end{document}\n",
+        r#"\label{sec:foo} \cite{bar}"#,
+        true,
+    );
+}
+
+#[test]
+fn test_environment_name() {
+    check("\\begin{document}\n{foo|}\n\\end{document}\n", "x", true);
+}
+
+#[test]
+fn test_unbalanced_insert() {
+    check(r#"\foo{bar|} \baz{qux}"#, "}", false);
+}
+
+#[test]
+fn test_unbalanced_environment() {
+    check(
+        "\\begin{document}\n|\n\\end{document}\n",
+        r#"\end{foo}"#,
+        false,
+    );
+}
+
+#[test]
+fn test_top_level() {
+    check(r#"\foo{bar} |\baz{qux}"#, "quux ", false);
+}
+
+#[test]
+fn test_command_definition() {
+    check(r#"\newcommand{\foo}{\begin{|}"#, "x", false);
+}
+
+#[test]
+fn test_environment_definition() {
+    check(r#"\newenvironment{foo}{{\begin{|}}}{}"#, "bar", false);
+}
+
+#[test]
+fn test_block_comment() {
+    check(r#"{\iffalse a|} \fi}"#, "b", true);
+    check(r#"{a|} b \fi"#, r#"\iffalse "#, false);
+}
+
+#[test]
+fn test_graphics_path() {
+    check(r#"\graphicspath{{a/}{b|/}}"#, "c", false);
+}
+
+#[test]
+fn test_graphics_path_new_group() {
+    check(r#"\graphicspath{{a/}|}"#, "{b/}", false);
+}
+
+#[test]
+fn test_graphics_path_in_environment() {
+    check(
+        "\\begin{document}\n\\graphicspath{{a/}{b|/}}\n\\end{document}\n",
+        "c",
+        true,
+    );
+}
@@ -1753,6 +1753,32 @@ fn test_bibtex_include_simple() {
     );
 }
 
+#[test]
+fn test_glossary_include() {
+    check(
+        r#"\loadglsentries[main]{terms}"#,
+        expect![[r#"
+            ROOT@0..28
+              PREAMBLE@0..28
+                LATEX_INCLUDE@0..28
+                  COMMAND_NAME@0..15 "\\loadglsentries"
+                  BRACK_GROUP_KEY_VALUE@15..21
+                    L_BRACK@15..16 "["
+                    KEY_VALUE_BODY@16..20
+                      KEY_VALUE_PAIR@16..20
+                        KEY@16..20
+                          WORD@16..20 "main"
+                    R_BRACK@20..21 "]"
+                  CURLY_GROUP_WORD_LIST@21..28
+                    L_CURLY@21..22 "{"
+                    KEY@22..27
+                      WORD@22..27 "terms"
+                    R_CURLY@27..28 "}"
+
+        "#]],
+    );
+}
+
 #[test]
 fn test_class_include_empty() {
     check(
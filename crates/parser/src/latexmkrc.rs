@@ -2,6 +2,8 @@ use std::path::{Path, PathBuf};
 
 use syntax::latexmkrc::LatexmkrcData;
 
+mod eval;
+
 mod v483 {
     use std::path::Path;
 
@@ -39,7 +41,11 @@ mod v483 {
 
         let aux_dir = change_root(src_dir, temp_dir.path(), &aux_dir);
         let out_dir = change_root(src_dir, temp_dir.path(), &out_dir);
-        Ok(LatexmkrcData { aux_dir, out_dir })
+        Ok(LatexmkrcData {
+            aux_dir,
+            out_dir,
+            ..Default::default()
+        })
     }
 
     /// Extracts $aux_dir and $out_dir from lines of the form
@@ -95,7 +101,11 @@ mod v484 {
         let aux_dir = change_root(src_dir, temp_dir.path(), &aux_dir);
         let out_dir = change_root(src_dir, temp_dir.path(), &out_dir);
 
-        Ok(LatexmkrcData { aux_dir, out_dir })
+        Ok(LatexmkrcData {
+            aux_dir,
+            out_dir,
+            ..Default::default()
+        })
     }
 
     /// Extracts $aux_dir and $out_dir from lines of the form
@@ -123,7 +133,24 @@ mod v484 {
     }
 }
 
+/// Reads the settings of a `latexmkrc` file.
+///
+/// The file is evaluated in-process if possible. Otherwise, `latexmk` is used to report
+/// the output directories which means that the other settings are not available.
 pub fn parse_latexmkrc(input: &str, src_dir: &Path) -> std::io::Result<LatexmkrcData> {
+    let result = match eval::evaluate(input) {
+        Some(data) => Ok(normalize_dirs(data, src_dir)),
+        None => {
+            log::debug!("Falling back to latexmk to evaluate latexmkrc in {src_dir:?}");
+            run_latexmk(input, src_dir)
+        }
+    };
+
+    log::debug!("Latexmkrc parsing result: src_dir={src_dir:?}, output={result:?}");
+    result
+}
+
+fn run_latexmk(input: &str, src_dir: &Path) -> std::io::Result<LatexmkrcData> {
     let output = std::process::Command::new("latexmk")
         .arg("--version")
         .output()?;
@@ -135,14 +162,27 @@ pub fn parse_latexmkrc(input: &str, src_dir: &Path) -> std::io::Result<Latexmkrc
         .and_then(|(i, line)| line[i..].trim_end().strip_prefix("Version "))
         .and_then(versions::Versioning::new);
 
-    let result = if version.map_or(false, |v| v >= versions::Versioning::new("4.84").unwrap()) {
+    if version.map_or(false, |v| v >= versions::Versioning::new("4.84").unwrap()) {
         v484::parse_latexmkrc(input, src_dir)
     } else {
         v483::parse_latexmkrc(input, src_dir)
+    }
+}
+
+/// Makes the directories relative to the source directory like `latexmk -dir-report` does.
+///
+/// Like `latexmk`, the auxiliary directory defaults to the output directory.
+fn normalize_dirs(mut data: LatexmkrcData, src_dir: &Path) -> LatexmkrcData {
+    let relative = |dir: Option<String>| {
+        let path = src_dir.join(dir?);
+        let relative = pathdiff::diff_paths(path, src_dir)?;
+        let relative = relative.to_str()?;
+        (!relative.is_empty()).then(|| relative.to_string())
     };
 
-    log::debug!("Latexmkrc parsing result: src_dir={src_dir:?}, output={result:?}");
-    result
+    data.out_dir = relative(data.out_dir);
+    data.aux_dir = relative(data.aux_dir).or_else(|| data.out_dir.clone());
+    data
 }
 
 fn change_root(src_dir: &Path, tmp_dir: &Path, out_dir: &str) -> Option<String> {
@@ -155,3 +195,6 @@ fn change_root(src_dir: &Path, tmp_dir: &Path, out_dir: &str) -> Option<String>
 
     Some(relative_to_src.to_str()?.to_string())
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,433 @@
+use rustc_hash::FxHashMap;
+use syntax::latexmkrc::LatexmkrcData;
+
+/// Evaluates the subset of Perl that is commonly found in `latexmkrc` files.
+///
+/// Supported are scalar and array assignments (including `.=` and `push`),
+/// single- and double-quoted strings with interpolation, `q`, `qq` and `qw` literals,
+/// the concatenation operator and reads and writes of `$ENV{...}`.
+/// Returns `None` if the file contains anything else so that the caller can fall back to `latexmk`.
+pub fn evaluate(input: &str) -> Option<LatexmkrcData> {
+    let tokens = tokenize(input)?;
+    let mut evaluator = Evaluator {
+        tokens: &tokens,
+        pos: 0,
+        scalars: FxHashMap::default(),
+        arrays: FxHashMap::default(),
+        env: FxHashMap::default(),
+    };
+
+    while evaluator.peek().is_some() {
+        evaluator.statement()?;
+    }
+
+    let default_files = evaluator.arrays.remove("default_files").unwrap_or_default();
+
+    let scalar = |name: &str| evaluator.scalars.get(name).cloned();
+    Some(LatexmkrcData {
+        aux_dir: scalar("aux_dir"),
+        out_dir: scalar("out_dir"),
+        jobname: scalar("jobname").filter(|name| !name.is_empty()),
+        pdf_mode: scalar("pdf_mode").and_then(|mode| mode.trim().parse().ok()),
+        pdflatex: scalar("pdflatex"),
+        lualatex: scalar("lualatex"),
+        default_files,
+    })
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Token {
+    Scalar(String),
+    Array(String),
+    Ident(String),
+    Number(String),
+    String { text: String, interpolate: bool },
+    Words(Vec<String>),
+    Punct(&'static str),
+}
+
+const PUNCTUATION: &[&str] = &[".=", "=>", "=", ",", ";", "(", ")", "{", "}", "."];
+
+fn tokenize(input: &str) -> Option<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = input;
+    loop {
+        rest = rest.trim_start();
+        let Some(c) = rest.chars().next() else {
+            break;
+        };
+
+        if c == '#' {
+            rest = rest.find('\n').map_or("", |i| &rest[i..]);
+        } else if c == '$' || c == '@' {
+            let (name, tail) = read_variable_name(&rest[1..])?;
+            tokens.push(if c == '$' {
+                Token::Scalar(name.into())
+            } else {
+                Token::Array(name.into())
+            });
+
+            rest = tail;
+        } else if c == '\'' || c == '"' {
+            let (text, tail) = read_delimited(&rest[1..], c)?;
+            let interpolate = c == '"';
+            tokens.push(Token::String { text, interpolate });
+            rest = tail;
+        } else if c.is_ascii_digit() {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '_')
+                .unwrap_or(rest.len());
+
+            tokens.push(Token::Number(rest[..end].replace('_', "")));
+            rest = &rest[end..];
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+
+            let (ident, tail) = rest.split_at(end);
+            let delim = tail.chars().next().filter(|c| is_quote_delimiter(*c));
+            match (ident, delim) {
+                ("q" | "qq" | "qw", Some(delim)) => {
+                    let (text, tail) = read_delimited(&tail[delim.len_utf8()..], delim)?;
+                    tokens.push(match ident {
+                        "q" => Token::String {
+                            text,
+                            interpolate: false,
+                        },
+                        "qq" => Token::String {
+                            text,
+                            interpolate: true,
+                        },
+                        _ => Token::Words(text.split_whitespace().map(String::from).collect()),
+                    });
+
+                    rest = tail;
+                }
+                _ => {
+                    tokens.push(Token::Ident(ident.into()));
+                    rest = tail;
+                }
+            };
+        } else {
+            let punct = PUNCTUATION.iter().find(|punct| rest.starts_with(**punct))?;
+            tokens.push(Token::Punct(punct));
+            rest = &rest[punct.len()..];
+        }
+    }
+
+    Some(tokens)
+}
+
+fn read_variable_name(input: &str) -> Option<(&str, &str)> {
+    if let Some(input) = input.strip_prefix('{') {
+        let end = input.find('}')?;
+        let name = input[..end].trim();
+        return is_identifier(name).then(|| (name, &input[end + 1..]));
+    }
+
+    let end = input
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .unwrap_or(input.len());
+
+    let name = &input[..end];
+    is_identifier(name).then(|| (name, &input[end..]))
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_quote_delimiter(c: char) -> bool {
+    !c.is_alphanumeric() && !c.is_whitespace() && c != '_' && c != ',' && c != ';' && c != '='
+}
+
+/// Reads the body of a quote-like literal and keeps the escape sequences except for escaped delimiters
+/// which are removed later on when the string is evaluated.
+fn read_delimited(input: &str, open: char) -> Option<(String, &str)> {
+    let close = match open {
+        '(' => ')',
+        '[' => ']',
+        '{' => '}',
+        '<' => '>',
+        _ => open,
+    };
+
+    let mut text = String::new();
+    let mut depth = 0;
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c == '\\' {
+            let (_, next) = chars.next()?;
+            if next != open && next != close {
+                text.push('\\');
+            }
+
+            text.push(next);
+        } else if c == close && depth == 0 {
+            return Some((text, &input[i + c.len_utf8()..]));
+        } else {
+            if c == close {
+                depth -= 1;
+            } else if c == open && open != close {
+                depth += 1;
+            }
+
+            text.push(c);
+        }
+    }
+
+    None
+}
+
+struct Evaluator<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    scalars: FxHashMap<String, String>,
+    arrays: FxHashMap<String, Vec<String>>,
+    env: FxHashMap<String, String>,
+}
+
+impl<'a> Evaluator<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.tokens.get(self.pos)?;
+        self.pos += 1;
+        Some(token)
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Punct(p)) if *p == punct);
+        if found {
+            self.pos += 1;
+        }
+
+        found
+    }
+
+    fn expect(&mut self, punct: &str) -> Option<()> {
+        self.eat(punct).then_some(())
+    }
+
+    fn statement(&mut self) -> Option<()> {
+        if self.eat(";") {
+            return Some(());
+        }
+
+        if let Some(Token::Ident(ident)) = self.peek() {
+            if matches!(ident.as_str(), "my" | "our" | "local") {
+                self.pos += 1;
+            }
+        }
+
+        match self.next()? {
+            // The return value of the file (`1;`) is not relevant.
+            Token::Number(_) => {}
+            Token::Scalar(name) if name == "ENV" => {
+                self.expect("{")?;
+                let key = self.hash_key()?;
+                self.expect("}")?;
+                let value = self.assignment(self.lookup_env(&key))?;
+                self.env.insert(key, value);
+            }
+            Token::Scalar(name) => {
+                let current = self.scalars.get(name).cloned().unwrap_or_default();
+                let value = self.assignment(current)?;
+                self.scalars.insert(name.clone(), value);
+            }
+            Token::Array(name) => {
+                self.expect("=")?;
+                let values = self.list()?;
+                self.arrays.insert(name.clone(), values);
+            }
+            Token::Ident(ident) if ident == "push" || ident == "unshift" => {
+                let parens = self.eat("(");
+                let Some(Token::Array(name)) = self.next() else {
+                    return None;
+                };
+
+                let values = if self.eat(",") {
+                    self.items()?
+                } else {
+                    Vec::new()
+                };
+
+                if parens {
+                    self.expect(")")?;
+                }
+
+                let array = self.arrays.entry(name.clone()).or_default();
+                if ident == "push" {
+                    array.extend(values);
+                } else {
+                    array.splice(0..0, values);
+                }
+            }
+            _ => return None,
+        };
+
+        if self.peek().is_some() {
+            self.expect(";")?;
+        }
+
+        Some(())
+    }
+
+    fn assignment(&mut self, current: String) -> Option<String> {
+        if self.eat("=") {
+            self.expr()
+        } else if self.eat(".=") {
+            Some(current + &self.expr()?)
+        } else {
+            None
+        }
+    }
+
+    fn hash_key(&mut self) -> Option<String> {
+        match self.next()? {
+            Token::Ident(key) => Some(key.clone()),
+            Token::String { text, interpolate } => Some(self.string(text, *interpolate)),
+            _ => None,
+        }
+    }
+
+    fn list(&mut self) -> Option<Vec<String>> {
+        if self.eat("(") {
+            let values = if self.eat(")") {
+                Vec::new()
+            } else {
+                let values = self.items()?;
+                self.expect(")")?;
+                values
+            };
+
+            Some(values)
+        } else {
+            self.items()
+        }
+    }
+
+    fn items(&mut self) -> Option<Vec<String>> {
+        let mut values = Vec::new();
+        loop {
+            match self.peek()? {
+                Token::Array(name) => {
+                    self.pos += 1;
+                    values.extend(self.arrays.get(name).cloned().unwrap_or_default());
+                }
+                Token::Words(words) => {
+                    self.pos += 1;
+                    values.extend(words.iter().cloned());
+                }
+                _ => values.push(self.expr()?),
+            };
+
+            if !self.eat(",") && !self.eat("=>") {
+                break;
+            }
+
+            if matches!(self.peek(), Some(Token::Punct(")" | ";")) | None) {
+                break;
+            }
+        }
+
+        Some(values)
+    }
+
+    fn expr(&mut self) -> Option<String> {
+        let mut value = self.term()?;
+        while self.eat(".") {
+            value.push_str(&self.term()?);
+        }
+
+        Some(value)
+    }
+
+    fn term(&mut self) -> Option<String> {
+        match self.next()? {
+            Token::String { text, interpolate } => Some(self.string(text, *interpolate)),
+            Token::Number(number) => Some(number.clone()),
+            Token::Scalar(name) if name == "ENV" => {
+                self.expect("{")?;
+                let key = self.hash_key()?;
+                self.expect("}")?;
+                Some(self.lookup_env(&key))
+            }
+            Token::Scalar(name) => Some(self.scalars.get(name).cloned().unwrap_or_default()),
+            Token::Punct("(") => {
+                let value = self.expr()?;
+                self.expect(")")?;
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+
+    fn lookup_env(&self, key: &str) -> String {
+        self.env
+            .get(key)
+            .cloned()
+            .or_else(|| std::env::var(key).ok())
+            .unwrap_or_default()
+    }
+
+    /// Processes the escape sequences and interpolates the variables of a string literal.
+    fn string(&self, text: &str, interpolate: bool) -> String {
+        if !interpolate {
+            return text.replace("\\\\", "\\").replace("\\'", "'");
+        }
+
+        let mut result = String::new();
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            rest = &rest[c.len_utf8()..];
+            match c {
+                '\\' => {
+                    let Some(next) = rest.chars().next() else {
+                        result.push('\\');
+                        break;
+                    };
+
+                    rest = &rest[next.len_utf8()..];
+                    result.push(match next {
+                        'n' => '\n',
+                        't' => '\t',
+                        _ => next,
+                    });
+                }
+                '$' | '@' => match read_variable_name(rest) {
+                    Some(("ENV", tail)) if c == '$' && tail.starts_with('{') => {
+                        let Some(end) = tail.find('}') else {
+                            result.push_str("$ENV");
+                            rest = tail;
+                            continue;
+                        };
+
+                        let key = tail[1..end].trim().trim_matches(|c| c == '\'' || c == '"');
+                        result.push_str(&self.lookup_env(key));
+                        rest = &tail[end + 1..];
+                    }
+                    Some((name, tail)) if c == '$' => {
+                        result.push_str(self.scalars.get(name).map_or("", String::as_str));
+                        rest = tail;
+                    }
+                    Some((name, tail)) => {
+                        let values = self.arrays.get(name).map_or(&[][..], Vec::as_slice);
+                        result.push_str(&values.join(" "));
+                        rest = tail;
+                    }
+                    None => result.push(c),
+                },
+                _ => result.push(c),
+            };
+        }
+
+        result
+    }
+}
@@ -0,0 +1,180 @@
+use std::path::Path;
+
+use expect_test::{expect, Expect};
+
+use super::eval::evaluate;
+
+fn check(input: &str, expect: Expect) {
+    expect.assert_debug_eq(&evaluate(input));
+}
+
+#[test]
+fn test_empty() {
+    check(
+        r#""#,
+        expect![[r#"
+            Some(
+                LatexmkrcData {
+                    aux_dir: None,
+                    out_dir: None,
+                    jobname: None,
+                    pdf_mode: None,
+                    pdflatex: None,
+                    lualatex: None,
+                    default_files: [],
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_scalars() {
+    check(
+        r#"
+# Build with LuaLaTeX
+$pdf_mode = 4;
+$lualatex = 'lualatex -synctex=1 %O %S';
+$pdflatex = "pdflatex -shell-escape %O %S";
+$out_dir = 'build';
+$jobname = "thesis";
+1;
+"#,
+        expect![[r#"
+            Some(
+                LatexmkrcData {
+                    aux_dir: None,
+                    out_dir: Some(
+                        "build",
+                    ),
+                    jobname: Some(
+                        "thesis",
+                    ),
+                    pdf_mode: Some(
+                        4,
+                    ),
+                    pdflatex: Some(
+                        "pdflatex -shell-escape %O %S",
+                    ),
+                    lualatex: Some(
+                        "lualatex -synctex=1 %O %S",
+                    ),
+                    default_files: [],
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_interpolation() {
+    check(
+        r#"
+my $build = "out";
+$out_dir = "$build/pdf";
+$aux_dir = "${build}/aux" . '/$x';
+$jobname = 'main';
+$jobname .= "-\$final";
+"#,
+        expect![[r#"
+            Some(
+                LatexmkrcData {
+                    aux_dir: Some(
+                        "out/aux/$x",
+                    ),
+                    out_dir: Some(
+                        "out/pdf",
+                    ),
+                    jobname: Some(
+                        "main-$final",
+                    ),
+                    pdf_mode: None,
+                    pdflatex: None,
+                    lualatex: None,
+                    default_files: [],
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_env() {
+    check(
+        r#"
+$ENV{'TEXLAB_TEST_BUILD'} = 'target';
+$out_dir = "$ENV{TEXLAB_TEST_BUILD}/pdf";
+$aux_dir = $ENV{"TEXLAB_TEST_BUILD"} . '/aux';
+"#,
+        expect![[r#"
+            Some(
+                LatexmkrcData {
+                    aux_dir: Some(
+                        "target/aux",
+                    ),
+                    out_dir: Some(
+                        "target/pdf",
+                    ),
+                    jobname: None,
+                    pdf_mode: None,
+                    pdflatex: None,
+                    lualatex: None,
+                    default_files: [],
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_default_files() {
+    check(
+        r#"
+@default_files = ('main.tex');
+push @default_files, "appendix.tex", qw(slides.tex notes.tex);
+push(@default_files, 'poster.tex');
+"#,
+        expect![[r#"
+            Some(
+                LatexmkrcData {
+                    aux_dir: None,
+                    out_dir: None,
+                    jobname: None,
+                    pdf_mode: None,
+                    pdflatex: None,
+                    lualatex: None,
+                    default_files: [
+                        "main.tex",
+                        "appendix.tex",
+                        "slides.tex",
+                        "notes.tex",
+                        "poster.tex",
+                    ],
+                },
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_unsupported() {
+    check(
+        r#"
+$out_dir = 'build';
+add_cus_dep('glo', 'gls', 0, 'makeglossaries');
+sub makeglossaries {
+    system("makeglossaries '$_[0]'");
+}
+"#,
+        expect![[r#"
+            None
+        "#]],
+    );
+}
+
+#[test]
+fn test_normalize_dirs() {
+    let data = super::parse_latexmkrc("$out_dir = './build/';", Path::new("/texlab")).unwrap();
+    assert_eq!(data.out_dir.as_deref(), Some("build"));
+    assert_eq!(data.aux_dir.as_deref(), Some("build"));
+}
@@ -1,10 +1,19 @@
 mod bibtex;
+mod blg;
 mod build_log;
 mod config;
+mod fls;
 mod latex;
 mod latexmkrc;
+mod tectonic;
 
 pub use self::{
-    bibtex::parse_bibtex, build_log::parse_build_log, config::*, latex::parse_latex,
+    bibtex::parse_bibtex,
+    blg::parse_blg,
+    build_log::{parse_build_log, BuildLogParser, BuildProgress},
+    config::*,
+    fls::parse_fls,
+    latex::{parse_latex, reparse_latex},
     latexmkrc::parse_latexmkrc,
+    tectonic::parse_tectonic,
 };
@@ -0,0 +1,74 @@
+use serde::Deserialize;
+use syntax::tectonic::{TectonicData, TectonicOutput, TectonicOutputKind};
+
+#[derive(Debug, Deserialize)]
+struct RawConfig {
+    doc: Option<RawDoc>,
+    #[serde(default)]
+    output: Vec<RawOutput>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawDoc {
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawOutput {
+    name: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    tex_format: Option<String>,
+    shell_escape: Option<bool>,
+    preamble: Option<String>,
+    index: Option<String>,
+    postamble: Option<String>,
+    inputs: Option<Vec<toml::Value>>,
+}
+
+/// Parses a `Tectonic.toml` file.
+///
+/// The `inputs` key takes precedence over `preamble`, `index` and `postamble` like in Tectonic.
+/// Inline inputs are not part of the `src` directory and are skipped.
+pub fn parse_tectonic(input: &str) -> Result<TectonicData, toml::de::Error> {
+    let config: RawConfig = toml::from_str(input)?;
+    let outputs = config
+        .output
+        .into_iter()
+        .map(|output| {
+            let default = TectonicOutput::default();
+            let kind = match output.kind.as_deref() {
+                Some("html") => TectonicOutputKind::Html,
+                _ => TectonicOutputKind::Pdf,
+            };
+
+            let inputs = match output.inputs {
+                Some(inputs) => inputs
+                    .into_iter()
+                    .filter_map(|input| input.as_str().map(String::from))
+                    .collect(),
+                None => [output.preamble, output.index, output.postamble]
+                    .into_iter()
+                    .zip(default.inputs)
+                    .map(|(file, default)| file.unwrap_or(default))
+                    .collect(),
+            };
+
+            TectonicOutput {
+                name: output.name,
+                kind,
+                tex_format: output.tex_format.unwrap_or(default.tex_format),
+                shell_escape: output.shell_escape.unwrap_or(default.shell_escape),
+                inputs,
+            }
+        })
+        .collect();
+
+    Ok(TectonicData {
+        name: config.doc.and_then(|doc| doc.name),
+        outputs,
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,95 @@
+use expect_test::{expect, Expect};
+
+use crate::parse_tectonic;
+
+fn check(input: &str, expect: Expect) {
+    expect.assert_debug_eq(&parse_tectonic(input).unwrap());
+}
+
+#[test]
+fn test_default() {
+    check(
+        r#"
+[doc]
+name = "thesis"
+bundle = "https://data1.fullyjustified.net/tlextras-2022.0r0.tar"
+
+[[output]]
+name = "default"
+type = "pdf"
+"#,
+        expect![[r#"
+            TectonicData {
+                name: Some(
+                    "thesis",
+                ),
+                outputs: [
+                    TectonicOutput {
+                        name: "default",
+                        kind: Pdf,
+                        tex_format: "latex",
+                        shell_escape: false,
+                        inputs: [
+                            "_preamble.tex",
+                            "index.tex",
+                            "_postamble.tex",
+                        ],
+                    },
+                ],
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_multiple_outputs() {
+    check(
+        r#"
+[doc]
+name = "book"
+
+[[output]]
+name = "print"
+type = "pdf"
+tex_format = "plain"
+shell_escape = true
+preamble = "print-preamble.tex"
+index = "book.tex"
+
+[[output]]
+name = "web"
+type = "html"
+inputs = ["web-preamble.tex", { inline = "\\def\\web{}" }, "book.tex"]
+"#,
+        expect![[r#"
+            TectonicData {
+                name: Some(
+                    "book",
+                ),
+                outputs: [
+                    TectonicOutput {
+                        name: "print",
+                        kind: Pdf,
+                        tex_format: "plain",
+                        shell_escape: true,
+                        inputs: [
+                            "print-preamble.tex",
+                            "book.tex",
+                            "_postamble.tex",
+                        ],
+                    },
+                    TectonicOutput {
+                        name: "web",
+                        kind: Html,
+                        tex_format: "latex",
+                        shell_escape: false,
+                        inputs: [
+                            "web-preamble.tex",
+                            "book.tex",
+                        ],
+                    },
+                ],
+            }
+        "#]],
+    );
+}
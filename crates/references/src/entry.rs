@@ -19,12 +19,21 @@ pub(super) fn find_all(context: &mut ReferenceContext) -> Option<()> {
             result.object.name_text()
         }
         DocumentData::Bib(data) => {
-            let result = queries::object_at_cursor(
+            match queries::object_at_cursor(
                 &data.semantics.entries,
                 offset,
                 queries::SearchMode::Name,
-            )?;
-            result.object.name_text()
+            ) {
+                Some(result) => result.object.name_text(),
+                None => {
+                    let result = queries::object_at_cursor(
+                        &data.semantics.links,
+                        offset,
+                        queries::SearchMode::Name,
+                    )?;
+                    result.object.name_text()
+                }
+            }
         }
         _ => return None,
     };
@@ -37,12 +46,25 @@ pub(super) fn find_all(context: &mut ReferenceContext) -> Option<()> {
         });
     }
 
-    for (document, obj) in queries::objects_with_name::<bib::Entry>(project, name) {
+    for (document, obj) in queries::objects_with_name::<bib::EntryLink>(project, name) {
         context.results.push(Reference {
             location: DocumentLocation::new(document, obj.name.range),
-            kind: ReferenceKind::Definition,
+            kind: ReferenceKind::Reference,
         });
     }
 
+    for (document, obj) in bib::Entry::find_all(project) {
+        let aliases = obj.aliases.iter().filter(|alias| alias.text == name);
+        for span in std::iter::once(&obj.name)
+            .filter(|span| span.text == name)
+            .chain(aliases)
+        {
+            context.results.push(Reference {
+                location: DocumentLocation::new(document, span.range),
+                kind: ReferenceKind::Definition,
+            });
+        }
+    }
+
     Some(())
 }
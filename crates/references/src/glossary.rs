@@ -0,0 +1,31 @@
+use base_db::{
+    semantics::tex,
+    util::queries::{self, Object, ObjectKind},
+    DocumentLocation,
+};
+
+use crate::{Reference, ReferenceContext, ReferenceKind};
+
+pub(super) fn find_all(context: &mut ReferenceContext) -> Option<()> {
+    let data = context.params.feature.document.data.as_tex()?;
+    let mode = queries::SearchMode::Full;
+    let entries = &data.semantics.glossary_entries;
+    let name = queries::object_at_cursor(entries, context.params.offset, mode)?
+        .object
+        .name_text();
+
+    let project = &context.params.feature.project;
+    for (document, entry) in queries::objects_with_name::<tex::GlossaryEntry>(project, name) {
+        let kind = match entry.kind() {
+            ObjectKind::Definition => ReferenceKind::Definition,
+            ObjectKind::Reference => ReferenceKind::Reference,
+        };
+
+        context.results.push(Reference {
+            location: DocumentLocation::new(document, entry.name.range),
+            kind,
+        });
+    }
+
+    Some(())
+}
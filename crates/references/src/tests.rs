@@ -53,6 +53,46 @@ fn test_entry_definition_include_decl() {
     );
 }
 
+#[test]
+fn test_entry_crossref() {
+    check(
+        r#"
+%! main.bib
+@book{foo, title = {Foo}}
+      ^^^
+@inbook{bar, crossref = {foo}}
+                          |
+                         ^^^
+@inbook{baz, xdata = {qux, foo}}
+                           ^^^
+
+%! main.tex
+\addbibresource{main.bib}
+\cite{foo}
+      ^^^
+"#,
+        true,
+    );
+}
+
+#[test]
+fn test_entry_alias() {
+    check(
+        r#"
+%! main.bib
+@book{foo, ids = {bar}}
+                  ^^^
+
+%! main.tex
+\addbibresource{main.bib}
+\cite{bar}
+       |
+      ^^^
+"#,
+        true,
+    );
+}
+
 #[test]
 fn test_entry_reference() {
     check(
@@ -260,3 +300,60 @@ fn test_new_command_definition_include_decl() {
         true,
     );
 }
+
+#[test]
+fn test_glossary_entry_definition() {
+    check(
+        r#"
+%! foo.tex
+\newglossaryentry{foo}{name=Foo, description=Bar}
+                   |
+
+%! bar.tex
+\gls{foo}
+     ^^^
+\Glspl{foo}
+       ^^^
+\input{foo.tex}
+"#,
+        false,
+    );
+}
+
+#[test]
+fn test_acronym_reference_include_decl() {
+    check(
+        r#"
+%! foo.tex
+\DeclareAcronym{foo}{short=FOO, long=Foo Bar}
+                ^^^
+
+%! bar.tex
+\ac{foo}
+     |
+    ^^^
+\gls{foo}
+     ^^^
+\input{foo.tex}
+"#,
+        true,
+    );
+}
+
+#[test]
+fn test_label_project_config() {
+    check(
+        r#"
+%! .texlabroot
+[experimental]
+labelReferenceCommands = ["myref"]
+
+%! main.tex
+\label{foo}
+       |
+\myref{foo}
+       ^^^
+"#,
+        false,
+    );
+}
@@ -1,6 +1,6 @@
 use base_db::{
     semantics::{bib, tex, Span},
-    util::queries::{self},
+    util::queries::{self, Object},
     DocumentData,
 };
 
@@ -18,10 +18,20 @@ pub(super) fn prepare_rename(params: &RenameParams) -> Option<Span> {
             Some(Span::new(result.object.name.text.clone(), result.range))
         }
         DocumentData::Bib(data) => {
-            let result = queries::object_at_cursor(
+            let entry = queries::object_at_cursor(
                 &data.semantics.entries,
                 params.offset,
                 queries::SearchMode::Name,
+            );
+
+            if let Some(result) = entry {
+                return Some(Span::new(result.object.name.text.clone(), result.range));
+            }
+
+            let result = queries::object_at_cursor(
+                &data.semantics.links,
+                params.offset,
+                queries::SearchMode::Name,
             )?;
 
             Some(Span::new(result.object.name.text.clone(), result.range))
@@ -37,10 +47,17 @@ pub(super) fn rename(builder: &mut RenameBuilder) -> Option<()> {
     let citations = queries::objects_with_name::<tex::Citation>(project, &name.text)
         .map(|(doc, obj)| (doc, obj.name.range));
 
-    let entries = queries::objects_with_name::<bib::Entry>(project, &name.text)
+    let links = queries::objects_with_name::<bib::EntryLink>(project, &name.text)
         .map(|(doc, obj)| (doc, obj.name.range));
 
-    for (document, range) in citations.chain(entries) {
+    let entries = bib::Entry::find_all(project).flat_map(|(doc, obj)| {
+        std::iter::once(&obj.name)
+            .chain(&obj.aliases)
+            .filter(|span| span.text == name.text)
+            .map(move |span| (doc, span.range))
+    });
+
+    for (document, range) in citations.chain(entries).chain(links) {
         let entry = builder.result.changes.entry(document);
         entry.or_default().push(range);
     }
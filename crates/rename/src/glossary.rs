@@ -0,0 +1,25 @@
+use base_db::{
+    semantics::{tex, Span},
+    util::queries::{self, Object},
+};
+
+use crate::{RenameBuilder, RenameParams};
+
+pub(super) fn prepare_rename(params: &RenameParams) -> Option<Span> {
+    let data = params.feature.document.data.as_tex()?;
+    let entries = &data.semantics.glossary_entries;
+    let entry = queries::object_at_cursor(entries, params.offset, queries::SearchMode::Name)?;
+    Some(Span::new(entry.object.name.text.clone(), entry.range))
+}
+
+pub(super) fn rename(builder: &mut RenameBuilder) -> Option<()> {
+    let name = prepare_rename(&builder.params)?;
+
+    let project = &builder.params.feature.project;
+    for (document, entry) in queries::objects_with_name::<tex::GlossaryEntry>(project, &name.text) {
+        let entry_ranges = builder.result.changes.entry(document);
+        entry_ranges.or_default().push(entry.name_range());
+    }
+
+    Some(())
+}
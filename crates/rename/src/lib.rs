@@ -1,5 +1,6 @@
 mod command;
 mod entry;
+mod glossary;
 mod label;
 
 use base_db::{Document, FeatureParams};
@@ -26,6 +27,7 @@ pub fn prepare_rename(params: &RenameParams) -> Option<TextRange> {
     command::prepare_rename(params)
         .or_else(|| entry::prepare_rename(params))
         .or_else(|| label::prepare_rename(params))
+        .or_else(|| glossary::prepare_rename(params))
         .map(|span| span.range)
 }
 
@@ -35,7 +37,8 @@ pub fn rename(params: RenameParams) -> RenameResult {
 
     command::rename(&mut builder)
         .or_else(|| entry::rename(&mut builder))
-        .or_else(|| label::rename(&mut builder));
+        .or_else(|| label::rename(&mut builder))
+        .or_else(|| glossary::rename(&mut builder));
 
     builder.result
 }
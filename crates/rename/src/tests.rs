@@ -52,6 +52,27 @@ fn test_entry() {
     )
 }
 
+#[test]
+fn test_entry_crossref() {
+    check(
+        r#"
+%! main.bib
+@book{foo, title = {Foo}}
+      ^^^
+@inbook{bar, crossref = {foo},
+                          |
+                         ^^^
+    related = "baz, foo"}
+                    ^^^
+
+%! main.tex
+\addbibresource{main.bib}
+\cite{foo}
+      ^^^
+"#,
+    )
+}
+
 #[test]
 fn test_citation() {
     check(
@@ -87,3 +108,24 @@ fn test_label() {
 "#,
     )
 }
+
+#[test]
+fn test_glossary_entry() {
+    check(
+        r#"
+%! foo.tex
+\newacronym{foo}{FOO}{Foo Bar}\include{bar}
+             |
+            ^^^
+
+%! bar.tex
+\acrshort{foo}
+          ^^^
+\gls{foo}
+     ^^^
+
+%! baz.tex
+\gls{foo}
+"#,
+    )
+}
@@ -0,0 +1,68 @@
+use base_db::semantics::bib;
+use rowan::{ast::AstNode, TextRange, WalkEvent};
+use rustc_hash::FxHashSet;
+use syntax::bibtex;
+
+use crate::{SemanticToken, SemanticTokenParams, TokenKind, TokenModifiers};
+
+pub(super) fn find_tokens(
+    params: &SemanticTokenParams,
+    results: &mut Vec<SemanticToken>,
+) -> Option<()> {
+    let data = params.feature.document.data.as_bib()?;
+
+    let used_keys = bib::used_keys(&params.feature.project);
+    let used_entries: FxHashSet<TextRange> = data
+        .semantics
+        .entries
+        .iter()
+        .filter(|entry| entry.is_used(&used_keys))
+        .map(|entry| entry.name.range)
+        .collect();
+
+    let mut traversal = data.root_node().preorder_with_tokens();
+    while let Some(event) = traversal.next() {
+        let WalkEvent::Enter(element) = event else {
+            continue;
+        };
+
+        if element.text_range().intersect(params.range).is_none() {
+            if element.as_node().is_some() {
+                traversal.skip_subtree();
+            }
+
+            continue;
+        }
+
+        let Some(token) = element.into_token() else {
+            continue;
+        };
+
+        let parent = token.parent()?;
+        let (kind, modifiers) = match (token.kind(), parent.kind()) {
+            (bibtex::TYPE, _) => (TokenKind::EntryType, TokenModifiers::NONE),
+            (bibtex::JUNK, bibtex::ROOT) if !token.text().trim().is_empty() => {
+                (TokenKind::Comment, TokenModifiers::NONE)
+            }
+            (bibtex::NAME, bibtex::ENTRY) if used_entries.contains(&token.text_range()) => {
+                (TokenKind::Citation, TokenModifiers::DEFINITION)
+            }
+            (bibtex::NAME, bibtex::ENTRY) => (
+                TokenKind::Citation,
+                TokenModifiers::DEFINITION | TokenModifiers::UNUSED,
+            ),
+            (bibtex::NAME, bibtex::STRING) => {
+                (TokenKind::StringReference, TokenModifiers::DEFINITION)
+            }
+            (bibtex::NAME, bibtex::FIELD) => (TokenKind::FieldName, TokenModifiers::NONE),
+            (bibtex::NAME, kind) if bibtex::Value::can_cast(kind) => {
+                (TokenKind::StringReference, TokenModifiers::NONE)
+            }
+            _ => continue,
+        };
+
+        super::push_token(results, token.text_range(), kind, modifiers);
+    }
+
+    Some(())
+}
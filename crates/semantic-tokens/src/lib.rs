@@ -0,0 +1,99 @@
+mod bib;
+mod tex;
+
+use std::ops::BitOr;
+
+use base_db::FeatureParams;
+use rowan::TextRange;
+
+#[derive(Debug)]
+pub struct SemanticTokenParams<'a> {
+    pub feature: FeatureParams<'a>,
+    pub range: TextRange,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SemanticToken {
+    pub range: TextRange,
+    pub kind: TokenKind,
+    pub modifiers: TokenModifiers,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum TokenKind {
+    Command,
+    EnvironmentName,
+    Label,
+    Citation,
+    Math,
+    Comment,
+    Verbatim,
+    EntryType,
+    FieldName,
+    StringReference,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct TokenModifiers(u32);
+
+impl TokenModifiers {
+    pub const NONE: Self = Self(0);
+    pub const DEFINITION: Self = Self(1 << 0);
+    pub const UNDEFINED: Self = Self(1 << 1);
+    pub const UNUSED: Self = Self(1 << 2);
+
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for TokenModifiers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Computes the semantic tokens that intersect the requested range.
+///
+/// The returned tokens are sorted by their position and do not overlap.
+/// Multi-line tokens like math regions or block comments are not split.
+pub fn find_all(params: &SemanticTokenParams) -> Vec<SemanticToken> {
+    let mut results = Vec::new();
+    tex::find_tokens(params, &mut results);
+    bib::find_tokens(params, &mut results);
+    results
+}
+
+fn push_token(
+    results: &mut Vec<SemanticToken>,
+    range: TextRange,
+    kind: TokenKind,
+    modifiers: TokenModifiers,
+) {
+    let is_region = matches!(
+        kind,
+        TokenKind::Math | TokenKind::Comment | TokenKind::Verbatim
+    );
+
+    if let Some(last) = results.last_mut().filter(|_| is_region) {
+        if last.range.end() == range.start() && last.kind == kind && last.modifiers == modifiers {
+            last.range = last.range.cover(range);
+            return;
+        }
+    }
+
+    results.push(SemanticToken {
+        range,
+        kind,
+        modifiers,
+    });
+}
+
+#[cfg(test)]
+mod tests;
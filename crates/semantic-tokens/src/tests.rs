@@ -0,0 +1,260 @@
+use expect_test::{expect, Expect};
+use rowan::{TextRange, TextSize};
+
+use crate::{SemanticTokenParams, TokenModifiers};
+
+fn check(input: &str, expect: Expect) {
+    let fixture = test_utils::fixture::Fixture::parse(input);
+    let (feature, _) = fixture.make_params().unwrap();
+    let text = &feature.document.text;
+    let range = fixture
+        .locations()
+        .next()
+        .map(|location| location.range)
+        .unwrap_or_else(|| TextRange::new(TextSize::from(0), TextSize::of(text.as_str())));
+
+    let params = SemanticTokenParams { feature, range };
+    let mut output = String::new();
+    for token in crate::find_all(&params) {
+        let modifiers = [
+            (TokenModifiers::DEFINITION, " definition"),
+            (TokenModifiers::UNDEFINED, " undefined"),
+            (TokenModifiers::UNUSED, " unused"),
+        ]
+        .into_iter()
+        .filter(|(modifier, _)| token.modifiers.contains(*modifier))
+        .map(|(_, name)| name)
+        .collect::<String>();
+
+        output.push_str(&format!(
+            "{:?}{} {:?}\n",
+            token.kind,
+            modifiers,
+            &text[std::ops::Range::<usize>::from(token.range)]
+        ));
+    }
+
+    expect.assert_eq(&output);
+}
+
+#[test]
+fn test_command() {
+    check(
+        r#"
+%! main.tex
+\newcommand{\foo}[1]{\textbf{#1}}
+|"#,
+        expect![[r#"
+            Command "\\newcommand"
+            Command "\\foo"
+            Command "\\textbf"
+        "#]],
+    );
+}
+
+#[test]
+fn test_environment() {
+    check(
+        r#"
+%! main.tex
+\begin{document}
+\end{document}
+|"#,
+        expect![[r#"
+            Command "\\begin"
+            EnvironmentName "document"
+            Command "\\end"
+            EnvironmentName "document"
+        "#]],
+    );
+}
+
+#[test]
+fn test_label() {
+    check(
+        r#"
+%! main.tex
+\label{foo}
+\label{bar}
+\ref{foo, baz}
+|"#,
+        expect![[r#"
+            Command "\\label"
+            Label definition "foo"
+            Command "\\label"
+            Label definition unused "bar"
+            Command "\\ref"
+            Label "foo"
+            Label undefined "baz"
+        "#]],
+    );
+}
+
+#[test]
+fn test_citation() {
+    check(
+        r#"
+%! main.tex
+\bibliography{main}
+\cite{foo, bar}
+|
+
+%! main.bib
+@article{foo,}"#,
+        expect![[r#"
+            Command "\\bibliography"
+            Command "\\cite"
+            Citation "foo"
+            Citation undefined "bar"
+        "#]],
+    );
+}
+
+#[test]
+fn test_citation_alias() {
+    check(
+        r#"
+%! main.tex
+\bibliography{main}
+\cite{baz}
+|
+
+%! main.bib
+@article{foo, ids = {bar, baz}}"#,
+        expect![[r#"
+            Command "\\bibliography"
+            Command "\\cite"
+            Citation "baz"
+        "#]],
+    );
+}
+
+#[test]
+fn test_math() {
+    check(
+        r#"
+%! main.tex
+$x = \alpha$
+\begin{align}
+    y
+\end{align}
+|"#,
+        expect![[r#"
+            Math "$x = "
+            Command "\\alpha"
+            Math "$"
+            Command "\\begin"
+            EnvironmentName "align"
+            Math "y"
+            Command "\\end"
+            EnvironmentName "align"
+        "#]],
+    );
+}
+
+#[test]
+fn test_comment_and_verbatim() {
+    check(
+        r#"
+%! main.tex
+% \label{foo}
+\verb|\foo|
+\begin{verbatim}
+\label{foo}
+\end{verbatim}
+|"#,
+        expect![[r#"
+            Comment "% \\label{foo}"
+            Command "\\verb"
+            Verbatim "|\\foo|"
+            Command "\\begin"
+            EnvironmentName "verbatim"
+            Verbatim "\\label{foo}\n"
+            Command "\\end"
+            EnvironmentName "verbatim"
+        "#]],
+    );
+}
+
+#[test]
+fn test_range() {
+    check(
+        r#"
+%! main.tex
+\foo
+\bar
+^^^^
+\baz"#,
+        expect![[r#"
+            Command "\\bar"
+        "#]],
+    );
+}
+
+#[test]
+fn test_bibtex() {
+    check(
+        r#"
+%! main.bib
+junk
+@string{foo = "Foo"}
+@article{bar, title = foo # {Bar}}
+|
+
+%! main.tex
+\bibliography{main}"#,
+        expect![[r#"
+            Comment "junk\n"
+            EntryType "@string"
+            StringReference definition "foo"
+            EntryType "@article"
+            Citation definition unused "bar"
+            FieldName "title"
+            StringReference "foo"
+        "#]],
+    );
+}
+
+#[test]
+fn test_bibtex_used_entry() {
+    check(
+        r#"
+%! main.bib
+@article{bar,}
+|
+
+%! main.tex
+\bibliography{main}
+\cite{bar}"#,
+        expect![[r#"
+            EntryType "@article"
+            Citation definition "bar"
+        "#]],
+    );
+}
+
+#[test]
+fn test_bibtex_crossref_entry() {
+    check(
+        r#"
+%! main.bib
+@inbook{foo, crossref = {bar}}
+@book{bar,}
+@book{baz, ids = {qux}}
+|
+
+%! main.tex
+\bibliography{main}
+\cite{foo, qux}"#,
+        expect![[r#"
+            EntryType "@inbook"
+            Citation definition "foo"
+            FieldName "crossref"
+            EntryType "@book"
+            Citation definition "bar"
+            EntryType "@book"
+            Citation definition "baz"
+            FieldName "ids"
+        "#]],
+    );
+}
@@ -0,0 +1,177 @@
+use base_db::{
+    deps::ProjectRoot,
+    semantics::{
+        bib,
+        tex::{self, LabelKind},
+    },
+    util::queries::Object,
+};
+use parser::SyntaxConfig;
+use rowan::{ast::AstNode, TextRange, WalkEvent};
+use rustc_hash::FxHashMap;
+use syntax::latex;
+
+use crate::{SemanticToken, SemanticTokenParams, TokenKind, TokenModifiers};
+
+pub(super) fn find_tokens(
+    params: &SemanticTokenParams,
+    results: &mut Vec<SemanticToken>,
+) -> Option<()> {
+    let data = params.feature.document.data.as_tex()?;
+    let root = ProjectRoot::walk_and_find(params.feature.workspace, &params.feature.document.dir);
+    let config = &root.syntax;
+
+    let mut names = FxHashMap::default();
+    find_label_names(params, &mut names);
+    find_citation_names(params, &mut names);
+
+    let mut traversal = data.root_node().preorder_with_tokens();
+    while let Some(event) = traversal.next() {
+        let WalkEvent::Enter(element) = event else {
+            continue;
+        };
+
+        if element.text_range().intersect(params.range).is_none() {
+            if element.as_node().is_some() {
+                traversal.skip_subtree();
+            }
+
+            continue;
+        }
+
+        match element {
+            rowan::NodeOrToken::Node(node) => {
+                if node.kind() != latex::KEY {
+                    continue;
+                }
+
+                let Some(first_token) = node.first_token() else {
+                    continue;
+                };
+
+                if matches!(
+                    find_context(&first_token, config),
+                    Some(TokenKind::Comment | TokenKind::Verbatim)
+                ) {
+                    continue;
+                }
+
+                let range = latex::small_range(&latex::Key::cast(node.clone()).unwrap());
+                let name = names.get(&range).copied().or_else(|| {
+                    let parent = node.parent()?.parent()?;
+                    matches!(parent.kind(), latex::BEGIN | latex::END)
+                        .then_some((TokenKind::EnvironmentName, TokenModifiers::NONE))
+                });
+
+                if let Some((kind, modifiers)) = name {
+                    super::push_token(results, range, kind, modifiers);
+                    traversal.skip_subtree();
+                }
+            }
+            rowan::NodeOrToken::Token(token) => {
+                let kind = match token.kind() {
+                    latex::COMMENT => Some(TokenKind::Comment),
+                    latex::VERBATIM => Some(TokenKind::Verbatim),
+                    kind => match find_context(&token, config) {
+                        Some(TokenKind::Math) if kind == latex::COMMAND_NAME => {
+                            Some(TokenKind::Command)
+                        }
+                        Some(TokenKind::Math) if kind == latex::LINE_BREAK => None,
+                        Some(context) => Some(context),
+                        None if kind == latex::COMMAND_NAME => Some(TokenKind::Command),
+                        None => None,
+                    },
+                };
+
+                if let Some(kind) = kind {
+                    let range = token.text_range();
+                    super::push_token(results, range, kind, TokenModifiers::NONE);
+                }
+            }
+        };
+    }
+
+    Some(())
+}
+
+/// Finds the region (block comment, verbatim or math) that contains the given token.
+fn find_context(token: &latex::SyntaxToken, config: &SyntaxConfig) -> Option<TokenKind> {
+    let mut result = None;
+    let mut in_header = false;
+    for node in token.parent_ancestors() {
+        match node.kind() {
+            latex::BLOCK_COMMENT => return Some(TokenKind::Comment),
+            latex::FORMULA | latex::EQUATION => {
+                result = result.or(Some(TokenKind::Math));
+            }
+            latex::BEGIN | latex::END => {
+                in_header = true;
+            }
+            latex::ENVIRONMENT if !std::mem::take(&mut in_header) => {
+                let Some(name) = latex::Environment::cast(node)
+                    .and_then(|env| env.begin())
+                    .and_then(|begin| begin.name())
+                    .and_then(|name| name.key())
+                    .map(|name| name.to_string())
+                else {
+                    continue;
+                };
+
+                if config.verbatim_environments.contains(&name) {
+                    return Some(TokenKind::Verbatim);
+                }
+
+                if config.math_environments.contains(&name) {
+                    result = result.or(Some(TokenKind::Math));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    result
+}
+
+fn find_label_names(
+    params: &SemanticTokenParams,
+    names: &mut FxHashMap<TextRange, (TokenKind, TokenModifiers)>,
+) {
+    let workspace = params.feature.workspace;
+    let document = params.feature.document;
+    for (label, resolved) in tex::resolve_labels(workspace, document) {
+        let modifiers = match label.kind {
+            LabelKind::Definition if !resolved => {
+                TokenModifiers::DEFINITION | TokenModifiers::UNUSED
+            }
+            LabelKind::Definition => TokenModifiers::DEFINITION,
+            LabelKind::Reference | LabelKind::ReferenceRange if !resolved => {
+                TokenModifiers::UNDEFINED
+            }
+            LabelKind::Reference | LabelKind::ReferenceRange => TokenModifiers::NONE,
+        };
+
+        names.insert(label.name.range, (TokenKind::Label, modifiers));
+    }
+}
+
+fn find_citation_names(
+    params: &SemanticTokenParams,
+    names: &mut FxHashMap<TextRange, (TokenKind, TokenModifiers)>,
+) -> Option<()> {
+    let data = params.feature.document.data.as_tex()?;
+
+    let entries = bib::entry_keys(&params.feature.project);
+
+    for citation in &data.semantics.citations {
+        let name = citation.name_text();
+        let modifiers = if name != "*" && !entries.contains(name) {
+            TokenModifiers::UNDEFINED
+        } else {
+            TokenModifiers::NONE
+        };
+
+        names.insert(citation.name.range, (TokenKind::Citation, modifiers));
+    }
+
+    Some(())
+}
@@ -0,0 +1,80 @@
+use rowan::ast::AstNode;
+use syntax::latex;
+
+use crate::{Group, Parameter, Signature, SignatureHelp, SignatureHelpParams};
+
+pub fn find_signature_help(
+    params: &SignatureHelpParams,
+    node: &latex::SyntaxNode,
+) -> Option<SignatureHelp> {
+    let command = latex::GenericCommand::cast(node.clone())?;
+    let name = command.name()?;
+    let signature = find_user_signature(params, &name.text()[1..])
+        .or_else(|| find_package_signature(params, &name.text()[1..]))?;
+
+    let groups: Vec<Group> = command
+        .syntax()
+        .children()
+        .filter_map(|child| {
+            if let Some(group) = latex::CurlyGroup::cast(child.clone()) {
+                Some(Group {
+                    range: latex::small_range(&group),
+                    optional: false,
+                    closed: latex::HasCurly::right_curly(&group).is_some(),
+                })
+            } else {
+                let group = latex::MixedGroup::cast(child)?;
+                let left = group.left_delim()?;
+                (left.kind() == latex::L_BRACK).then(|| Group {
+                    range: latex::small_range(&group),
+                    optional: true,
+                    closed: group.right_delim().is_some(),
+                })
+            }
+        })
+        .collect();
+
+    let active_parameter = signature.active_parameter(name.text_range(), &groups, params.offset)?;
+    Some(SignatureHelp {
+        signature,
+        active_parameter,
+    })
+}
+
+fn find_user_signature(params: &SignatureHelpParams, name: &str) -> Option<Signature> {
+    let definition = params
+        .feature
+        .project
+        .documents
+        .iter()
+        .filter_map(|document| document.data.as_tex())
+        .flat_map(|data| data.semantics.command_definitions.iter())
+        .find(|definition| definition.name.text == name)?;
+
+    Some(Signature::new(
+        format!("\\{name}"),
+        definition.arity,
+        definition.default_argument.as_deref(),
+    ))
+}
+
+fn find_package_signature(params: &SignatureHelpParams, name: &str) -> Option<Signature> {
+    let command = params
+        .feature
+        .project
+        .included_packages()
+        .flat_map(|package| package.commands.iter())
+        .find(|command| command.name == name && !command.parameters.is_empty())?;
+
+    let mut signature = Signature::new(format!("\\{name}"), command.parameters.len(), None);
+    for (parameter, Parameter { documentation, .. }) in command
+        .parameters
+        .iter()
+        .zip(signature.parameters.iter_mut())
+    {
+        let choices: Vec<_> = parameter.0.iter().map(|argument| argument.name).collect();
+        *documentation = (!choices.is_empty()).then(|| format!("One of: {}", choices.join(", ")));
+    }
+
+    Some(signature)
+}
@@ -0,0 +1,69 @@
+use rowan::{ast::AstNode, NodeOrToken};
+use syntax::latex::{self, HasBrack, HasCurly};
+
+use crate::{Group, Signature, SignatureHelp, SignatureHelpParams};
+
+pub fn find_signature_help(
+    params: &SignatureHelpParams,
+    node: &latex::SyntaxNode,
+) -> Option<SignatureHelp> {
+    let environment = latex::Environment::cast(node.clone())?;
+    let begin = environment.begin()?;
+    let name = begin.name()?;
+    let name_text = name.key()?.to_string();
+
+    let definition = params
+        .feature
+        .project
+        .documents
+        .iter()
+        .filter_map(|document| document.data.as_tex())
+        .flat_map(|data| data.semantics.environment_definitions.iter())
+        .find(|definition| definition.name.text == name_text)?;
+
+    let signature = Signature::new(
+        format!("\\begin{{{name_text}}}"),
+        definition.arity,
+        definition.default_argument.as_deref(),
+    );
+
+    let mut groups = Vec::new();
+    if let Some(options) = begin.options() {
+        groups.push(Group {
+            range: latex::small_range(&options),
+            optional: true,
+            closed: options.right_brack().is_some(),
+        });
+    }
+
+    // The mandatory arguments are parsed as part of the environment body.
+    for child in environment
+        .syntax()
+        .children_with_tokens()
+        .skip_while(|child| child.as_node() != Some(begin.syntax()))
+        .skip(1)
+    {
+        match child {
+            NodeOrToken::Token(token) if token.kind() == latex::WHITESPACE => continue,
+            NodeOrToken::Node(node) => {
+                let Some(group) = latex::CurlyGroup::cast(node) else {
+                    break;
+                };
+
+                groups.push(Group {
+                    range: latex::small_range(&group),
+                    optional: false,
+                    closed: group.right_curly().is_some(),
+                });
+            }
+            NodeOrToken::Token(_) => break,
+        };
+    }
+
+    let head = latex::small_range(&name);
+    let active_parameter = signature.active_parameter(head, &groups, params.offset)?;
+    Some(SignatureHelp {
+        signature,
+        active_parameter,
+    })
+}
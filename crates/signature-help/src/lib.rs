@@ -0,0 +1,118 @@
+mod command;
+mod environment;
+
+use base_db::FeatureParams;
+use rowan::{TextRange, TextSize};
+
+#[derive(Debug)]
+pub struct SignatureHelpParams<'a> {
+    pub feature: FeatureParams<'a>,
+    pub offset: TextSize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SignatureHelp {
+    pub signature: Signature,
+    pub active_parameter: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Signature {
+    pub label: String,
+    pub parameters: Vec<Parameter>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Parameter {
+    pub label: String,
+    pub optional: bool,
+    pub documentation: Option<String>,
+}
+
+pub fn find(params: &SignatureHelpParams) -> Option<SignatureHelp> {
+    let data = params.feature.document.data.as_tex()?;
+    let token = data
+        .root_node()
+        .token_at_offset(params.offset)
+        .left_biased()?;
+
+    // Arguments of one command can contain other commands without a known signature.
+    token.parent_ancestors().find_map(|node| {
+        command::find_signature_help(params, &node)
+            .or_else(|| environment::find_signature_help(params, &node))
+    })
+}
+
+/// An argument of a command that has already been written.
+#[derive(Debug)]
+struct Group {
+    range: TextRange,
+    optional: bool,
+    closed: bool,
+}
+
+impl Signature {
+    /// Builds a signature like `\foo[#1]{#2}` where only the first argument may be optional.
+    fn new(prefix: String, arity: usize, default_argument: Option<&str>) -> Self {
+        let mut label = prefix;
+        let mut parameters = Vec::new();
+        for i in 1..=arity {
+            let optional = i == 1 && default_argument.is_some();
+            let parameter_label = if optional {
+                format!("[#{i}]")
+            } else {
+                format!("{{#{i}}}")
+            };
+
+            label.push_str(&parameter_label);
+            parameters.push(Parameter {
+                label: parameter_label,
+                optional,
+                documentation: default_argument
+                    .filter(|_| optional)
+                    .map(|default| format!("Default: {default}")),
+            });
+        }
+
+        Self { label, parameters }
+    }
+
+    /// Finds the parameter that corresponds to the argument at the given offset.
+    ///
+    /// Optional parameters are skipped if the next argument uses curly braces.
+    /// If the cursor directly follows the last argument, then the next parameter is active.
+    fn active_parameter(
+        &self,
+        head: TextRange,
+        groups: &[Group],
+        offset: TextSize,
+    ) -> Option<usize> {
+        if self.parameters.is_empty() {
+            return None;
+        }
+
+        if offset <= head.end() {
+            return Some(0);
+        }
+
+        let mut index = 0;
+        for group in groups {
+            while !group.optional && self.parameters.get(index).is_some_and(|p| p.optional) {
+                index += 1;
+            }
+
+            let end = group.range.end();
+            if group.range.start() < offset && (offset < end || (!group.closed && offset == end)) {
+                return Some(index);
+            }
+
+            index += 1;
+        }
+
+        let last = groups.last()?;
+        (last.range.end() == offset && index < self.parameters.len()).then_some(index)
+    }
+}
+
+#[cfg(test)]
+mod tests;
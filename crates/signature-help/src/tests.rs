@@ -0,0 +1,273 @@
+use expect_test::{expect, Expect};
+
+use crate::SignatureHelpParams;
+
+fn check(input: &str, expect: Expect) {
+    let fixture = test_utils::fixture::Fixture::parse(input);
+    let (feature, offset) = fixture.make_params().unwrap();
+    let params = SignatureHelpParams { feature, offset };
+    let data = crate::find(&params).map(|help| {
+        let parameter = &help.signature.parameters[help.active_parameter];
+        (
+            help.signature.label,
+            parameter.label.clone(),
+            parameter.documentation.clone(),
+        )
+    });
+
+    expect.assert_debug_eq(&data);
+}
+
+#[test]
+fn test_smoke() {
+    check(
+        r#"
+%! main.tex
+
+|"#,
+        expect![[r#"
+            None
+        "#]],
+    );
+}
+
+#[test]
+fn test_user_command_first() {
+    check(
+        r#"
+%! main.tex
+\newcommand{\foo}[2]{#1 #2}
+\foo{}
+     |"#,
+        expect![[r#"
+            Some(
+                (
+                    "\\foo{#1}{#2}",
+                    "{#1}",
+                    None,
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_user_command_second() {
+    check(
+        r#"
+%! main.tex
+\newcommand{\foo}[2]{#1 #2}
+\foo{bar}{baz}
+           |"#,
+        expect![[r#"
+            Some(
+                (
+                    "\\foo{#1}{#2}",
+                    "{#2}",
+                    None,
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_user_command_after_argument() {
+    check(
+        r#"
+%! main.tex
+\newcommand{\foo}[2]{#1 #2}
+\foo{bar}
+         |"#,
+        expect![[r#"
+            Some(
+                (
+                    "\\foo{#1}{#2}",
+                    "{#2}",
+                    None,
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_user_command_optional() {
+    check(
+        r#"
+%! main.tex
+\newcommand{\foo}[3][qux]{#1 #2 #3}
+\foo[bar]{baz}
+     |"#,
+        expect![[r#"
+            Some(
+                (
+                    "\\foo[#1]{#2}{#3}",
+                    "[#1]",
+                    Some(
+                        "Default: qux",
+                    ),
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_user_command_optional_omitted() {
+    check(
+        r#"
+%! main.tex
+\newcommand{\foo}[3][qux]{#1 #2 #3}
+\foo{bar}{baz}
+           |"#,
+        expect![[r#"
+            Some(
+                (
+                    "\\foo[#1]{#2}{#3}",
+                    "{#3}",
+                    None,
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_user_command_other_document() {
+    check(
+        r#"
+%! main.tex
+\include{defs}
+\foo{}
+     |
+
+%! defs.tex
+\newcommand{\foo}[1]{#1}"#,
+        expect![[r#"
+            Some(
+                (
+                    "\\foo{#1}",
+                    "{#1}",
+                    None,
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_user_command_without_arguments() {
+    check(
+        r#"
+%! main.tex
+\newcommand{\foo}{bar}
+\foo{}
+     |"#,
+        expect![[r#"
+            None
+        "#]],
+    );
+}
+
+#[test]
+fn test_nested_command() {
+    check(
+        r#"
+%! main.tex
+\newcommand{\foo}[2]{#1 #2}
+\foo{\textbf{bar}}
+             |"#,
+        expect![[r#"
+            Some(
+                (
+                    "\\foo{#1}{#2}",
+                    "{#1}",
+                    None,
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_package_command() {
+    check(
+        r#"
+%! main.tex
+\usepackage{amsfonts}
+\mathbb{}
+        |"#,
+        expect![[r#"
+            Some(
+                (
+                    "\\mathbb{#1}",
+                    "{#1}",
+                    Some(
+                        "One of: A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z",
+                    ),
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_user_environment() {
+    check(
+        r#"
+%! main.tex
+\newenvironment{foo}[2][bar]{#1 #2}{}
+\begin{foo}{baz}
+             |
+\end{foo}"#,
+        expect![[r#"
+            Some(
+                (
+                    "\\begin{foo}[#1]{#2}",
+                    "{#2}",
+                    None,
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_user_environment_optional() {
+    check(
+        r#"
+%! main.tex
+\newenvironment{foo}[2][bar]{#1 #2}{}
+\begin{foo}[qux]{baz}
+             |
+\end{foo}"#,
+        expect![[r#"
+            Some(
+                (
+                    "\\begin{foo}[#1]{#2}",
+                    "[#1]",
+                    Some(
+                        "Default: bar",
+                    ),
+                ),
+            )
+        "#]],
+    );
+}
+
+#[test]
+fn test_user_environment_body() {
+    check(
+        r#"
+%! main.tex
+\newenvironment{foo}[2][bar]{#1 #2}{}
+\begin{foo}{baz}
+Qux
+ |
+\end{foo}"#,
+        expect![[r#"
+            None
+        "#]],
+    );
+}
@@ -1,7 +1,10 @@
 mod bib;
 mod tex;
 
-use base_db::{deps::Project, util, Document, DocumentData, SymbolConfig, Workspace};
+use base_db::{
+    deps::{Project, ProjectRoot},
+    util, Document, DocumentData, SymbolConfig, Workspace,
+};
 
 use crate::Symbol;
 
@@ -9,7 +12,8 @@ pub fn document_symbols(workspace: &Workspace, document: &Document) -> Vec<Symbo
     let project = Project::from_child(workspace, document);
     let mut symbols = match &document.data {
         DocumentData::Tex(data) => {
-            let builder = tex::SymbolBuilder::new(&project, workspace.config());
+            let root = ProjectRoot::walk_and_find(workspace, &document.dir);
+            let builder = tex::SymbolBuilder::new(&project, &root.syntax);
             builder.visit(&data.root_node())
         }
         DocumentData::Bib(data) => {
@@ -21,9 +25,11 @@ pub fn document_symbols(workspace: &Workspace, document: &Document) -> Vec<Symbo
         }
         DocumentData::Aux(_)
         | DocumentData::Log(_)
-        | DocumentData::Root
+        | DocumentData::Fls(_)
+        | DocumentData::Blg(_)
+        | DocumentData::Root(_)
         | DocumentData::Latexmkrc(_)
-        | DocumentData::Tectonic => Vec::new(),
+        | DocumentData::Tectonic(_) => Vec::new(),
     };
 
     filter_symbols(&mut symbols, &workspace.config().symbols);
@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
-use base_db::{deps::Project, semantics::Span, util::FloatKind, Config};
+use base_db::{deps::Project, semantics::Span, util::FloatKind};
+use parser::SyntaxConfig;
 use rowan::ast::AstNode;
 use syntax::latex::{self, HasBrack, HasCurly, LatexLanguage};
 use titlecase::titlecase;
@@ -10,11 +11,11 @@ use crate::{Symbol, SymbolKind};
 #[derive(Debug)]
 pub struct SymbolBuilder<'a> {
     project: &'a Project<'a>,
-    config: &'a Config,
+    config: &'a SyntaxConfig,
 }
 
 impl<'a> SymbolBuilder<'a> {
-    pub fn new(project: &'a Project<'a>, config: &'a Config) -> Self {
+    pub fn new(project: &'a Project<'a>, config: &'a SyntaxConfig) -> Self {
         Self { project, config }
     }
 
@@ -28,9 +29,9 @@ impl<'a> SymbolBuilder<'a> {
         } else if let Some(environment) = latex::Environment::cast(node.clone()) {
             environment.begin().and_then(|begin| {
                 let name = begin.name()?.key()?.to_string();
-                if self.config.syntax.math_environments.contains(&name) {
+                if self.config.math_environments.contains(&name) {
                     self.visit_equation(&environment)
-                } else if self.config.syntax.enum_environments.contains(&name) {
+                } else if self.config.enum_environments.contains(&name) {
                     self.visit_enumeration(&environment, &name)
                 } else if let Ok(float_kind) = FloatKind::from_str(&name) {
                     self.visit_float(&environment, float_kind)
@@ -83,7 +84,7 @@ impl<'a> SymbolBuilder<'a> {
     }
 
     fn visit_enum_item(&self, enum_item: &latex::EnumItem) -> Option<Symbol> {
-        let enum_envs = &self.config.syntax.enum_environments;
+        let enum_envs = &self.config.enum_environments;
         if !enum_item
             .syntax()
             .ancestors()
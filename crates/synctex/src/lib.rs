@@ -0,0 +1,330 @@
+use std::{
+    io::Read,
+    path::{Component, Path, PathBuf},
+};
+
+use flate2::read::GzDecoder;
+use rustc_hash::FxHashMap;
+
+/// A rectangle on a page of the PDF file.
+///
+/// The coordinates are given in PostScript points relative to the top left corner of the page.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PdfBox {
+    /// The one-based page number.
+    pub page: u32,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SourceLocation {
+    pub path: PathBuf,
+    /// The zero-based line number.
+    pub line: u32,
+}
+
+/// The contents of a `.synctex` file written by TeX engines with `-synctex=1`.
+#[derive(Debug, Clone)]
+pub struct SyncTex {
+    inputs: FxHashMap<u32, PathBuf>,
+    records: Vec<Record>,
+    magnification: f64,
+    unit: f64,
+    x_offset: f64,
+    y_offset: f64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum RecordKind {
+    HBox,
+    VBox,
+    Point,
+}
+
+/// A node of the output with the source position that produced it.
+///
+/// All dimensions are given in scaled points.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Record {
+    kind: RecordKind,
+    page: u32,
+    tag: u32,
+    line: u32,
+    h: i64,
+    v: i64,
+    width: i64,
+    height: i64,
+    depth: i64,
+}
+
+impl Record {
+    fn left(&self) -> i64 {
+        self.h.min(self.h + self.width)
+    }
+
+    fn right(&self) -> i64 {
+        self.h.max(self.h + self.width)
+    }
+
+    fn top(&self) -> i64 {
+        self.v - self.height
+    }
+
+    fn bottom(&self) -> i64 {
+        self.v + self.depth
+    }
+
+    fn area(&self) -> i64 {
+        (self.right() - self.left()) * (self.bottom() - self.top())
+    }
+
+    fn contains(&self, h: i64, v: i64) -> bool {
+        (self.left()..=self.right()).contains(&h) && (self.top()..=self.bottom()).contains(&v)
+    }
+
+    fn distance(&self, h: i64, v: i64) -> i64 {
+        let dx = (self.left() - h).max(h - self.right()).max(0);
+        let dy = (self.top() - v).max(v - self.bottom()).max(0);
+        dx + dy
+    }
+}
+
+impl SyncTex {
+    /// Reads a `.synctex.gz` or an uncompressed `.synctex` file.
+    ///
+    /// Relative input paths are resolved against `base_dir`,
+    /// which should be the directory in which TeX was run.
+    pub fn read(path: &Path, base_dir: &Path) -> std::io::Result<Self> {
+        let mut bytes = std::fs::read(path)?;
+        if path.extension().is_some_and(|extension| extension == "gz") {
+            let mut buffer = Vec::new();
+            GzDecoder::new(bytes.as_slice()).read_to_end(&mut buffer)?;
+            bytes = buffer;
+        }
+
+        let text = String::from_utf8_lossy(&bytes);
+        Ok(Self::parse(&text, base_dir))
+    }
+
+    pub fn parse(text: &str, base_dir: &Path) -> Self {
+        let mut synctex = Self {
+            inputs: FxHashMap::default(),
+            records: Vec::new(),
+            magnification: 1000.0,
+            unit: 1.0,
+            x_offset: 0.0,
+            y_offset: 0.0,
+        };
+
+        let mut page = 0;
+        for line in text.lines() {
+            if let Some((key, value)) = line.split_once(':').filter(|(key, _)| {
+                matches!(
+                    *key,
+                    "Input" | "Magnification" | "Unit" | "X Offset" | "Y Offset"
+                )
+            }) {
+                synctex.parse_header(key, value, base_dir);
+                continue;
+            }
+
+            let mut chars = line.chars();
+            let kind = match chars.next() {
+                Some('{') => {
+                    page = chars.as_str().parse().unwrap_or(page);
+                    continue;
+                }
+                Some('(' | 'h') => RecordKind::HBox,
+                Some('[' | 'v') => RecordKind::VBox,
+                Some('k' | 'g' | '$' | 'x' | 'r') => RecordKind::Point,
+                _ => continue,
+            };
+
+            if let Some(record) = parse_record(kind, page, chars.as_str()) {
+                synctex.records.push(record);
+            }
+        }
+
+        synctex
+    }
+
+    fn parse_header(&mut self, key: &str, value: &str, base_dir: &Path) {
+        match key {
+            "Input" => {
+                if let Some((tag, path)) = value.split_once(':') {
+                    if let Ok(tag) = tag.parse() {
+                        self.inputs.insert(tag, normalize(&base_dir.join(path)));
+                    }
+                }
+            }
+            "Magnification" => {
+                self.magnification = value.trim().parse().unwrap_or(self.magnification);
+            }
+            "Unit" => {
+                self.unit = value.trim().parse().unwrap_or(self.unit);
+            }
+            "X Offset" => {
+                self.x_offset = value.trim().parse().unwrap_or(self.x_offset);
+            }
+            "Y Offset" => {
+                self.y_offset = value.trim().parse().unwrap_or(self.y_offset);
+            }
+            _ => {}
+        };
+    }
+
+    /// Finds the area of the PDF file that corresponds to the given source line.
+    ///
+    /// If the line did not produce any output, then the closest line that did is used instead.
+    pub fn forward(&self, path: &Path, line: u32) -> Option<PdfBox> {
+        let path = normalize(path);
+        let tags: Vec<u32> = self
+            .inputs
+            .iter()
+            .filter(|(_, input)| **input == path)
+            .map(|(tag, _)| *tag)
+            .collect();
+
+        let line = line + 1;
+        let records = self
+            .records
+            .iter()
+            .filter(|record| tags.contains(&record.tag));
+
+        let nearest = records
+            .clone()
+            .min_by_key(|record| (record.line.abs_diff(line), record.line < line))?;
+
+        let records: Vec<Record> = records
+            .filter(|record| record.line == nearest.line && record.page == nearest.page)
+            .copied()
+            .collect();
+
+        // Vertical boxes usually span the entire page so they are only used as a last resort.
+        let boxes = records
+            .iter()
+            .filter(|record| record.kind != RecordKind::VBox)
+            .copied()
+            .reduce(union)
+            .or_else(|| records.iter().copied().reduce(union))?;
+
+        let x = self.scaled_to_points(boxes.left(), self.x_offset);
+        let y = self.scaled_to_points(boxes.top(), self.y_offset);
+        Some(PdfBox {
+            page: boxes.page,
+            x,
+            y,
+            width: self.scaled_to_points(boxes.right(), self.x_offset) - x,
+            height: self.scaled_to_points(boxes.bottom(), self.y_offset) - y,
+        })
+    }
+
+    /// Finds the source location that produced the output at the given position.
+    ///
+    /// The position is given in PostScript points relative to the top left corner of the page.
+    pub fn inverse(&self, page: u32, x: f64, y: f64) -> Option<SourceLocation> {
+        let h = self.points_to_scaled(x, self.x_offset);
+        let v = self.points_to_scaled(y, self.y_offset);
+
+        let candidates = || {
+            self.records
+                .iter()
+                .filter(move |record| record.page == page && record.kind != RecordKind::VBox)
+        };
+
+        let container = candidates()
+            .filter(|record| record.kind == RecordKind::HBox && record.contains(h, v))
+            .min_by_key(|record| record.area());
+
+        // The nodes inside of a box point to the source more precisely than the box itself.
+        let record = match container {
+            Some(container) => candidates()
+                .filter(|record| *record != container && container.contains(record.h, record.v))
+                .min_by_key(|record| record.distance(h, v))
+                .unwrap_or(container),
+            None => candidates().min_by_key(|record| record.distance(h, v))?,
+        };
+
+        let path = self.inputs.get(&record.tag)?.clone();
+        let line = record.line.saturating_sub(1);
+        Some(SourceLocation { path, line })
+    }
+
+    fn scaled_to_points(&self, value: i64, offset: f64) -> f64 {
+        let scaled = value as f64 * self.unit * self.magnification / 1000.0 + offset;
+        scaled / 65536.0 * 72.0 / 72.27
+    }
+
+    fn points_to_scaled(&self, value: f64, offset: f64) -> i64 {
+        let scaled = value * 72.27 / 72.0 * 65536.0;
+        ((scaled - offset) / (self.unit * self.magnification / 1000.0)).round() as i64
+    }
+}
+
+/// Parses records like `12,34:h,v:W,H,D` where the dimensions are optional.
+fn parse_record(kind: RecordKind, page: u32, text: &str) -> Option<Record> {
+    let mut parts = text.split(':');
+
+    let mut link = parts.next()?.split(',');
+    let tag = link.next()?.parse().ok()?;
+    let line = link.next()?.parse().ok()?;
+
+    let mut point = parts.next()?.split(',');
+    let h = point.next()?.parse().ok()?;
+    let v = point.next()?.parse().ok()?;
+
+    let mut size = parts
+        .next()
+        .into_iter()
+        .flat_map(|size| size.split(','))
+        .map(|value| value.parse().unwrap_or(0));
+
+    Some(Record {
+        kind,
+        page,
+        tag,
+        line,
+        h,
+        v,
+        width: size.next().unwrap_or(0),
+        height: size.next().unwrap_or(0),
+        depth: size.next().unwrap_or(0),
+    })
+}
+
+fn union(a: Record, b: Record) -> Record {
+    let left = a.left().min(b.left());
+    let right = a.right().max(b.right());
+    let top = a.top().min(b.top());
+    let bottom = a.bottom().max(b.bottom());
+    Record {
+        h: left,
+        v: bottom,
+        width: right - left,
+        height: bottom - top,
+        depth: 0,
+        ..a
+    }
+}
+
+/// Removes `.` and resolves `..` components without touching the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                result.pop();
+            }
+            component => result.push(component),
+        };
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests;
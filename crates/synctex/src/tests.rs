@@ -0,0 +1,152 @@
+use std::path::Path;
+
+use expect_test::{expect, Expect};
+
+use crate::SyncTex;
+
+// Two lines of text on the first page and a figure caption on the second page.
+// The dimensions are chosen so that 65536sp correspond to 1pt.
+const SYNCTEX: &str = r#"SyncTeX Version:1
+Input:1:/project/./main.tex
+Input:2:/project/chapters/intro.tex
+Output:pdf
+Magnification:1000
+Unit:1
+X Offset:0
+Y Offset:0
+Content:
+!114
+{1
+[1,5:4736286,4736286:30785863,45427794,0
+(2,3:4736286,6553600:30785863,655360,131072
+g2,3:4736286,6553600
+x2,3:9830400,6553600
+k2,4:19660800,6553600:655360
+)
+(2,8:4736286,8192000:30785863,655360,131072
+x2,8:6553600,8192000
+)
+]
+}1
+{2
+[1,12:4736286,4736286:30785863,45427794,0
+(1,14:4736286,13107200:30785863,655360,131072
+x1,14:6553600,13107200
+)
+]
+}2
+Postamble:
+Count:14
+"#;
+
+fn check_forward(path: &str, line: u32, expect: Expect) {
+    let synctex = SyncTex::parse(SYNCTEX, Path::new("/project"));
+    expect.assert_debug_eq(&synctex.forward(Path::new(path), line));
+}
+
+fn check_inverse(page: u32, x: f64, y: f64, expect: Expect) {
+    let synctex = SyncTex::parse(SYNCTEX, Path::new("/project"));
+    expect.assert_debug_eq(&synctex.inverse(page, x, y));
+}
+
+#[test]
+fn test_forward() {
+    check_forward(
+        "/project/chapters/intro.tex",
+        2,
+        expect![[r#"
+        Some(
+            PdfBox {
+                page: 1,
+                x: 71.99998905471669,
+                y: 89.66376089663761,
+                width: 467.99998966278804,
+                height: 11.955168119551686,
+            },
+        )
+    "#]],
+    );
+}
+
+#[test]
+fn test_forward_nearest_line() {
+    check_forward(
+        "/project/chapters/intro.tex",
+        5,
+        expect![[r#"
+        Some(
+            PdfBox {
+                page: 1,
+                x: 71.99998905471669,
+                y: 114.57036114570361,
+                width: 467.99998966278804,
+                height: 11.955168119551686,
+            },
+        )
+    "#]],
+    );
+}
+
+#[test]
+fn test_forward_relative_input() {
+    check_forward(
+        "/project/main.tex",
+        13,
+        expect![[r#"
+        Some(
+            PdfBox {
+                page: 2,
+                x: 71.99998905471669,
+                y: 189.29016189290164,
+                width: 467.99998966278804,
+                height: 11.955168119551672,
+            },
+        )
+    "#]],
+    );
+}
+
+#[test]
+fn test_forward_unknown_file() {
+    check_forward(
+        "/project/other.tex",
+        0,
+        expect![[r#"
+        None
+    "#]],
+    );
+}
+
+#[test]
+fn test_inverse() {
+    check_inverse(
+        1,
+        140.0,
+        100.0,
+        expect![[r#"
+        Some(
+            SourceLocation {
+                path: "/project/chapters/intro.tex",
+                line: 2,
+            },
+        )
+    "#]],
+    );
+}
+
+#[test]
+fn test_inverse_outside_of_box() {
+    check_inverse(
+        2,
+        300.0,
+        400.0,
+        expect![[r#"
+        Some(
+            SourceLocation {
+                path: "/project/main.tex",
+                line: 13,
+            },
+        )
+    "#]],
+    );
+}
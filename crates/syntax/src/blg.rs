@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+
+use crate::BuildErrorLevel;
+
+/// The errors and warnings that BibTeX or Biber wrote to a `.blg` file.
+#[derive(Debug, Clone, Default)]
+pub struct BlgData {
+    pub errors: Vec<BlgError>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct BlgError {
+    pub level: BuildErrorLevel,
+    pub message: String,
+    /// The database file as it is referred to in the log.
+    pub file: Option<PathBuf>,
+    pub line: Option<u32>,
+    /// The key of the entry that caused the error.
+    pub entry: Option<String>,
+}
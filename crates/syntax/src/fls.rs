@@ -0,0 +1,8 @@
+use std::path::PathBuf;
+
+/// The files that were read and written during a TeX run as recorded by `-recorder`.
+#[derive(Debug, Clone, Default)]
+pub struct FlsData {
+    pub inputs: Vec<PathBuf>,
+    pub outputs: Vec<PathBuf>,
+}
@@ -619,17 +619,57 @@ impl NewCommandDefinition {
         self.syntax().children().find_map(CurlyGroupCommand::cast)
     }
 
+    pub fn arity(&self) -> Option<BrackGroupWord> {
+        self.syntax().children().find_map(BrackGroupWord::cast)
+    }
+
+    pub fn default_argument(&self) -> Option<BrackGroup> {
+        self.syntax().children().find_map(BrackGroup::cast)
+    }
+
     pub fn implementation(&self) -> Option<CurlyGroup> {
         self.syntax().children().find_map(CurlyGroup::cast)
     }
 }
 
+cst_node!(EnvironmentDefinition, ENVIRONMENT_DEFINITION);
+
+impl EnvironmentDefinition {
+    pub fn command(&self) -> Option<SyntaxToken> {
+        self.syntax().first_token()
+    }
+
+    pub fn name(&self) -> Option<CurlyGroupWord> {
+        self.syntax().children().find_map(CurlyGroupWord::cast)
+    }
+
+    pub fn arity(&self) -> Option<BrackGroupWord> {
+        self.syntax().children().find_map(BrackGroupWord::cast)
+    }
+
+    pub fn default_argument(&self) -> Option<BrackGroup> {
+        self.syntax().children().find_map(BrackGroup::cast)
+    }
+
+    pub fn begin_implementation(&self) -> Option<CurlyGroup> {
+        self.syntax().children().find_map(CurlyGroup::cast)
+    }
+
+    pub fn end_implementation(&self) -> Option<CurlyGroup> {
+        self.syntax().children().filter_map(CurlyGroup::cast).nth(1)
+    }
+}
+
 cst_node!(AcronymReference, ACRONYM_REFERENCE);
 
 impl AcronymReference {
     pub fn command(&self) -> Option<SyntaxToken> {
         self.syntax().first_token()
     }
+
+    pub fn name(&self) -> Option<CurlyGroupWord> {
+        self.syntax().children().find_map(CurlyGroupWord::cast)
+    }
 }
 
 cst_node!(AcronymDefinition, ACRONYM_DEFINITION);
@@ -642,6 +682,15 @@ impl AcronymDefinition {
     pub fn name(&self) -> Option<CurlyGroupWord> {
         self.syntax().children().find_map(CurlyGroupWord::cast)
     }
+
+    /// The short form given as `\acro{key}[short]{long}`.
+    pub fn short_option(&self) -> Option<BrackGroup> {
+        self.syntax().children().find_map(BrackGroup::cast)
+    }
+
+    pub fn arguments(&self) -> impl Iterator<Item = CurlyGroup> {
+        self.syntax().children().filter_map(CurlyGroup::cast)
+    }
 }
 
 cst_node!(AcronymDeclaration, ACRONYM_DECLARATION);
@@ -654,6 +703,10 @@ impl AcronymDeclaration {
     pub fn name(&self) -> Option<CurlyGroupWord> {
         self.syntax().children().find_map(CurlyGroupWord::cast)
     }
+
+    pub fn options(&self) -> Option<CurlyGroupKeyValue> {
+        self.syntax().children().find_map(CurlyGroupKeyValue::cast)
+    }
 }
 
 cst_node!(ColorDefinition, COLOR_DEFINITION);
@@ -725,6 +778,10 @@ impl GlossaryEntryDefinition {
     pub fn name(&self) -> Option<CurlyGroupWord> {
         self.syntax().children().find_map(CurlyGroupWord::cast)
     }
+
+    pub fn options(&self) -> Option<CurlyGroupKeyValue> {
+        self.syntax().children().find_map(CurlyGroupKeyValue::cast)
+    }
 }
 
 cst_node!(TikzLibraryImport, TIKZ_LIBRARY_IMPORT);
@@ -1,5 +1,10 @@
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct LatexmkrcData {
     pub aux_dir: Option<String>,
     pub out_dir: Option<String>,
+    pub jobname: Option<String>,
+    pub pdf_mode: Option<u8>,
+    pub pdflatex: Option<String>,
+    pub lualatex: Option<String>,
+    pub default_files: Vec<String>,
 }
@@ -1,6 +1,9 @@
 pub mod bibtex;
+pub mod blg;
+pub mod fls;
 pub mod latex;
 pub mod latexmkrc;
+pub mod tectonic;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
 pub enum BuildErrorLevel {
@@ -8,13 +11,28 @@ pub enum BuildErrorLevel {
     Warning,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum BuildErrorCategory {
+    UndefinedControlSequence,
+    MissingFile,
+    OverfullBox,
+    UnderfullBox,
+    UndefinedCitation,
+    UndefinedReference,
+    DuplicateLabel,
+    Other,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct BuildError {
     pub relative_path: std::path::PathBuf,
     pub level: BuildErrorLevel,
+    pub category: BuildErrorCategory,
     pub message: String,
+    pub help: Option<String>,
     pub hint: Option<String>,
     pub line: Option<u32>,
+    pub column: Option<u32>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
@@ -0,0 +1,48 @@
+/// The contents of a `Tectonic.toml` file.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct TectonicData {
+    pub name: Option<String>,
+    pub outputs: Vec<TectonicOutput>,
+}
+
+/// An `[[output]]` section of a Tectonic project.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TectonicOutput {
+    pub name: String,
+    pub kind: TectonicOutputKind,
+    pub tex_format: String,
+    pub shell_escape: bool,
+
+    /// The files in the `src` directory that are processed in this order.
+    pub inputs: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum TectonicOutputKind {
+    Pdf,
+    Html,
+}
+
+impl TectonicOutput {
+    /// Returns the name of the PDF file that is written to `build/<name>/`.
+    pub fn pdf_name(&self) -> Option<String> {
+        (self.kind == TectonicOutputKind::Pdf).then(|| format!("{}.pdf", self.name))
+    }
+}
+
+impl Default for TectonicOutput {
+    /// The output that `tectonic -X new` creates.
+    fn default() -> Self {
+        Self {
+            name: String::from("default"),
+            kind: TectonicOutputKind::Pdf,
+            tex_format: String::from("latex"),
+            shell_escape: false,
+            inputs: vec![
+                String::from("_preamble.tex"),
+                String::from("index.tex"),
+                String::from("_postamble.tex"),
+            ],
+        }
+    }
+}
@@ -0,0 +1,464 @@
+use line_index::{LineCol, LineIndex};
+use rowan::{ast::AstNode, NodeOrToken, TextRange, TextSize};
+use rustc_hash::FxHashSet;
+use syntax::latex;
+
+pub struct Options {
+    pub insert_spaces: bool,
+    pub tab_size: usize,
+    pub line_length: usize,
+    pub verbatim_environments: FxHashSet<String>,
+}
+
+impl Options {
+    fn indent(&self) -> String {
+        if self.insert_spaces {
+            " ".repeat(self.tab_size)
+        } else {
+            String::from("\t")
+        }
+    }
+
+    fn width(&self, text: &str) -> usize {
+        text.chars()
+            .map(|c| if c == '\t' { self.tab_size } else { 1 })
+            .sum()
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            insert_spaces: true,
+            tab_size: 4,
+            line_length: 80,
+            verbatim_environments: FxHashSet::default(),
+        }
+    }
+}
+
+/// Formats the entire document.
+pub fn format(root: &latex::SyntaxNode, options: &Options) -> String {
+    let lines = Formatter::new(options).run(root);
+    let mut output = render(&lines, options);
+
+    let text = root.text();
+    let ends_with_newline = u32::from(text.len()) > 0
+        && matches!(
+            text.char_at(text.len() - TextSize::from(1)),
+            Some('\n' | '\r')
+        );
+
+    if ends_with_newline && !output.is_empty() {
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Formats all lines that intersect the given range.
+///
+/// Returns the range of the original text that has to be replaced with the formatted text.
+pub fn format_range(
+    root: &latex::SyntaxNode,
+    line_index: &LineIndex,
+    range: TextRange,
+    options: &Options,
+) -> Option<(TextRange, String)> {
+    let lines = Formatter::new(options).run(root);
+    let line_start = |offset: TextSize| {
+        let line = line_index.line_col(offset).line;
+        line_index.offset(LineCol { line, col: 0 })
+    };
+
+    let line_end = |offset: TextSize| {
+        let line = line_index.line_col(offset).line;
+        line_index
+            .offset(LineCol {
+                line: line + 1,
+                col: 0,
+            })
+            .unwrap_or_else(|| root.text_range().end())
+    };
+
+    // Lines of the output do not necessarily map to lines of the input,
+    // so we extend the selection until it covers whole lines on both sides.
+    let mut range = range;
+    let selection = loop {
+        let start = line_start(range.start())?;
+        let end = line_end(range.end());
+        let selection: Vec<_> = lines
+            .iter()
+            .filter(|line| line.range.start() < end && line.range.end() > start)
+            .collect();
+
+        let new_range = selection
+            .iter()
+            .fold(range, |range, line| range.cover(line.range));
+
+        if new_range == range {
+            break selection;
+        }
+
+        range = new_range;
+    };
+
+    let start = line_start(selection.first()?.range.start())?;
+    let end = selection.last()?.range.end();
+    let output = render(selection, options);
+    Some((TextRange::new(start, end), output))
+}
+
+#[derive(Debug)]
+struct Line {
+    range: TextRange,
+    depth: usize,
+    hanging: usize,
+    pieces: Vec<Piece>,
+}
+
+#[derive(Debug)]
+enum Piece {
+    Text(String),
+    Space(String, bool),
+}
+
+struct Formatter<'a> {
+    options: &'a Options,
+    lines: Vec<Line>,
+    current: Option<Line>,
+    space: Option<(TextRange, String, bool)>,
+    depth: usize,
+    blank: Option<TextRange>,
+    suppress_blank: bool,
+    line_break: bool,
+}
+
+impl<'a> Formatter<'a> {
+    fn new(options: &'a Options) -> Self {
+        Self {
+            options,
+            lines: Vec::new(),
+            current: None,
+            space: None,
+            depth: 0,
+            blank: None,
+            suppress_blank: false,
+            line_break: false,
+        }
+    }
+
+    fn run(mut self, root: &latex::SyntaxNode) -> Vec<Line> {
+        self.visit_node(root.clone());
+        self.flush();
+        self.lines
+    }
+
+    fn push(&mut self, range: TextRange, text: &str) {
+        if std::mem::take(&mut self.line_break) {
+            self.flush();
+        }
+
+        let space = self.space.take();
+        if let Some(line) = &mut self.current {
+            if let Some((space_range, space_text, breakable)) = space {
+                line.pieces.push(Piece::Space(space_text, breakable));
+                line.range = line.range.cover(space_range);
+            }
+
+            line.pieces.push(Piece::Text(text.into()));
+            line.range = line.range.cover(range);
+            return;
+        }
+
+        let blank = self.blank.take();
+        if let Some(blank) = blank.filter(|_| !self.suppress_blank && !self.lines.is_empty()) {
+            self.lines.push(Line {
+                range: blank,
+                depth: 0,
+                hanging: 0,
+                pieces: Vec::new(),
+            });
+        }
+
+        self.suppress_blank = false;
+        self.current = Some(Line {
+            range,
+            depth: self.depth,
+            hanging: self.depth,
+            pieces: vec![Piece::Text(text.into())],
+        });
+    }
+
+    fn push_space(&mut self, range: TextRange, text: &str, breakable: bool) {
+        if self.current.is_none() || self.line_break {
+            return;
+        }
+
+        match &mut self.space {
+            Some((space_range, space_text, space_breakable)) => {
+                *space_range = space_range.cover(range);
+                space_text.push_str(text);
+                *space_breakable &= breakable;
+            }
+            None => self.space = Some((range, text.into(), breakable)),
+        }
+    }
+
+    fn flush(&mut self) {
+        self.line_break = false;
+        let space = self.space.take();
+        if let Some(mut line) = self.current.take() {
+            // Trailing whitespace gets removed but still belongs to the line.
+            if let Some((range, _, _)) = space {
+                line.range = line.range.cover(range);
+            }
+
+            self.lines.push(line);
+        }
+    }
+
+    fn visit(&mut self, element: latex::SyntaxElement) {
+        match element {
+            NodeOrToken::Node(node) => self.visit_node(node),
+            NodeOrToken::Token(token) => self.visit_token(token),
+        }
+    }
+
+    fn visit_node(&mut self, node: latex::SyntaxNode) {
+        match node.kind() {
+            latex::ENVIRONMENT => self.visit_environment(node),
+            latex::ENUM_ITEM => self.visit_enum_item(node),
+            _ => {
+                for child in node.children_with_tokens() {
+                    self.visit(child);
+                }
+            }
+        }
+    }
+
+    fn visit_token(&mut self, token: latex::SyntaxToken) {
+        match token.kind() {
+            latex::LINE_BREAK => {
+                let is_blank = self.current.is_none()
+                    || token.text().matches('\n').count() > 1
+                    || token.text().matches('\r').count() > 1;
+
+                self.flush();
+                if is_blank {
+                    self.blank = Some(token.text_range());
+                }
+            }
+            latex::WHITESPACE => {
+                // Keys (labels, paths, ...) must not be split across lines.
+                let breakable = !token
+                    .parent_ancestors()
+                    .any(|node| node.kind() == latex::KEY);
+
+                self.push_space(token.text_range(), token.text(), breakable);
+            }
+            _ => self.push(token.text_range(), token.text()),
+        }
+    }
+
+    fn visit_environment(&mut self, node: latex::SyntaxNode) {
+        let name = latex::Environment::cast(node.clone())
+            .and_then(|env| env.begin())
+            .and_then(|begin| begin.name())
+            .and_then(|name| name.key())
+            .map(|name| name.to_string())
+            .unwrap_or_default();
+
+        let is_block = self.current.is_none() || self.line_break;
+        if self.options.verbatim_environments.contains(&name) {
+            self.visit_verbatim_environment(node, is_block);
+            return;
+        }
+
+        let depth = self.depth;
+        let body_depth = if is_block && name != "document" {
+            depth + 1
+        } else {
+            depth
+        };
+
+        let mut is_header = false;
+        for child in node.children_with_tokens() {
+            match child {
+                NodeOrToken::Node(child) if child.kind() == latex::BEGIN => {
+                    self.visit_header(child, body_depth);
+                    self.suppress_blank = is_block;
+                    is_header = is_block;
+                }
+                NodeOrToken::Node(child) if child.kind() == latex::END => {
+                    self.depth = depth;
+                    if is_block {
+                        self.blank = None;
+                        self.line_break = true;
+                    }
+
+                    self.visit_header(child, depth);
+                    self.line_break |= is_block;
+                }
+                // Arguments like in `\begin{tabular}{ll}` stay on the same line.
+                NodeOrToken::Node(child)
+                    if is_header
+                        && self.current.is_some()
+                        && matches!(
+                            child.kind(),
+                            latex::CURLY_GROUP | latex::BRACK_GROUP | latex::MIXED_GROUP
+                        ) =>
+                {
+                    self.visit_node(child);
+                }
+                child => {
+                    self.line_break |= std::mem::take(&mut is_header);
+                    self.visit(child);
+                }
+            };
+        }
+
+        self.depth = depth;
+    }
+
+    /// Removes the whitespace between `\begin` or `\end` and the environment name.
+    ///
+    /// The new depth applies to the lines after the header.
+    fn visit_header(&mut self, node: latex::SyntaxNode, depth: usize) {
+        let has_comment = node
+            .children_with_tokens()
+            .any(|child| child.kind() == latex::COMMENT);
+
+        let tokens: Vec<_> = node
+            .descendants_with_tokens()
+            .filter_map(|element| element.into_token())
+            .collect();
+
+        let last = tokens.iter().rposition(|token| !is_trivia(token.kind()));
+        for (i, token) in tokens.into_iter().enumerate() {
+            let is_normalized = !has_comment
+                && token.parent().as_ref() == Some(&node)
+                && matches!(token.kind(), latex::WHITESPACE | latex::LINE_BREAK);
+
+            if !is_normalized {
+                self.visit_token(token);
+            }
+
+            if Some(i) == last {
+                self.depth = depth;
+            }
+        }
+
+        self.depth = depth;
+    }
+
+    fn visit_verbatim_environment(&mut self, node: latex::SyntaxNode, is_block: bool) {
+        let tokens: Vec<_> = node
+            .descendants_with_tokens()
+            .filter_map(|element| element.into_token())
+            .collect();
+
+        let Some(last) = tokens.iter().rposition(|token| !is_trivia(token.kind())) else {
+            return;
+        };
+
+        let range = TextRange::new(
+            tokens[0].text_range().start(),
+            tokens[last].text_range().end(),
+        );
+
+        let text: String = tokens[..=last].iter().map(|token| token.text()).collect();
+        self.line_break |= is_block;
+        self.push(range, &text);
+        self.line_break |= is_block;
+
+        for token in tokens.into_iter().skip(last + 1) {
+            self.visit_token(token);
+        }
+    }
+
+    fn visit_enum_item(&mut self, node: latex::SyntaxNode) {
+        self.line_break = true;
+        let mut children = node.children_with_tokens();
+        if let Some(command) = children.next() {
+            self.visit(command);
+        }
+
+        self.depth += 1;
+        if let Some(line) = &mut self.current {
+            line.hanging = self.depth;
+        }
+
+        for child in children {
+            self.visit(child);
+        }
+
+        self.depth -= 1;
+    }
+}
+
+fn is_trivia(kind: latex::SyntaxKind) -> bool {
+    matches!(kind, latex::WHITESPACE | latex::LINE_BREAK | latex::COMMENT)
+}
+
+fn render<'a>(lines: impl IntoIterator<Item = &'a Line>, options: &Options) -> String {
+    let indent = options.indent();
+    let mut output = String::new();
+    for (i, line) in lines.into_iter().enumerate() {
+        if i > 0 {
+            output.push('\n');
+        }
+
+        if line.pieces.is_empty() {
+            continue;
+        }
+
+        output.push_str(&indent.repeat(line.depth));
+        let mut column = options.width(&indent) * line.depth;
+        for (j, piece) in line.pieces.iter().enumerate() {
+            match piece {
+                Piece::Text(text) => {
+                    output.push_str(text);
+                    column = match text.rfind('\n') {
+                        Some(k) => options.width(&text[k + 1..]),
+                        None => column + options.width(text),
+                    };
+                }
+                Piece::Space(text, breakable) => {
+                    let width = options.width(text);
+                    let next_width = word_width(&line.pieces[j + 1..], options);
+                    if *breakable && column + width + next_width > options.line_length {
+                        output.push('\n');
+                        output.push_str(&indent.repeat(line.hanging));
+                        column = options.width(&indent) * line.hanging;
+                    } else {
+                        output.push_str(text);
+                        column += width;
+                    }
+                }
+            }
+        }
+    }
+
+    output
+}
+
+/// Computes the width of the pieces up to the next possible line break.
+fn word_width(pieces: &[Piece], options: &Options) -> usize {
+    let mut width = 0;
+    for piece in pieces {
+        match piece {
+            Piece::Text(text) => match text.find('\n') {
+                Some(i) => return width + options.width(&text[..i]),
+                None => width += options.width(text),
+            },
+            Piece::Space(_, true) => break,
+            Piece::Space(text, false) => width += options.width(text),
+        }
+    }
+
+    width
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,245 @@
+use expect_test::{expect, Expect};
+use line_index::LineIndex;
+use rowan::{TextRange, TextSize};
+use syntax::latex;
+
+use crate::Options;
+
+fn options() -> Options {
+    let config = parser::SyntaxConfig::default();
+    Options {
+        line_length: 40,
+        tab_size: 2,
+        verbatim_environments: config.verbatim_environments,
+        ..Options::default()
+    }
+}
+
+fn check(input: &str, expect: Expect) {
+    let config = parser::SyntaxConfig::default();
+    let root = latex::SyntaxNode::new_root(parser::parse_latex(input, &config));
+    let output = crate::format(&root, &options());
+    expect.assert_eq(&output);
+}
+
+/// The range is given by the lines between the markers `|`.
+fn check_range(input: &str, expect: Expect) {
+    let start = input.find('|').unwrap();
+    let end = input.rfind('|').unwrap() - 1;
+    let input = input.replace('|', "");
+    let range = TextRange::new(TextSize::from(start as u32), TextSize::from(end as u32));
+
+    let config = parser::SyntaxConfig::default();
+    let root = latex::SyntaxNode::new_root(parser::parse_latex(&input, &config));
+    let line_index = LineIndex::new(&input);
+    let (range, text) = crate::format_range(&root, &line_index, range, &options()).unwrap();
+
+    let mut output = input.clone();
+    output.replace_range(std::ops::Range::<usize>::from(range), &text);
+    expect.assert_eq(&output);
+}
+
+#[test]
+fn test_environment() {
+    check(
+        r#"\begin{figure}
+\centering
+      \includegraphics{foo}
+\end{figure}
+"#,
+        expect![[r#"
+            \begin{figure}
+              \centering
+              \includegraphics{foo}
+            \end{figure}
+        "#]],
+    );
+}
+
+#[test]
+fn test_nested_environments() {
+    check(
+        r#"\begin{document}
+\begin{center}
+\begin{tabular}{ll}
+a & b \\
+\end{tabular}
+\end{center}
+\end{document}"#,
+        expect![[r#"
+            \begin{document}
+            \begin{center}
+              \begin{tabular}{ll}
+                a & b \\
+              \end{tabular}
+            \end{center}
+            \end{document}"#]],
+    );
+}
+
+#[test]
+fn test_normalize_begin_end() {
+    check(
+        r#"Foo
+\begin {center} Bar
+
+\end  {center} Baz"#,
+        expect![[r#"
+            Foo
+            \begin{center}
+              Bar
+            \end{center}
+            Baz"#]],
+    );
+}
+
+#[test]
+fn test_inline_environment() {
+    check(
+        r#"Foo \begin{math}x\end{math} bar"#,
+        expect![[r#"Foo \begin{math}x\end{math} bar"#]],
+    );
+}
+
+#[test]
+fn test_items() {
+    check(
+        r#"\begin{itemize}
+\item Lorem ipsum dolor sit amet, consectetur adipiscing elit.
+\item Foo \item Bar
+\end{itemize}"#,
+        expect![[r#"
+            \begin{itemize}
+              \item Lorem ipsum dolor sit amet,
+                consectetur adipiscing elit.
+              \item Foo
+              \item Bar
+            \end{itemize}"#]],
+    );
+}
+
+#[test]
+fn test_wrap_paragraph() {
+    check(
+        r#"Lorem ipsum dolor sit amet, consectetur adipiscing elit. \textbf{Sed do} eiusmod tempor.
+
+Short line.
+Another short line."#,
+        expect![[r#"
+            Lorem ipsum dolor sit amet, consectetur
+            adipiscing elit. \textbf{Sed do} eiusmod
+            tempor.
+
+            Short line.
+            Another short line."#]],
+    );
+}
+
+#[test]
+fn test_wrap_keys() {
+    check(
+        r#"Lorem ipsum dolor sit amet, see \ref{foo bar baz}."#,
+        expect![[r#"
+            Lorem ipsum dolor sit amet, see
+            \ref{foo bar baz}."#]],
+    );
+}
+
+#[test]
+fn test_comments() {
+    check(
+        r#"\begin{center} % Foo
+    % Bar
+Baz%
+\end{center}"#,
+        expect![[r#"
+            \begin{center} % Foo
+              % Bar
+              Baz%
+            \end{center}"#]],
+    );
+}
+
+#[test]
+fn test_verbatim_environment() {
+    check(
+        r#"\begin{itemize}
+\item Foo
+\begin{verbatim}
+  Lorem ipsum dolor sit amet, consectetur adipiscing elit.
+\end{verbatim}
+\end{itemize}"#,
+        expect![[r#"
+            \begin{itemize}
+              \item Foo
+                \begin{verbatim}
+              Lorem ipsum dolor sit amet, consectetur adipiscing elit.
+            \end{verbatim}
+            \end{itemize}"#]],
+    );
+}
+
+#[test]
+fn test_verbatim_command() {
+    check(
+        r#"Lorem ipsum dolor sit amet, consectetur \verb|adipiscing elit, sed do|"#,
+        expect![[r#"
+            Lorem ipsum dolor sit amet, consectetur
+            \verb|adipiscing elit, sed do|"#]],
+    );
+}
+
+#[test]
+fn test_blank_lines() {
+    check(
+        r#"
+
+Foo
+
+
+
+Bar
+
+"#,
+        expect![[r#"
+            Foo
+
+            Bar
+        "#]],
+    );
+}
+
+#[test]
+fn test_range() {
+    check_range(
+        r#"\begin{itemize}
+\item Foo
+|\item Bar   \item Baz|
+\item Qux
+\end{itemize}"#,
+        expect![[r#"
+            \begin{itemize}
+            \item Foo
+              \item Bar
+              \item Baz
+            \item Qux
+            \end{itemize}"#]],
+    );
+}
+
+#[test]
+fn test_range_verbatim() {
+    check_range(
+        r#"\begin{center}
+\begin{verbatim}
+|  Foo|
+\end{verbatim}
+\end{center}"#,
+        expect![[r#"
+            \begin{center}
+              \begin{verbatim}
+              Foo
+            \end{verbatim}
+            \end{center}"#]],
+    );
+}
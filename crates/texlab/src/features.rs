@@ -1,3 +1,4 @@
+pub mod code_action;
 pub mod completion;
 pub mod definition;
 pub mod folding;
@@ -8,4 +9,7 @@ pub mod inlay_hint;
 pub mod link;
 pub mod reference;
 pub mod rename;
+pub mod semantic_tokens;
+pub mod signature_help;
 pub mod symbols;
+pub mod synctex;
@@ -0,0 +1,18 @@
+use base_db::Workspace;
+
+use crate::util::{from_proto, to_proto};
+
+pub fn find_all(
+    workspace: &Workspace,
+    params: lsp_types::CodeActionParams,
+) -> Option<Vec<lsp_types::CodeActionOrCommand>> {
+    let params = from_proto::code_action_params(workspace, params)?;
+    let document = params.feature.document;
+    let actions = code_actions::find_all(&params)
+        .into_iter()
+        .filter_map(|action| to_proto::code_action(workspace, document, action))
+        .map(lsp_types::CodeActionOrCommand::CodeAction)
+        .collect();
+
+    Some(actions)
+}
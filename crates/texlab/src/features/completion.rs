@@ -1,11 +1,14 @@
-use base_db::{util::RenderedObject, MatchingAlgo, Workspace};
+use base_db::{deps::Project, util::RenderedObject, MatchingAlgo, Workspace};
 use completion::{ArgumentData, CompletionItem, CompletionItemData, EntryTypeData, FieldTypeData};
 use line_index::LineIndex;
 use rowan::ast::AstNode;
 use serde::{Deserialize, Serialize};
 use syntax::bibtex;
 
-use crate::util::{from_proto, line_index_ext::LineIndexExt, lsp_enums::Structure, ClientFlags};
+use crate::util::{
+    from_proto, line_index_ext::LineIndexExt, lsp_enums::Structure, to_proto,
+    to_proto::format_package_files, ClientFlags,
+};
 
 pub fn complete(
     workspace: &Workspace,
@@ -44,7 +47,11 @@ pub fn complete(
     })
 }
 
-pub fn resolve(workspace: &Workspace, item: &mut lsp_types::CompletionItem) -> Option<()> {
+pub fn resolve(
+    workspace: &Workspace,
+    item: &mut lsp_types::CompletionItem,
+    client_flags: &ClientFlags,
+) -> Option<()> {
     let data = from_proto::completion_resolve_info(item)?;
     match data {
         ResolveInfo::Package | ResolveInfo::DocumentClass => {
@@ -58,13 +65,24 @@ pub fn resolve(workspace: &Workspace, item: &mut lsp_types::CompletionItem) -> O
             ));
         }
         ResolveInfo::Citation { uri, key } => {
-            let data = workspace.lookup(&uri)?.data.as_bib()?;
+            let document = workspace.lookup(&uri)?;
+            let data = document.data.as_bib()?;
             let root = bibtex::Root::cast(data.root_node())?;
             let entry = root.find_entry(&key)?;
-            let value = citeproc::render(&entry, &data.semantics)?;
+            let project = Project::from_child(workspace, document);
+            let style = citeproc::find_style(workspace, document);
+            let markdown = client_flags.completion_markdown;
+            let value = citeproc::render(
+                &entry,
+                &data.semantics,
+                &project,
+                style.as_deref(),
+                from_proto::citation_format(markdown),
+            )?;
+
             item.documentation = Some(lsp_types::Documentation::MarkupContent(
                 lsp_types::MarkupContent {
-                    kind: lsp_types::MarkupKind::Markdown,
+                    kind: to_proto::markup_kind(markdown),
                     value,
                 },
             ));
@@ -440,11 +458,3 @@ pub enum ResolveInfo {
     Package,
     DocumentClass,
 }
-
-fn format_package_files(file_names: &[&str]) -> String {
-    if file_names.is_empty() {
-        "built-in".into()
-    } else {
-        file_names.join(", ")
-    }
-}
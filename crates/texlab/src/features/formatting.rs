@@ -1,10 +1,15 @@
 mod bibtex_internal;
+mod latex_internal;
 mod latexindent;
 
 use base_db::{Formatter, Workspace};
 use distro::Language;
 
-use self::{bibtex_internal::format_bibtex_internal, latexindent::format_with_latexindent};
+use self::{
+    bibtex_internal::format_bibtex_internal,
+    latex_internal::{format_latex_internal, format_latex_internal_range},
+    latexindent::format_with_latexindent,
+};
 
 pub fn format_source_code(
     workspace: &Workspace,
@@ -15,7 +20,7 @@ pub fn format_source_code(
     match document.language {
         Language::Tex => match workspace.config().formatting.tex_formatter {
             Formatter::Null => None,
-            Formatter::Server => None,
+            Formatter::Server => format_latex_internal(workspace, document, options),
             Formatter::LatexIndent => format_with_latexindent(workspace, document),
         },
         Language::Bib => match workspace.config().formatting.bib_formatter {
@@ -25,8 +30,29 @@ pub fn format_source_code(
         },
         Language::Aux
         | Language::Log
+        | Language::Fls
+        | Language::Blg
         | Language::Root
         | Language::Latexmkrc
         | Language::Tectonic => None,
     }
 }
+
+/// Only the built-in LaTeX formatter supports formatting a part of a document.
+pub fn format_source_code_range(
+    workspace: &Workspace,
+    uri: &lsp_types::Url,
+    range: lsp_types::Range,
+    options: &lsp_types::FormattingOptions,
+) -> Option<Vec<lsp_types::TextEdit>> {
+    let document = workspace.lookup(uri)?;
+    match (
+        document.language,
+        &workspace.config().formatting.tex_formatter,
+    ) {
+        (Language::Tex, Formatter::Server) => {
+            format_latex_internal_range(workspace, document, range, options)
+        }
+        _ => None,
+    }
+}
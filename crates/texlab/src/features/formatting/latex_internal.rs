@@ -0,0 +1,92 @@
+use base_db::{deps::ProjectRoot, Document, Workspace};
+use rowan::TextLen;
+
+use crate::util::line_index_ext::LineIndexExt;
+
+pub fn format_latex_internal(
+    workspace: &Workspace,
+    document: &Document,
+    options: &lsp_types::FormattingOptions,
+) -> Option<Vec<lsp_types::TextEdit>> {
+    let data = document.data.as_tex()?;
+    let options = texfmt_options(workspace, document, options);
+    let output = texfmt::format(&data.root_node(), &options);
+    let end = document.line_index.line_col_lsp(document.text.text_len())?;
+    let range = lsp_types::Range::new(lsp_types::Position::new(0, 0), end);
+    Some(vec![lsp_types::TextEdit::new(range, output)])
+}
+
+pub fn format_latex_internal_range(
+    workspace: &Workspace,
+    document: &Document,
+    range: lsp_types::Range,
+    options: &lsp_types::FormattingOptions,
+) -> Option<Vec<lsp_types::TextEdit>> {
+    let data = document.data.as_tex()?;
+    let options = texfmt_options(workspace, document, options);
+    let line_index = &document.line_index;
+    let range = line_index.offset_lsp_range(range)?;
+    let (range, output) = texfmt::format_range(&data.root_node(), line_index, range, &options)?;
+    let start = line_index.line_col_lsp(range.start())?;
+    let end = line_index.line_col_lsp(range.end())?;
+    let range = lsp_types::Range::new(start, end);
+    Some(vec![lsp_types::TextEdit::new(range, output)])
+}
+
+fn texfmt_options(
+    workspace: &Workspace,
+    document: &Document,
+    options: &lsp_types::FormattingOptions,
+) -> texfmt::Options {
+    let config = workspace.config();
+    let root = ProjectRoot::walk_and_find(workspace, &document.dir);
+    texfmt::Options {
+        insert_spaces: options.insert_spaces,
+        line_length: config.formatting.line_length,
+        tab_size: options.tab_size as usize,
+        verbatim_environments: root.syntax.verbatim_environments,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base_db::{Owner, Workspace};
+    use distro::Language;
+    use line_index::LineCol;
+    use lsp_types::{FormattingOptions, Url};
+
+    #[test]
+    fn test_project_verbatim_environment() {
+        let mut workspace = Workspace::default();
+        let open = |workspace: &mut Workspace, name: &str, text: &str, language| {
+            let uri = Url::parse(&format!("file:///texlab/{name}")).unwrap();
+            workspace.open(
+                uri.clone(),
+                text.into(),
+                language,
+                Owner::Client,
+                LineCol { line: 0, col: 0 },
+            );
+            uri
+        };
+
+        open(
+            &mut workspace,
+            "texlab.toml",
+            "[experimental]\nverbatimEnvironments = [\"myverb\"]\n",
+            Language::Root,
+        );
+
+        let text = "\\begin{myverb}\n   x\n\\end{myverb}\n";
+        let uri = open(&mut workspace, "main.tex", text, Language::Tex);
+        let document = workspace.lookup(&uri).unwrap();
+        let options = FormattingOptions {
+            tab_size: 2,
+            insert_spaces: true,
+            ..FormattingOptions::default()
+        };
+
+        let edits = super::format_latex_internal(&workspace, document, &options).unwrap();
+        assert_eq!(edits[0].new_text, text);
+    }
+}
@@ -0,0 +1,46 @@
+use base_db::Workspace;
+use lsp_types::{SemanticTokenModifier, SemanticTokenType, SemanticTokensLegend};
+
+use crate::util::{from_proto, to_proto};
+
+/// The order of the token types has to match `to_proto::semantic_token_type`
+/// and the order of the modifiers has to match `semantic_tokens::TokenModifiers`.
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: vec![
+            SemanticTokenType::MACRO,
+            SemanticTokenType::CLASS,
+            SemanticTokenType::VARIABLE,
+            SemanticTokenType::ENUM_MEMBER,
+            SemanticTokenType::NUMBER,
+            SemanticTokenType::COMMENT,
+            SemanticTokenType::STRING,
+            SemanticTokenType::KEYWORD,
+            SemanticTokenType::PROPERTY,
+            SemanticTokenType::PARAMETER,
+        ],
+        token_modifiers: vec![
+            SemanticTokenModifier::DECLARATION,
+            SemanticTokenModifier::new("undefined"),
+            SemanticTokenModifier::new("unused"),
+        ],
+    }
+}
+
+pub fn find_all(
+    workspace: &Workspace,
+    params: lsp_types::SemanticTokensParams,
+) -> Option<lsp_types::SemanticTokens> {
+    let params = from_proto::semantic_tokens_full_params(workspace, params)?;
+    let tokens = semantic_tokens::find_all(&params);
+    to_proto::semantic_tokens(params.feature.document, tokens)
+}
+
+pub fn find_in_range(
+    workspace: &Workspace,
+    params: lsp_types::SemanticTokensRangeParams,
+) -> Option<lsp_types::SemanticTokens> {
+    let params = from_proto::semantic_tokens_range_params(workspace, params)?;
+    let tokens = semantic_tokens::find_all(&params);
+    to_proto::semantic_tokens(params.feature.document, tokens)
+}
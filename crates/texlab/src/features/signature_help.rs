@@ -0,0 +1,12 @@
+use base_db::Workspace;
+
+use crate::util::{from_proto, to_proto};
+
+pub fn find(
+    workspace: &Workspace,
+    params: lsp_types::SignatureHelpParams,
+) -> Option<lsp_types::SignatureHelp> {
+    let params = from_proto::signature_help_params(workspace, params)?;
+    let help = ::signature_help::find(&params)?;
+    Some(to_proto::signature_help(help))
+}
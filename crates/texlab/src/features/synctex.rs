@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+
+use base_db::{deps, deps::ProjectRoot, Document, Workspace};
+use commands::ForwardSearch;
+use synctex::{PdfBox, SyncTex};
+
+use crate::util::normalize_uri;
+
+/// Finds the area of the PDF file that corresponds to the given position.
+///
+/// Returns the path of the PDF file and the area on the page.
+pub fn forward(
+    workspace: &Workspace,
+    params: lsp_types::TextDocumentPositionParams,
+) -> Option<(PathBuf, PdfBox)> {
+    let child = workspace.lookup(&params.text_document.uri)?;
+    let parent = deps::root(workspace, child);
+
+    let pdf_path = ForwardSearch::find_pdf(workspace, parent).ok()?;
+    let synctex = read_synctex(workspace, parent, &pdf_path)?;
+    let pdf_box = synctex.forward(child.path.as_deref()?, params.position.line)?;
+    Some((pdf_path, pdf_box))
+}
+
+/// Finds the source location that produced the output at the given position of the PDF file.
+pub fn inverse(
+    workspace: &Workspace,
+    pdf_path: &Path,
+    page: u32,
+    x: f64,
+    y: f64,
+) -> Option<lsp_types::Location> {
+    let root = workspace
+        .iter()
+        .filter(|document| {
+            document
+                .data
+                .as_tex()
+                .is_some_and(|data| data.semantics.can_be_root)
+        })
+        .find(|document| {
+            ForwardSearch::find_pdf(workspace, document).is_ok_and(|path| path == pdf_path)
+        });
+
+    let synctex = match root {
+        Some(root) => read_synctex(workspace, root, pdf_path)?,
+        None => {
+            let dir = pdf_path.parent()?;
+            let path = find_synctex_file(dir, pdf_path.file_stem()?.to_str()?)?;
+            SyncTex::read(&path, dir).ok()?
+        }
+    };
+
+    let location = synctex.inverse(page, x, y)?;
+    let mut uri = lsp_types::Url::from_file_path(&location.path).ok()?;
+    normalize_uri(&mut uri);
+
+    let position = lsp_types::Position::new(location.line, 0);
+    let range = lsp_types::Range::new(position, position);
+    Some(lsp_types::Location::new(uri, range))
+}
+
+/// Reads the SyncTeX file that was written alongside the PDF file of the given root document.
+fn read_synctex(workspace: &Workspace, root: &Document, pdf_path: &Path) -> Option<SyncTex> {
+    let project_root = ProjectRoot::walk_and_find(workspace, &root.dir);
+    let compile_dir = project_root.compile_dir.to_file_path().ok()?;
+    let stem = pdf_path.file_stem()?.to_str()?;
+
+    let path = pdf_path
+        .parent()
+        .and_then(|dir| find_synctex_file(dir, stem))
+        .or_else(|| {
+            let aux_dir = project_root.aux_dir.to_file_path().ok()?;
+            find_synctex_file(&aux_dir, stem)
+        })?;
+
+    log::debug!("[SyncTeX] Reading {}", path.display());
+    SyncTex::read(&path, &compile_dir)
+        .map_err(|why| log::warn!("[SyncTeX] Unable to read {}: {why}", path.display()))
+        .ok()
+}
+
+fn find_synctex_file(dir: &Path, stem: &str) -> Option<PathBuf> {
+    [".synctex.gz", ".synctex"]
+        .into_iter()
+        .map(|extension| dir.join(format!("{stem}{extension}")))
+        .find(|path| path.exists())
+}
@@ -1,3 +1,5 @@
+mod builds;
+mod continuous;
 mod dispatch;
 mod extensions;
 pub mod options;
@@ -11,7 +13,7 @@ use std::{
 };
 
 use anyhow::Result;
-use base_db::{deps, Owner, Workspace};
+use base_db::{deps, BuildConfig, Owner, Workspace};
 use commands::{BuildCommand, CleanCommand, CleanTarget, ForwardSearch};
 use crossbeam_channel::{Receiver, Sender};
 use distro::{Distro, Language};
@@ -20,7 +22,7 @@ use lsp_server::{Connection, ErrorCode, Message, RequestId};
 use lsp_types::{notification::*, request::*, *};
 use notify::event::ModifyKind;
 use notify_debouncer_full::{DebouncedEvent, Debouncer, FileIdMap};
-use parking_lot::{Mutex, RwLock};
+use parking_lot::RwLock;
 use rustc_hash::FxHashSet;
 use serde::{de::DeserializeOwned, Serialize};
 use threadpool::ThreadPool;
@@ -28,16 +30,20 @@ use threadpool::ThreadPool;
 use crate::{
     client::LspClient,
     features::{
-        completion, definition, folding, formatting, highlight, hover, inlay_hint, link, reference,
-        rename, symbols,
+        code_action, completion, definition, folding, formatting, highlight, hover, inlay_hint,
+        link, reference, rename, semantic_tokens, signature_help, symbols, synctex,
     },
     util::{from_proto, line_index_ext::LineIndexExt, normalize_uri, to_proto, ClientFlags},
 };
 
 use self::{
+    builds::{BuildJob, BuildListener, BuildManager, BuildOutput},
+    continuous::ContinuousBuilds,
     extensions::{
-        BuildParams, BuildRequest, BuildResult, BuildStatus, EnvironmentLocation,
-        ForwardSearchRequest, ForwardSearchResult, ForwardSearchStatus, TextWithRange,
+        BuildParams, BuildRequest, BuildResult, BuildStatus, BuildStatusParams, BuildStatusRequest,
+        EnvironmentLocation, ForwardSearchRequest, ForwardSearchResult, ForwardSearchStatus,
+        SynctexForwardRequest, SynctexForwardResult, SynctexInverseParams, SynctexInverseRequest,
+        TextWithRange,
     },
     options::{Options, StartupOptions},
     progress::ProgressReporter,
@@ -52,6 +58,8 @@ enum InternalMessage {
     ChktexFinished(Url, Vec<diagnostics::Diagnostic>),
     ForwardSearch(Url, Option<Position>),
     InverseSearch(TextDocumentPositionParams),
+    BuildPassFinished(Url, Option<PathBuf>),
+    BuildOutput(Url, Option<PathBuf>, Vec<syntax::BuildError>),
 }
 
 pub struct Server {
@@ -64,7 +72,8 @@ pub struct Server {
     diagnostic_manager: diagnostics::Manager,
     watcher: FileWatcher,
     pool: ThreadPool,
-    pending_builds: Arc<Mutex<FxHashSet<u32>>>,
+    builds: BuildManager,
+    continuous: ContinuousBuilds,
 }
 
 impl Server {
@@ -111,7 +120,8 @@ impl Server {
             diagnostic_manager: diagnostics::Manager::default(),
             watcher,
             pool: threadpool::Builder::new().build(),
-            pending_builds: Default::default(),
+            builds: BuildManager::default(),
+            continuous: ContinuousBuilds::default(),
         };
 
         let options = serde_json::from_value(params.initialization_options.unwrap_or_default())
@@ -154,6 +164,11 @@ impl Server {
                 ]),
                 ..CompletionOptions::default()
             }),
+            signature_help_provider: Some(SignatureHelpOptions {
+                trigger_characters: Some(vec!["{".into(), "[".into()]),
+                retrigger_characters: Some(vec!["}".into(), "]".into()]),
+                work_done_progress_options: WorkDoneProgressOptions::default(),
+            }),
             document_symbol_provider: Some(OneOf::Left(true)),
             workspace_symbol_provider: Some(OneOf::Left(true)),
             rename_provider: Some(OneOf::Right(RenameOptions {
@@ -162,6 +177,7 @@ impl Server {
             })),
             document_highlight_provider: Some(OneOf::Left(true)),
             document_formatting_provider: Some(OneOf::Left(true)),
+            document_range_formatting_provider: Some(OneOf::Left(true)),
             execute_command_provider: Some(ExecuteCommandOptions {
                 commands: vec![
                     "texlab.cleanAuxiliary".into(),
@@ -170,10 +186,25 @@ impl Server {
                     "texlab.findEnvironments".into(),
                     "texlab.showDependencyGraph".into(),
                     "texlab.cancelBuild".into(),
+                    "texlab.startContinuousBuild".into(),
+                    "texlab.stopContinuousBuild".into(),
                 ],
                 ..Default::default()
             }),
             inlay_hint_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                resolve_provider: Some(false),
+                work_done_progress_options: WorkDoneProgressOptions::default(),
+            })),
+            semantic_tokens_provider: Some(
+                SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
+                    legend: semantic_tokens::legend(),
+                    range: Some(true),
+                    full: Some(SemanticTokensFullOptions::Bool(true)),
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                }),
+            ),
             ..ServerCapabilities::default()
         }
     }
@@ -422,11 +453,12 @@ impl Server {
         let mut uri = params.text_document.uri;
         normalize_uri(&mut uri);
 
-        if self.workspace.read().config().build.on_save {
+        if build_config(&self.workspace.read(), &uri).on_save && !self.is_built_continuously(&uri) {
             let text_document = TextDocumentIdentifier::new(uri.clone());
             let params = BuildParams {
                 text_document,
                 position: None,
+                target: None,
             };
 
             self.build(None, params)?;
@@ -517,8 +549,9 @@ impl Server {
     }
 
     fn completion_resolve(&self, id: RequestId, mut item: CompletionItem) -> Result<()> {
+        let client_flags = Arc::clone(&self.client_flags);
         self.run_query(id, move |workspace| {
-            completion::resolve(workspace, &mut item);
+            completion::resolve(workspace, &mut item, &client_flags);
             item
         });
 
@@ -548,7 +581,16 @@ impl Server {
         normalize_uri(&mut params.text_document_position_params.text_document.uri);
         let uri_and_pos = &params.text_document_position_params;
         self.update_cursor(&uri_and_pos.text_document.uri, uri_and_pos.position);
-        self.run_query(id, move |db| hover::find(db, params));
+        let client_flags = Arc::clone(&self.client_flags);
+        self.run_query(id, move |db| hover::find(db, params, &client_flags));
+        Ok(())
+    }
+
+    fn signature_help(&mut self, id: RequestId, mut params: SignatureHelpParams) -> Result<()> {
+        normalize_uri(&mut params.text_document_position_params.text_document.uri);
+        let uri_and_pos = &params.text_document_position_params;
+        self.update_cursor(&uri_and_pos.text_document.uri, uri_and_pos.position);
+        self.run_query(id, move |db| signature_help::find(db, params));
         Ok(())
     }
 
@@ -589,6 +631,16 @@ impl Server {
         Ok(())
     }
 
+    fn range_formatting(&self, id: RequestId, params: DocumentRangeFormattingParams) -> Result<()> {
+        let mut uri = params.text_document.uri;
+        normalize_uri(&mut uri);
+        self.run_query(id, move |db| {
+            formatting::format_source_code_range(db, &uri, params.range, &params.options)
+        });
+
+        Ok(())
+    }
+
     fn execute_command(&self, id: RequestId, params: ExecuteCommandParams) -> Result<()> {
         match params.command.as_str() {
             "texlab.cleanAuxiliary" => {
@@ -617,15 +669,28 @@ impl Server {
                     .send_response(lsp_server::Response::new_ok(id, dot))?;
             }
             "texlab.cancelBuild" => {
-                let pending_builds = Arc::clone(&self.pending_builds);
+                let builds = self.builds.clone();
                 self.run_fallible(id, move || {
-                    for pid in pending_builds.lock().drain() {
+                    let (pids, jobs) = builds.cancel_all();
+                    for pid in pids {
                         let _ = BuildCommand::cancel(pid);
                     }
 
+                    for listener in jobs.into_iter().flat_map(|job| job.listeners) {
+                        listener(BuildStatus::Cancelled);
+                    }
+
                     Ok(())
                 });
             }
+            "texlab.startContinuousBuild" => {
+                let result = self.start_continuous_build(params);
+                self.run_fallible(id, move || result);
+            }
+            "texlab.stopContinuousBuild" => {
+                let result = self.stop_continuous_build(params);
+                self.run_fallible(id, move || result);
+            }
             _ => {
                 self.client
                     .send_error(
@@ -654,16 +719,23 @@ impl Server {
         Ok(())
     }
 
+    fn semantic_tokens_full(&self, id: RequestId, mut params: SemanticTokensParams) -> Result<()> {
+        normalize_uri(&mut params.text_document.uri);
+        self.run_query(id, move |db| semantic_tokens::find_all(db, params));
+        Ok(())
+    }
+
     fn semantic_tokens_range(
         &self,
-        _id: RequestId,
-        _params: SemanticTokensRangeParams,
+        id: RequestId,
+        mut params: SemanticTokensRangeParams,
     ) -> Result<()> {
+        normalize_uri(&mut params.text_document.uri);
+        self.run_query(id, move |db| semantic_tokens::find_in_range(db, params));
         Ok(())
     }
 
     fn build(&self, id: Option<RequestId>, params: BuildParams) -> Result<()> {
-        static LOCK: Mutex<()> = Mutex::new(());
         static NEXT_TOKEN: AtomicI32 = AtomicI32::new(1);
 
         let mut uri = params.text_document.uri;
@@ -673,66 +745,211 @@ impl Server {
 
         let client = self.client.clone();
 
-        let fwd_search_after = workspace.config().build.forward_search_after;
+        let fwd_search_after = build_config(&workspace, &uri).forward_search_after;
+
+        let listener: BuildListener = {
+            let client = client.clone();
+            Box::new(move |status| {
+                if let Some(id) = id {
+                    let result = BuildResult { status };
+                    let _ = client.send_response(lsp_server::Response::new_ok(id, result));
+                }
+            })
+        };
+
+        let forward_search = fwd_search_after.then(|| (uri.clone(), params.position));
+
+        let command = match BuildCommand::new(&workspace, &uri, params.target.as_deref()) {
+            Ok(command) => command,
+            Err(why) => {
+                log::error!("Failed to compile document \"{uri}\": {why}");
+                listener(BuildStatus::Failure);
+                if let Some((uri, position)) = forward_search {
+                    let _ = self
+                        .internal_tx
+                        .send(InternalMessage::ForwardSearch(uri, position));
+                }
+
+                return Ok(());
+            }
+        };
+
+        // A build of the same project is already running and will be repeated afterwards.
+        let job = BuildJob {
+            uri,
+            command,
+            listeners: vec![listener],
+            forward_search,
+        };
+
+        let Some(job) = self.builds.enqueue(job) else {
+            return Ok(());
+        };
 
         let (sender, receiver) = crossbeam_channel::unbounded();
         self.redirect_build_log(receiver);
 
-        let command = BuildCommand::new(&workspace, &uri);
-        let internal = self.internal_tx.clone();
         let progress = self.client_flags.progress;
-        let pending_builds = Arc::clone(&self.pending_builds);
-
+        let builds = self.builds.clone();
+        let internal = self.internal_tx.clone();
         self.pool.execute(move || {
-            let guard = LOCK.lock();
-
-            let progress_reporter = if progress {
-                let token = NEXT_TOKEN.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                Some(ProgressReporter::new(client.clone(), token, &uri))
-            } else {
-                None
-            };
-
-            let status = command
-                .and_then(|command| {
-                    let mut process = command.spawn(sender)?;
-                    let pid = process.id();
-                    pending_builds.lock().insert(pid);
-                    let result = process.wait();
-
-                    let status = if pending_builds.lock().remove(&pid) {
-                        if result?.success() {
-                            BuildStatus::Success
-                        } else {
-                            BuildStatus::Error
+            let mut next = Some(job);
+            while let Some(job) = next.take() {
+                let progress_reporter = if progress {
+                    let token = NEXT_TOKEN.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    Some(ProgressReporter::new(
+                        client.clone(),
+                        token,
+                        &job.command.project,
+                    ))
+                } else {
+                    None
+                };
+
+                let mut output = BuildOutput::default();
+                let (status, exit_code) = run_build(&builds, &job.command, |line| {
+                    output.push(&line);
+                    if let Some(progress) = output.take_progress() {
+                        if let Some(reporter) = &progress_reporter {
+                            reporter.report(progress);
                         }
-                    } else {
-                        BuildStatus::Cancelled
-                    };
+                    }
+
+                    if let Some(errors) = output.take_errors() {
+                        let log_path = job.command.log_path.clone();
+                        let message =
+                            InternalMessage::BuildOutput(job.uri.clone(), log_path, errors);
+                        let _ = internal.send(message);
+                    }
 
-                    Ok(status)
-                })
-                .unwrap_or_else(|why| {
-                    log::error!("Failed to compile document \"{uri}\": {why}");
-                    BuildStatus::Failure
+                    let _ = sender.send(line);
                 });
 
-            drop(progress_reporter);
-            drop(guard);
+                drop(progress_reporter);
 
-            if let Some(id) = id {
-                let result = BuildResult { status };
-                let _ = client.send_response(lsp_server::Response::new_ok(id, result));
+                let (status, queued) = builds.finish(&job.command, status, exit_code);
+                for listener in job.listeners {
+                    listener(status);
+                }
+
+                // Merged requests share a single forward search.
+                if let Some((uri, position)) = job.forward_search {
+                    if status != BuildStatus::Cancelled {
+                        let _ = internal.send(InternalMessage::ForwardSearch(uri, position));
+                    }
+                }
+
+                next = queued;
             }
+        });
+
+        Ok(())
+    }
 
-            if fwd_search_after && status != BuildStatus::Cancelled {
-                let _ = internal.send(InternalMessage::ForwardSearch(uri, params.position));
+    /// Checks whether the project of the document is rebuilt by a continuous build anyway.
+    fn is_built_continuously(&self, uri: &Url) -> bool {
+        BuildCommand::new(&self.workspace.read(), uri, None)
+            .is_ok_and(|command| self.continuous.is_running(&command.project))
+    }
+
+    fn build_status(&self, id: RequestId, params: BuildStatusParams) -> Result<()> {
+        let result = match params.text_document {
+            Some(text_document) => {
+                let mut uri = text_document.uri;
+                normalize_uri(&mut uri);
+                BuildCommand::new(&self.workspace.read(), &uri, None)
+                    .map(|command| self.builds.status(Some(&command.project)))
+                    .unwrap_or_default()
             }
+            None => self.builds.status(None),
+        };
+
+        self.client
+            .send_response(lsp_server::Response::new_ok(id, result))?;
+
+        Ok(())
+    }
+
+    fn start_continuous_build(&self, params: ExecuteCommandParams) -> Result<bool> {
+        let mut params = self.parse_command_params::<TextDocumentIdentifier>(params.arguments)?;
+        normalize_uri(&mut params.uri);
+
+        let workspace = self.workspace.read();
+        let command = BuildCommand::continuous(&workspace, &params.uri, None)?;
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        self.redirect_build_log(receiver);
+
+        let internal = self.internal_tx.clone();
+        let uri = params.uri;
+        let listener = Box::new(move |command: &BuildCommand, log: syntax::BuildLog| {
+            log::info!(
+                "Continuous build of \"{}\" finished a pass with {} messages",
+                command.project,
+                log.errors.len()
+            );
+
+            let message = InternalMessage::BuildPassFinished(uri.clone(), command.log_path.clone());
+            let _ = internal.send(message);
         });
 
+        Ok(self.continuous.start(command, sender, listener))
+    }
+
+    fn stop_continuous_build(&self, params: ExecuteCommandParams) -> Result<bool> {
+        let mut params = self.parse_command_params::<TextDocumentIdentifier>(params.arguments)?;
+        normalize_uri(&mut params.uri);
+
+        let workspace = self.workspace.read();
+        let command = BuildCommand::continuous(&workspace, &params.uri, None)?;
+        Ok(self.continuous.stop(&command.project))
+    }
+
+    /// Reloads the build log after a pass of a continuous build so that the diagnostics are up to date.
+    fn build_pass_finished(&mut self, uri: Url, log_path: Option<PathBuf>) -> Result<()> {
+        if let Some(log_path) = log_path {
+            let mut workspace = self.workspace.write();
+            if workspace.load(&log_path, Language::Log).is_ok() {
+                if let Some(document) = workspace.lookup_file(&log_path) {
+                    self.diagnostic_manager.update_syntax(&workspace, document);
+                }
+            }
+        }
+
+        self.publish_diagnostics()?;
+
+        if build_config(&self.workspace.read(), &uri).forward_search_after {
+            self.forward_search(None, uri, None)?;
+        }
+
         Ok(())
     }
 
+    /// Shows the errors that a running build has reported so far.
+    fn build_output(
+        &mut self,
+        uri: Url,
+        log_path: Option<PathBuf>,
+        errors: Vec<syntax::BuildError>,
+    ) -> Result<()> {
+        let Some(log_uri) = log_path.and_then(|path| Url::from_file_path(path).ok()) else {
+            return Ok(());
+        };
+
+        {
+            let workspace = self.workspace.read();
+            let Some(document) = workspace.lookup(&uri) else {
+                return Ok(());
+            };
+
+            let root = deps::root(&workspace, document);
+            self.diagnostic_manager
+                .update_build_output(&workspace, log_uri, root, &errors);
+        }
+
+        self.publish_diagnostics()
+    }
+
     fn redirect_build_log(&self, receiver: Receiver<String>) {
         let client = self.client.clone();
         self.pool.execute(move || {
@@ -780,9 +997,38 @@ impl Server {
         Ok(())
     }
 
-    fn code_actions(&self, id: RequestId, _params: CodeActionParams) -> Result<()> {
-        self.client
-            .send_response(lsp_server::Response::new_ok(id, Vec::<CodeAction>::new()))?;
+    fn synctex_forward(&self, id: RequestId, mut params: TextDocumentPositionParams) -> Result<()> {
+        normalize_uri(&mut params.text_document.uri);
+        self.run_query(id, move |db| {
+            let (pdf_path, pdf_box) = synctex::forward(db, params)?;
+            Some(SynctexForwardResult {
+                uri: Url::from_file_path(pdf_path).ok()?,
+                page: pdf_box.page,
+                x: pdf_box.x,
+                y: pdf_box.y,
+                width: pdf_box.width,
+                height: pdf_box.height,
+            })
+        });
+
+        Ok(())
+    }
+
+    fn synctex_inverse(&self, id: RequestId, params: SynctexInverseParams) -> Result<()> {
+        self.run_query(id, move |db| {
+            let pdf_path = params.uri.to_file_path().ok()?;
+            synctex::inverse(db, &pdf_path, params.page, params.x, params.y)
+        });
+
+        Ok(())
+    }
+
+    fn code_actions(&self, id: RequestId, mut params: CodeActionParams) -> Result<()> {
+        normalize_uri(&mut params.text_document.uri);
+        self.run_query(id, move |db| {
+            code_action::find_all(db, params).unwrap_or_default()
+        });
+
         Ok(())
     }
 
@@ -798,6 +1044,19 @@ impl Server {
 
         let mut workspace = self.workspace.write();
 
+        // Images are not part of the workspace, so the result of looking them up can change
+        // even if no document is affected.
+        let graphics_changed = matches!(
+            event.kind,
+            notify::EventKind::Create(_)
+                | notify::EventKind::Remove(_)
+                | notify::EventKind::Modify(ModifyKind::Name(_))
+        );
+
+        if graphics_changed {
+            self.diagnostic_manager.invalidate_graphics();
+        }
+
         match event.kind {
             notify::EventKind::Remove(_) | notify::EventKind::Modify(ModifyKind::Name(_)) => {
                 let affected_uris = event
@@ -852,6 +1111,9 @@ impl Server {
             self.diagnostic_manager.cleanup(&workspace);
             drop(workspace);
             self.update_workspace();
+        } else if graphics_changed {
+            drop(workspace);
+            self.publish_diagnostics_with_delay();
         }
     }
 
@@ -1001,6 +1263,9 @@ impl Server {
                                 .on::<FoldingRangeRequest, _>(|id, params| self.folding_range(id, params))?
                                 .on::<References, _>(|id, params| self.references(id, params))?
                                 .on::<HoverRequest, _>(|id, params| self.hover(id, params))?
+                                .on::<SignatureHelpRequest, _>(|id, params| {
+                                    self.signature_help(id, params)
+                                })?
                                 .on::<DocumentSymbolRequest, _>(|id, params| {
                                     self.document_symbols(id, params)
                                 })?
@@ -1022,11 +1287,24 @@ impl Server {
                                     self.document_highlight(id, params)
                                 })?
                                 .on::<Formatting, _>(|id, params| self.formatting(id, params))?
+                                .on::<RangeFormatting, _>(|id, params| {
+                                    self.range_formatting(id, params)
+                                })?
                                 .on::<BuildRequest, _>(|id, params| self.build(Some(id), params))?
+                                .on::<BuildStatusRequest, _>(|id, params| self.build_status(id, params))?
                                 .on::<ForwardSearchRequest, _>(|id, params| {
                                     self.forward_search(Some(id), params.text_document.uri, Some(params.position))
                                 })?
+                                .on::<SynctexForwardRequest, _>(|id, params| {
+                                    self.synctex_forward(id, params)
+                                })?
+                                .on::<SynctexInverseRequest, _>(|id, params| {
+                                    self.synctex_inverse(id, params)
+                                })?
                                 .on::<ExecuteCommand,_>(|id, params| self.execute_command(id, params))?
+                                .on::<SemanticTokensFullRequest, _>(|id, params| {
+                                    self.semantic_tokens_full(id, params)
+                                })?
                                 .on::<SemanticTokensRangeRequest, _>(|id, params| {
                                     self.semantic_tokens_range(id, params)
                                 })?
@@ -1093,6 +1371,12 @@ impl Server {
                         InternalMessage::InverseSearch(params) => {
                             self.inverse_search(params)?;
                         }
+                        InternalMessage::BuildPassFinished(uri, log_path) => {
+                            self.build_pass_finished(uri, log_path)?;
+                        }
+                        InternalMessage::BuildOutput(uri, log_path, errors) => {
+                            self.build_output(uri, log_path, errors)?;
+                        }
                     };
                 }
             };
@@ -1116,12 +1400,54 @@ impl Server {
         self.register_configuration();
         self.pull_options();
         self.setup_ipc_server();
-        self.process_messages()?;
+        let result = self.process_messages();
+        self.continuous.stop_all();
+        result?;
         self.pool.join();
         Ok(())
     }
 }
 
+/// Runs a build and passes every line of its output to the given callback.
+fn run_build(
+    builds: &BuildManager,
+    command: &BuildCommand,
+    mut on_output: impl FnMut(String),
+) -> (BuildStatus, Option<i32>) {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let result = command.spawn(sender).and_then(|mut process| {
+        if !builds.attach(&command.project, process.id()) {
+            let _ = BuildCommand::cancel(process.id());
+        }
+
+        for line in receiver {
+            on_output(line);
+        }
+
+        Ok(process.wait()?)
+    });
+
+    match result {
+        Ok(exit) if exit.success() => (BuildStatus::Success, exit.code()),
+        Ok(exit) => (BuildStatus::Error, exit.code()),
+        Err(why) => {
+            log::error!("Failed to compile project \"{}\": {why}", command.project);
+            (BuildStatus::Failure, None)
+        }
+    }
+}
+
+/// Returns the build settings of the project that contains the given document.
+fn build_config(workspace: &Workspace, uri: &Url) -> BuildConfig {
+    match workspace.lookup(uri) {
+        Some(document) => {
+            let root = deps::root(workspace, document);
+            deps::ProjectRoot::walk_and_find(workspace, &root.dir).build
+        }
+        None => workspace.config().build.clone(),
+    }
+}
+
 struct FileWatcher {
     watcher: Debouncer<notify::RecommendedWatcher, FileIdMap>,
     watched_dirs: FxHashSet<PathBuf>,
@@ -0,0 +1,379 @@
+use std::{
+    collections::VecDeque,
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use commands::BuildCommand;
+use lsp_types::{Position, Url};
+use parking_lot::Mutex;
+use parser::{BuildLogParser, BuildProgress};
+use rustc_hash::FxHashMap;
+use syntax::{BuildError, BuildErrorLevel};
+
+use super::extensions::{BuildRecord, BuildStatus, ProjectBuildStatus};
+
+/// The number of finished builds that are kept per project.
+const HISTORY_SIZE: usize = 10;
+
+/// A callback that is invoked once the requested build has finished.
+pub type BuildListener = Box<dyn FnOnce(BuildStatus) + Send>;
+
+pub struct BuildJob {
+    /// The document that has been requested to build.
+    pub uri: Url,
+    pub command: BuildCommand,
+    pub listeners: Vec<BuildListener>,
+    /// The forward search that is run once after the build has finished.
+    pub forward_search: Option<(Url, Option<Position>)>,
+}
+
+impl std::fmt::Debug for BuildJob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BuildJob")
+            .field("uri", &self.uri)
+            .field("command", &self.command)
+            .field("listeners", &self.listeners.len())
+            .field("forward_search", &self.forward_search)
+            .finish()
+    }
+}
+
+/// Keeps track of the builds of every project.
+///
+/// Builds of different projects can run at the same time. If a project is built while
+/// another build of it is still running, then the requests are merged into a single build
+/// that starts once the running build has finished.
+#[derive(Debug, Default, Clone)]
+pub struct BuildManager {
+    projects: Arc<Mutex<FxHashMap<Url, ProjectState>>>,
+}
+
+#[derive(Debug, Default)]
+struct ProjectState {
+    running: Option<RunningBuild>,
+    queued: Option<BuildJob>,
+    history: VecDeque<BuildRecord>,
+}
+
+#[derive(Debug)]
+struct RunningBuild {
+    start_time: SystemTime,
+    pid: Option<u32>,
+    cancelled: bool,
+}
+
+impl BuildManager {
+    /// Registers a build and returns it if it can be started right away.
+    pub fn enqueue(&self, mut job: BuildJob) -> Option<BuildJob> {
+        let mut projects = self.projects.lock();
+        let state = projects.entry(job.command.project.clone()).or_default();
+        if state.running.is_none() {
+            state.running = Some(RunningBuild::new());
+            return Some(job);
+        }
+
+        // The latest command wins because it reflects the current configuration.
+        if let Some(queued) = state.queued.take() {
+            job.listeners.splice(0..0, queued.listeners);
+            job.forward_search = job.forward_search.or(queued.forward_search);
+        }
+
+        state.queued = Some(job);
+        None
+    }
+
+    /// Associates the process of the running build with its project.
+    ///
+    /// Returns `false` if the build has been cancelled before its process was started,
+    /// in which case the caller needs to stop the process.
+    pub fn attach(&self, project: &Url, pid: u32) -> bool {
+        let mut projects = self.projects.lock();
+        let Some(running) = projects
+            .get_mut(project)
+            .and_then(|state| state.running.as_mut())
+        else {
+            return true;
+        };
+
+        running.pid = Some(pid);
+        !running.cancelled
+    }
+
+    /// Stores the result of the running build and returns the next build of the project if any.
+    pub fn finish(
+        &self,
+        command: &BuildCommand,
+        status: BuildStatus,
+        exit_code: Option<i32>,
+    ) -> (BuildStatus, Option<BuildJob>) {
+        let end_time = SystemTime::now();
+        let (errors, warnings) = command.log_path.as_deref().map_or((0, 0), count_errors);
+        let pdf = command
+            .pdf_path
+            .as_ref()
+            .filter(|path| path.exists())
+            .and_then(|path| Url::from_file_path(path).ok());
+
+        let mut projects = self.projects.lock();
+        let state = projects.entry(command.project.clone()).or_default();
+        let running = state.running.take().unwrap_or_else(RunningBuild::new);
+        let status = if running.cancelled {
+            BuildStatus::Cancelled
+        } else {
+            status
+        };
+
+        let duration = end_time
+            .duration_since(running.start_time)
+            .unwrap_or_default();
+
+        state.history.push_front(BuildRecord {
+            start_time: timestamp(running.start_time),
+            end_time: timestamp(end_time),
+            duration: duration.as_millis() as u64,
+            status,
+            exit_code,
+            errors,
+            warnings,
+            pdf,
+        });
+
+        state.history.truncate(HISTORY_SIZE);
+
+        let next = state.queued.take();
+        if next.is_some() {
+            state.running = Some(RunningBuild::new());
+        }
+
+        (status, next)
+    }
+
+    /// Marks all running builds as cancelled and returns the processes that need to be stopped.
+    ///
+    /// Queued builds are dropped and returned so that their listeners can be notified.
+    pub fn cancel_all(&self) -> (Vec<u32>, Vec<BuildJob>) {
+        let mut pids = Vec::new();
+        let mut jobs = Vec::new();
+        for state in self.projects.lock().values_mut() {
+            if let Some(running) = &mut state.running {
+                running.cancelled = true;
+                pids.extend(running.pid);
+            }
+
+            jobs.extend(state.queued.take());
+        }
+
+        (pids, jobs)
+    }
+
+    /// Returns the state of the builds of the given project or of all projects.
+    pub fn status(&self, project: Option<&Url>) -> Vec<ProjectBuildStatus> {
+        let mut result: Vec<_> = self
+            .projects
+            .lock()
+            .iter()
+            .filter(|(uri, _)| project.map_or(true, |project| *uri == project))
+            .map(|(uri, state)| ProjectBuildStatus {
+                project: uri.clone(),
+                running: state.running.is_some(),
+                queued: state.queued.is_some(),
+                history: state.history.iter().cloned().collect(),
+            })
+            .collect();
+
+        result.sort_by(|a, b| a.project.cmp(&b.project));
+        result
+    }
+}
+
+impl RunningBuild {
+    fn new() -> Self {
+        Self {
+            start_time: SystemTime::now(),
+            pid: None,
+            cancelled: false,
+        }
+    }
+}
+
+/// Parses the output of a running build to report its progress and the errors found so far.
+#[derive(Debug, Default)]
+pub struct BuildOutput {
+    parser: BuildLogParser,
+    progress: BuildProgress,
+    error_count: usize,
+}
+
+impl BuildOutput {
+    pub fn push(&mut self, line: &str) {
+        self.parser.push_line(line);
+    }
+
+    /// Returns the progress if it has changed since the last call.
+    pub fn take_progress(&mut self) -> Option<BuildProgress> {
+        let progress = self.parser.progress();
+        if progress == self.progress {
+            return None;
+        }
+
+        self.progress = progress;
+        Some(progress)
+    }
+
+    /// Returns the errors of the current pass if new ones have been found since the last call.
+    ///
+    /// The errors of the previous pass are kept until the current pass reports its own errors.
+    pub fn take_errors(&mut self) -> Option<Vec<BuildError>> {
+        let errors = self.parser.errors();
+        if errors.len() == self.error_count {
+            return None;
+        }
+
+        self.error_count = errors.len();
+        (!errors.is_empty()).then(|| errors.to_vec())
+    }
+}
+
+fn timestamp(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_millis() as u64
+}
+
+fn count_errors(log_path: &Path) -> (usize, usize) {
+    let Ok(data) = std::fs::read(log_path) else {
+        return (0, 0);
+    };
+
+    let text = String::from_utf8_lossy(&data);
+    let errors = parser::parse_build_log(&text).errors;
+    let count = |level| errors.iter().filter(|error| error.level == level).count();
+    (
+        count(BuildErrorLevel::Error),
+        count(BuildErrorLevel::Warning),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use base_db::{Owner, Workspace};
+    use commands::BuildCommand;
+    use distro::Language;
+    use line_index::LineCol;
+    use lsp_types::{Position, Url};
+    use parking_lot::Mutex;
+
+    use super::{BuildJob, BuildManager, BuildStatus, HISTORY_SIZE};
+
+    fn create_job(statuses: &Arc<Mutex<Vec<BuildStatus>>>, line: u32) -> BuildJob {
+        let uri = Url::parse("file:///texlab/main.tex").unwrap();
+        let mut workspace = Workspace::default();
+        workspace.open(
+            uri.clone(),
+            String::new(),
+            Language::Tex,
+            Owner::Client,
+            LineCol { line: 0, col: 0 },
+        );
+
+        let command = BuildCommand::new(&workspace, &uri, None).unwrap();
+        let statuses = Arc::clone(statuses);
+        BuildJob {
+            uri: uri.clone(),
+            command,
+            listeners: vec![Box::new(move |status| statuses.lock().push(status))],
+            forward_search: Some((uri, Some(Position::new(line, 0)))),
+        }
+    }
+
+    #[test]
+    fn test_enqueue_coalesce() {
+        let statuses = Arc::default();
+        let builds = BuildManager::default();
+
+        let job = builds.enqueue(create_job(&statuses, 1)).unwrap();
+        assert!(builds.enqueue(create_job(&statuses, 2)).is_none());
+        assert!(builds.enqueue(create_job(&statuses, 3)).is_none());
+
+        let status = &builds.status(None)[0];
+        assert!(status.running);
+        assert!(status.queued);
+
+        let (status, next) = builds.finish(&job.command, BuildStatus::Success, Some(0));
+        assert_eq!(status, BuildStatus::Success);
+
+        let next = next.unwrap();
+        assert_eq!(next.listeners.len(), 2);
+        assert_eq!(next.forward_search.unwrap().1, Some(Position::new(3, 0)));
+
+        let status = &builds.status(None)[0];
+        assert!(status.running);
+        assert!(!status.queued);
+
+        let (status, next) = builds.finish(&next.command, BuildStatus::Error, Some(1));
+        assert_eq!(status, BuildStatus::Error);
+        assert!(next.is_none());
+
+        let status = &builds.status(None)[0];
+        assert!(!status.running);
+        assert_eq!(status.history.len(), 2);
+        assert_eq!(status.history[0].exit_code, Some(1));
+        assert_eq!(status.history[1].exit_code, Some(0));
+    }
+
+    #[test]
+    fn test_history_truncation() {
+        let statuses = Arc::default();
+        let builds = BuildManager::default();
+
+        for i in 0..HISTORY_SIZE as i32 + 5 {
+            let job = builds.enqueue(create_job(&statuses, 0)).unwrap();
+            builds.finish(&job.command, BuildStatus::Success, Some(i));
+        }
+
+        let history = &builds.status(None)[0].history;
+        assert_eq!(history.len(), HISTORY_SIZE);
+        assert_eq!(history[0].exit_code, Some(HISTORY_SIZE as i32 + 4));
+        assert_eq!(history[HISTORY_SIZE - 1].exit_code, Some(5));
+    }
+
+    #[test]
+    fn test_cancel_before_attach() {
+        let statuses = Arc::default();
+        let builds = BuildManager::default();
+
+        let job = builds.enqueue(create_job(&statuses, 0)).unwrap();
+        assert!(builds.enqueue(create_job(&statuses, 0)).is_none());
+
+        let (pids, jobs) = builds.cancel_all();
+        assert!(pids.is_empty());
+        assert_eq!(jobs.len(), 1);
+        for listener in jobs.into_iter().flat_map(|job| job.listeners) {
+            listener(BuildStatus::Cancelled);
+        }
+
+        assert!(!builds.attach(&job.command.project, 42));
+
+        let (status, next) = builds.finish(&job.command, BuildStatus::Success, Some(0));
+        assert_eq!(status, BuildStatus::Cancelled);
+        assert!(next.is_none());
+        assert_eq!(*statuses.lock(), vec![BuildStatus::Cancelled]);
+    }
+
+    #[test]
+    fn test_attach_running() {
+        let statuses = Arc::default();
+        let builds = BuildManager::default();
+
+        let job = builds.enqueue(create_job(&statuses, 0)).unwrap();
+        assert!(builds.attach(&job.command.project, 42));
+
+        let (pids, jobs) = builds.cancel_all();
+        assert_eq!(pids, vec![42]);
+        assert!(jobs.is_empty());
+    }
+}
@@ -0,0 +1,216 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use commands::BuildCommand;
+use crossbeam_channel::Sender;
+use lsp_types::Url;
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+use syntax::BuildLog;
+
+/// The delay before a crashed process is restarted for the first time.
+const MIN_RESTART_DELAY: Duration = Duration::from_secs(1);
+
+/// The delay is doubled after every crash without a finished pass in between.
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+
+/// A callback that is invoked whenever the compiler has finished a pass.
+pub type PassListener = Box<dyn Fn(&BuildCommand, BuildLog) + Send>;
+
+/// Supervises the long-running `latexmk -pvc` or `tectonic -X watch` processes of the continuous build mode.
+///
+/// There is at most one process per project. Processes that exit on their own are restarted
+/// until the continuous build is stopped.
+#[derive(Debug, Default, Clone)]
+pub struct ContinuousBuilds {
+    processes: Arc<Mutex<FxHashMap<Url, Process>>>,
+}
+
+#[derive(Debug)]
+struct Process {
+    id: u64,
+    pid: Option<u32>,
+}
+
+impl ContinuousBuilds {
+    pub fn is_running(&self, project: &Url) -> bool {
+        self.processes.lock().contains_key(project)
+    }
+
+    /// Starts the continuous build of a project. Returns `false` if it is already running.
+    pub fn start(
+        &self,
+        command: BuildCommand,
+        log: Sender<String>,
+        listener: PassListener,
+    ) -> bool {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+        {
+            let mut processes = self.processes.lock();
+            if processes.contains_key(&command.project) {
+                return false;
+            }
+
+            processes.insert(command.project.clone(), Process { id, pid: None });
+        }
+
+        let builds = self.clone();
+        thread::spawn(move || builds.supervise(id, command, log, listener));
+        true
+    }
+
+    /// Stops the continuous build of a project. Returns `false` if it was not running.
+    pub fn stop(&self, project: &Url) -> bool {
+        let Some(process) = self.processes.lock().remove(project) else {
+            return false;
+        };
+
+        if let Some(pid) = process.pid {
+            let _ = BuildCommand::cancel(pid);
+        }
+
+        true
+    }
+
+    pub fn stop_all(&self) {
+        let processes: Vec<_> = self.processes.lock().drain().collect();
+        for pid in processes.into_iter().filter_map(|(_, process)| process.pid) {
+            let _ = BuildCommand::cancel(pid);
+        }
+    }
+
+    fn supervise(
+        &self,
+        id: u64,
+        command: BuildCommand,
+        log: Sender<String>,
+        listener: PassListener,
+    ) {
+        let project = &command.project;
+        let mut delay = MIN_RESTART_DELAY;
+        loop {
+            let (sender, receiver) = crossbeam_channel::unbounded();
+            let mut child = match command.spawn(sender) {
+                Ok(child) => child,
+                Err(why) => {
+                    log::error!("Failed to start continuous build of \"{project}\": {why}");
+                    self.remove(project, id);
+                    return;
+                }
+            };
+
+            if !self.attach(project, id, child.id()) {
+                let _ = BuildCommand::cancel(child.id());
+                let _ = child.wait();
+                return;
+            }
+
+            let mut tracker = PassTracker::default();
+            for line in receiver {
+                if let Some(build_log) = tracker.push(&line) {
+                    delay = MIN_RESTART_DELAY;
+                    listener(&command, build_log);
+                }
+
+                let _ = log.send(line);
+            }
+
+            let status = child.wait();
+            if !self.is_active(project, id) {
+                return;
+            }
+
+            log::warn!(
+                "Continuous build of \"{project}\" exited unexpectedly ({status:?}), restarting in {delay:?}"
+            );
+
+            thread::sleep(delay);
+            delay = (delay * 2).min(MAX_RESTART_DELAY);
+            if !self.is_active(project, id) {
+                return;
+            }
+        }
+    }
+
+    fn attach(&self, project: &Url, id: u64, pid: u32) -> bool {
+        match self.processes.lock().get_mut(project) {
+            Some(process) if process.id == id => {
+                process.pid = Some(pid);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn is_active(&self, project: &Url, id: u64) -> bool {
+        self.processes
+            .lock()
+            .get(project)
+            .is_some_and(|process| process.id == id)
+    }
+
+    fn remove(&self, project: &Url, id: u64) {
+        let mut processes = self.processes.lock();
+        if processes
+            .get(project)
+            .is_some_and(|process| process.id == id)
+        {
+            processes.remove(project);
+        }
+    }
+}
+
+/// Splits the output of a continuous build into the passes of the TeX engine.
+#[derive(Debug, Default)]
+struct PassTracker {
+    output: String,
+}
+
+impl PassTracker {
+    /// Adds a line of output and returns the parsed output of the pass once it has finished.
+    fn push(&mut self, line: &str) -> Option<BuildLog> {
+        self.output.push_str(line);
+        self.output.push('\n');
+
+        // TeX engines finish with the location of the log file while Tectonic reports the written PDF.
+        let finished = line.starts_with("Transcript written on")
+            || (line.starts_with("note: Writing `") && line.contains(".pdf`"));
+
+        finished.then(|| parser::parse_build_log(&std::mem::take(&mut self.output)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PassTracker;
+
+    #[test]
+    fn test_pass_tracker() {
+        let mut tracker = PassTracker::default();
+        let output = [
+            "Latexmk: applying rule 'pdflatex'...",
+            "This is pdfTeX, Version 3.141592653-2.6-1.40.25 (TeX Live 2023) (preloaded format=pdflatex)",
+            "(./main.tex",
+            "! Undefined control sequence.",
+            "l.3 \\foo",
+            ")",
+            "Output written on main.pdf (1 page, 12345 bytes).",
+        ];
+
+        for line in output {
+            assert!(tracker.push(line).is_none());
+        }
+
+        let log = tracker.push("Transcript written on main.log.").unwrap();
+        assert_eq!(log.errors.len(), 1);
+        assert!(tracker.output.is_empty());
+    }
+}
@@ -1,7 +1,9 @@
 #![allow(non_camel_case_types)]
 
 use commands::ForwardSearchError;
-use lsp_types::{Position, Range, TextDocumentIdentifier, TextDocumentPositionParams};
+use lsp_types::{
+    Location, Position, Range, TextDocumentIdentifier, TextDocumentPositionParams, Url,
+};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
@@ -22,6 +24,10 @@ pub struct BuildParams {
 
     #[serde(default)]
     pub position: Option<Position>,
+
+    /// The name of the output of a Tectonic project that should be built.
+    #[serde(default)]
+    pub target: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -39,6 +45,54 @@ pub enum BuildStatus {
     Cancelled = 3,
 }
 
+pub struct BuildStatusRequest;
+
+impl lsp_types::request::Request for BuildStatusRequest {
+    type Params = BuildStatusParams;
+
+    type Result = Vec<ProjectBuildStatus>;
+
+    const METHOD: &'static str = "texlab/buildStatus";
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildStatusParams {
+    /// Restricts the result to the project of this document.
+    #[serde(default)]
+    pub text_document: Option<TextDocumentIdentifier>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectBuildStatus {
+    pub project: Url,
+    pub running: bool,
+    pub queued: bool,
+
+    /// The finished builds, starting with the most recent one.
+    pub history: Vec<BuildRecord>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildRecord {
+    /// Milliseconds since the Unix epoch.
+    pub start_time: u64,
+
+    /// Milliseconds since the Unix epoch.
+    pub end_time: u64,
+
+    /// The duration of the build in milliseconds.
+    pub duration: u64,
+
+    pub status: BuildStatus,
+    pub exit_code: Option<i32>,
+    pub errors: usize,
+    pub warnings: usize,
+    pub pdf: Option<Url>,
+}
+
 pub struct ForwardSearchRequest;
 
 impl lsp_types::request::Request for ForwardSearchRequest {
@@ -77,6 +131,46 @@ pub struct ForwardSearchResult {
     pub status: ForwardSearchStatus,
 }
 
+pub struct SynctexForwardRequest;
+
+impl lsp_types::request::Request for SynctexForwardRequest {
+    type Params = TextDocumentPositionParams;
+
+    type Result = Option<SynctexForwardResult>;
+
+    const METHOD: &'static str = "texlab/synctexForward";
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SynctexForwardResult {
+    pub uri: Url,
+    pub page: u32,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+pub struct SynctexInverseRequest;
+
+impl lsp_types::request::Request for SynctexInverseRequest {
+    type Params = SynctexInverseParams;
+
+    type Result = Option<Location>;
+
+    const METHOD: &'static str = "texlab/synctexInverse";
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SynctexInverseParams {
+    pub uri: Url,
+    pub page: u32,
+    pub x: f64,
+    pub y: f64,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EnvironmentLocation {
@@ -18,6 +18,7 @@ pub struct Options {
     pub forward_search: ForwardSearchOptions,
     pub completion: CompletionOptions,
     pub inlay_hints: InlayHintOptions,
+    pub citation: CitationOptions,
     pub experimental: ExperimentalOptions,
 }
 
@@ -104,6 +105,15 @@ pub struct InlayHintOptions {
     pub label_references: Option<bool>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct CitationOptions {
+    pub style: Option<String>,
+    pub locale: Option<String>,
+    pub locales_directory: Option<String>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(default)]
@@ -1,8 +1,9 @@
 use lsp_types::{
     notification::Progress, request::WorkDoneProgressCreate, NumberOrString, ProgressParams,
     ProgressParamsValue, Url, WorkDoneProgress, WorkDoneProgressBegin,
-    WorkDoneProgressCreateParams, WorkDoneProgressEnd,
+    WorkDoneProgressCreateParams, WorkDoneProgressEnd, WorkDoneProgressReport,
 };
+use parser::BuildProgress;
 
 use crate::LspClient;
 
@@ -35,10 +36,37 @@ impl ProgressReporter {
                 title: "Building".into(),
                 message: Some(String::from(uri.as_str())),
                 cancellable: Some(false),
-                percentage: None,
+                percentage: Some(0),
             })),
         });
 
         Self { client, token }
     }
+
+    /// Reports the current pass and page of the TeX engine.
+    ///
+    /// The percentage is estimated from the number of pages of the previous pass.
+    pub fn report(&self, progress: BuildProgress) {
+        let mut message = format!("Pass {}", progress.pass);
+        if let Some(page) = progress.page {
+            message.push_str(&format!(", page {page}"));
+        }
+
+        let percentage = progress
+            .page
+            .zip(progress.total_pages)
+            .filter(|(_, total)| *total > 0)
+            .map(|(page, total)| (page * 100 / total).min(100));
+
+        let _ = self.client.send_notification::<Progress>(ProgressParams {
+            token: NumberOrString::Number(self.token),
+            value: ProgressParamsValue::WorkDone(WorkDoneProgress::Report(
+                WorkDoneProgressReport {
+                    cancellable: Some(false),
+                    message: Some(message),
+                    percentage,
+                },
+            )),
+        });
+    }
 }
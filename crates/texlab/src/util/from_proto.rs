@@ -1,14 +1,19 @@
 use std::time::Duration;
 
 use base_db::{Config, FeatureParams, Formatter, SynctexConfig, Workspace};
+use code_actions::CodeActionParams;
 use completion::CompletionParams;
 use definition::DefinitionParams;
+use diagnostics::{BibError, Diagnostic, TexError};
 use highlights::HighlightParams;
 use hover::HoverParams;
 use inlay_hints::InlayHintParams;
+use line_index::LineIndex;
 use references::ReferenceParams;
 use rename::RenameParams;
-use rowan::TextSize;
+use rowan::{TextRange, TextSize};
+use semantic_tokens::SemanticTokenParams;
+use signature_help::SignatureHelpParams;
 
 use crate::{
     features::completion::ResolveInfo,
@@ -131,14 +136,115 @@ pub fn rename_params(
     Some(RenameParams { feature, offset })
 }
 
-pub fn hover_params(workspace: &Workspace, params: lsp_types::HoverParams) -> Option<HoverParams> {
+pub fn hover_params<'a>(
+    workspace: &'a Workspace,
+    params: lsp_types::HoverParams,
+    client_flags: &ClientFlags,
+) -> Option<HoverParams<'a>> {
     let (feature, offset) = feature_params_offset(
         workspace,
         params.text_document_position_params.text_document,
         params.text_document_position_params.position,
     )?;
 
-    Some(HoverParams { feature, offset })
+    let citation_format = citation_format(client_flags.hover_markdown);
+    Some(HoverParams {
+        feature,
+        offset,
+        citation_format,
+    })
+}
+
+/// Renders citations as Markdown if the client can display it.
+pub fn citation_format(markdown: bool) -> citeproc::Format {
+    if markdown {
+        citeproc::Format::Markdown
+    } else {
+        citeproc::Format::PlainText
+    }
+}
+
+pub fn signature_help_params(
+    workspace: &Workspace,
+    params: lsp_types::SignatureHelpParams,
+) -> Option<SignatureHelpParams<'_>> {
+    let (feature, offset) = feature_params_offset(
+        workspace,
+        params.text_document_position_params.text_document,
+        params.text_document_position_params.position,
+    )?;
+
+    Some(SignatureHelpParams { feature, offset })
+}
+
+pub fn code_action_params(
+    workspace: &Workspace,
+    params: lsp_types::CodeActionParams,
+) -> Option<CodeActionParams<'_>> {
+    let feature = feature_params(workspace, params.text_document)?;
+    let line_index = &feature.document.line_index;
+    let range = line_index.offset_lsp_range(params.range)?;
+    let diagnostics = params
+        .context
+        .diagnostics
+        .iter()
+        .filter_map(|diagnostic| fixable_diagnostic(line_index, diagnostic))
+        .collect();
+
+    Some(CodeActionParams {
+        feature,
+        range,
+        diagnostics,
+    })
+}
+
+/// Restores the diagnostics with quick fixes from the diagnostics that the client sends along
+/// with a code action request using the codes assigned by `to_proto::diagnostic`.
+fn fixable_diagnostic(
+    line_index: &LineIndex,
+    diagnostic: &lsp_types::Diagnostic,
+) -> Option<Diagnostic> {
+    if diagnostic.source.as_deref() != Some("texlab") {
+        return None;
+    }
+
+    let range = line_index.offset_lsp_range(diagnostic.range)?;
+    match diagnostic.code.as_ref()? {
+        lsp_types::NumberOrString::Number(2) => {
+            Some(Diagnostic::Tex(range, TexError::ExpectingRCurly))
+        }
+        lsp_types::NumberOrString::Number(3) => {
+            Some(Diagnostic::Tex(range, TexError::MismatchedEnvironment))
+        }
+        lsp_types::NumberOrString::Number(6) => {
+            Some(Diagnostic::Bib(range, BibError::ExpectingRCurly))
+        }
+        lsp_types::NumberOrString::Number(10) => {
+            Some(Diagnostic::Tex(range, TexError::UndefinedLabel))
+        }
+        lsp_types::NumberOrString::Number(12) => {
+            Some(Diagnostic::Bib(range, BibError::UnusedEntry))
+        }
+        _ => None,
+    }
+}
+
+pub fn semantic_tokens_full_params(
+    workspace: &Workspace,
+    params: lsp_types::SemanticTokensParams,
+) -> Option<SemanticTokenParams<'_>> {
+    let feature = feature_params(workspace, params.text_document)?;
+    let range = TextRange::up_to(TextSize::of(feature.document.text.as_str()));
+    Some(SemanticTokenParams { feature, range })
+}
+
+pub fn semantic_tokens_range_params(
+    workspace: &Workspace,
+    params: lsp_types::SemanticTokensRangeParams,
+) -> Option<SemanticTokenParams<'_>> {
+    let feature = feature_params(workspace, params.text_document)?;
+    let range = feature.document.line_index.offset_lsp_range(params.range)?;
+    Some(SemanticTokenParams { feature, range })
 }
 
 pub fn inlay_hint_params(
@@ -324,6 +430,10 @@ pub fn config(value: Options) -> Config {
     config.inlay_hints.label_definitions = value.inlay_hints.label_definitions.unwrap_or(true);
     config.inlay_hints.label_references = value.inlay_hints.label_references.unwrap_or(true);
 
+    config.citation.style = value.citation.style;
+    config.citation.locale = value.citation.locale;
+    config.citation.locales_dir = value.citation.locales_directory;
+
     config.completion.matcher = match value.completion.matcher {
         CompletionMatcher::Fuzzy => base_db::MatchingAlgo::Skim,
         CompletionMatcher::FuzzyIgnoreCase => base_db::MatchingAlgo::SkimIgnoreCase,
@@ -363,3 +473,52 @@ pub fn config(value: Options) -> Config {
 
     config
 }
+
+#[cfg(test)]
+mod tests {
+    use base_db::{Owner, Workspace};
+    use diagnostics::{BibError, Diagnostic, TexError};
+    use distro::Language;
+    use line_index::LineCol;
+    use rowan::{TextRange, TextSize};
+
+    use crate::util::to_proto;
+
+    use super::fixable_diagnostic;
+
+    #[test]
+    fn test_fixable_diagnostic_roundtrip() {
+        let uri = lsp_types::Url::parse("file:///texlab/main.tex").unwrap();
+        let mut workspace = Workspace::default();
+        workspace.open(
+            uri.clone(),
+            String::from("foo\nbar"),
+            Language::Tex,
+            Owner::Client,
+            LineCol { line: 0, col: 0 },
+        );
+
+        let document = workspace.lookup(&uri).unwrap();
+        let range = TextRange::new(TextSize::from(1), TextSize::from(6));
+        let diagnostics = [
+            Diagnostic::Tex(range, TexError::ExpectingRCurly),
+            Diagnostic::Tex(range, TexError::MismatchedEnvironment),
+            Diagnostic::Tex(range, TexError::UndefinedLabel),
+            Diagnostic::Bib(range, BibError::ExpectingRCurly),
+            Diagnostic::Bib(range, BibError::UnusedEntry),
+        ];
+
+        for diagnostic in diagnostics {
+            let lsp_diagnostic = to_proto::diagnostic(&workspace, document, &diagnostic).unwrap();
+            let actual = fixable_diagnostic(&document.line_index, &lsp_diagnostic);
+            assert_eq!(actual, Some(diagnostic));
+        }
+
+        let diagnostic = Diagnostic::Tex(range, TexError::UnusedLabel);
+        let lsp_diagnostic = to_proto::diagnostic(&workspace, document, &diagnostic).unwrap();
+        assert_eq!(
+            fixable_diagnostic(&document.line_index, &lsp_diagnostic),
+            None
+        );
+    }
+}
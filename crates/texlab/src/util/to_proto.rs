@@ -1,18 +1,21 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ffi::OsStr, path::Path};
 
 use base_db::{
     data::BibtexEntryTypeCategory, util::RenderedObject, Document, DocumentLocation, Workspace,
 };
-use definition::DefinitionResult;
+use code_actions::CodeAction;
+use definition::{DefinitionResult, DefinitionTarget};
 use diagnostics::{BibError, ChktexSeverity, Diagnostic, TexError};
 use folding::{FoldingRange, FoldingRangeKind};
 use highlights::{Highlight, HighlightKind};
 use hover::{Hover, HoverData};
 use inlay_hints::{InlayHint, InlayHintData};
-use line_index::LineIndex;
+use line_index::{LineCol, LineIndex};
 use lsp_types::NumberOrString;
 use rename::RenameResult;
-use rowan::TextRange;
+use rowan::{TextRange, TextSize};
+use semantic_tokens::{SemanticToken, TokenKind};
+use signature_help::SignatureHelp;
 use syntax::BuildErrorLevel;
 
 use super::{line_index_ext::LineIndexExt, ClientFlags};
@@ -23,9 +26,10 @@ pub fn diagnostic(
     diagnostic: &Diagnostic,
 ) -> Option<lsp_types::Diagnostic> {
     let range = match diagnostic {
-        Diagnostic::Tex(range, _) | Diagnostic::Bib(range, _) | Diagnostic::Build(range, _) => {
-            document.line_index.line_col_lsp_range(*range)?
-        }
+        Diagnostic::Tex(range, _)
+        | Diagnostic::Bib(range, _)
+        | Diagnostic::Build(range, _)
+        | Diagnostic::Blg(range, _) => document.line_index.line_col_lsp_range(*range)?,
         Diagnostic::Chktex(range) => {
             let start = lsp_types::Position::new(range.start.line, range.start.col);
             let end = lsp_types::Position::new(range.end.line, range.end.col);
@@ -41,6 +45,10 @@ pub fn diagnostic(
             TexError::UnusedLabel => lsp_types::DiagnosticSeverity::HINT,
             TexError::UndefinedLabel => lsp_types::DiagnosticSeverity::ERROR,
             TexError::UndefinedCitation => lsp_types::DiagnosticSeverity::ERROR,
+            TexError::UndefinedGlossaryEntry => lsp_types::DiagnosticSeverity::WARNING,
+            TexError::UnusedAcronym => lsp_types::DiagnosticSeverity::HINT,
+            TexError::UnresolvedLink => lsp_types::DiagnosticSeverity::ERROR,
+            TexError::MissingGraphics => lsp_types::DiagnosticSeverity::ERROR,
             TexError::DuplicateLabel(_) => lsp_types::DiagnosticSeverity::ERROR,
         },
         Diagnostic::Bib(_, error) => match error {
@@ -51,11 +59,22 @@ pub fn diagnostic(
             BibError::ExpectingFieldValue => lsp_types::DiagnosticSeverity::ERROR,
             BibError::UnusedEntry => lsp_types::DiagnosticSeverity::HINT,
             BibError::DuplicateEntry(_) => lsp_types::DiagnosticSeverity::ERROR,
+            BibError::MissingRequiredField(_) => lsp_types::DiagnosticSeverity::WARNING,
+            BibError::DuplicateField(_) => lsp_types::DiagnosticSeverity::ERROR,
+            BibError::UnknownField => lsp_types::DiagnosticSeverity::INFORMATION,
+            BibError::InvalidDate => lsp_types::DiagnosticSeverity::WARNING,
+            BibError::InvalidNameList => lsp_types::DiagnosticSeverity::WARNING,
+            BibError::InvalidNumber => lsp_types::DiagnosticSeverity::WARNING,
+            BibError::UndefinedCrossref => lsp_types::DiagnosticSeverity::ERROR,
         },
         Diagnostic::Build(_, error) => match error.level {
             BuildErrorLevel::Error => lsp_types::DiagnosticSeverity::ERROR,
             BuildErrorLevel::Warning => lsp_types::DiagnosticSeverity::WARNING,
         },
+        Diagnostic::Blg(_, error) => match error.level {
+            BuildErrorLevel::Error => lsp_types::DiagnosticSeverity::ERROR,
+            BuildErrorLevel::Warning => lsp_types::DiagnosticSeverity::WARNING,
+        },
         Diagnostic::Chktex(error) => match error.severity {
             ChktexSeverity::Message => lsp_types::DiagnosticSeverity::HINT,
             ChktexSeverity::Warning => lsp_types::DiagnosticSeverity::WARNING,
@@ -71,6 +90,10 @@ pub fn diagnostic(
             TexError::UnusedLabel => Some(NumberOrString::Number(9)),
             TexError::UndefinedLabel => Some(NumberOrString::Number(10)),
             TexError::UndefinedCitation => Some(NumberOrString::Number(11)),
+            TexError::UndefinedGlossaryEntry => Some(NumberOrString::Number(15)),
+            TexError::UnusedAcronym => Some(NumberOrString::Number(16)),
+            TexError::UnresolvedLink => Some(NumberOrString::Number(17)),
+            TexError::MissingGraphics => Some(NumberOrString::Number(18)),
             TexError::DuplicateLabel(_) => Some(NumberOrString::Number(14)),
         },
         Diagnostic::Bib(_, error) => match error {
@@ -81,18 +104,26 @@ pub fn diagnostic(
             BibError::ExpectingFieldValue => Some(NumberOrString::Number(8)),
             BibError::UnusedEntry => Some(NumberOrString::Number(12)),
             BibError::DuplicateEntry(_) => Some(NumberOrString::Number(13)),
+            BibError::MissingRequiredField(_) => Some(NumberOrString::Number(19)),
+            BibError::DuplicateField(_) => Some(NumberOrString::Number(20)),
+            BibError::UnknownField => Some(NumberOrString::Number(21)),
+            BibError::InvalidDate => Some(NumberOrString::Number(22)),
+            BibError::InvalidNameList => Some(NumberOrString::Number(23)),
+            BibError::InvalidNumber => Some(NumberOrString::Number(24)),
+            BibError::UndefinedCrossref => Some(NumberOrString::Number(25)),
         },
-        Diagnostic::Build(_, _) => None,
+        Diagnostic::Build(_, _) | Diagnostic::Blg(_, _) => None,
         Diagnostic::Chktex(error) => Some(NumberOrString::String(error.code.clone())),
     };
 
     let source = match &diagnostic {
         Diagnostic::Tex(_, _) | Diagnostic::Bib(_, _) => "texlab",
         Diagnostic::Build(_, _) => "latex",
+        Diagnostic::Blg(_, _) => "bibtex",
         Diagnostic::Chktex(_) => "ChkTeX",
     };
 
-    let message = String::from(match &diagnostic {
+    let mut message = String::from(match &diagnostic {
         Diagnostic::Tex(_, error) => match error {
             TexError::UnexpectedRCurly => "Unexpected \"}\"",
             TexError::ExpectingRCurly => "Expecting a curly bracket: \"}\"",
@@ -100,6 +131,10 @@ pub fn diagnostic(
             TexError::UnusedLabel => "Unused label",
             TexError::UndefinedLabel => "Undefined reference",
             TexError::UndefinedCitation => "Undefined reference",
+            TexError::UndefinedGlossaryEntry => "Undefined glossary entry",
+            TexError::UnusedAcronym => "Unused acronym",
+            TexError::UnresolvedLink => "File not found",
+            TexError::MissingGraphics => "Image file not found",
             TexError::DuplicateLabel(_) => "Duplicate label",
         },
         Diagnostic::Bib(_, error) => match error {
@@ -110,11 +145,30 @@ pub fn diagnostic(
             BibError::ExpectingFieldValue => "Expecting a field value",
             BibError::UnusedEntry => "Unused entry",
             BibError::DuplicateEntry(_) => "Duplicate entry key",
+            BibError::MissingRequiredField(_) => "Missing required field",
+            BibError::DuplicateField(_) => "Duplicate field",
+            BibError::UnknownField => "Unknown field",
+            BibError::InvalidDate => "Invalid date",
+            BibError::InvalidNameList => "Invalid name list",
+            BibError::InvalidNumber => "Invalid number",
+            BibError::UndefinedCrossref => "Undefined cross-referenced entry",
         },
         Diagnostic::Build(_, error) => &error.message,
+        Diagnostic::Blg(_, error) => &error.message,
         Diagnostic::Chktex(error) => &error.message,
     });
 
+    if let Diagnostic::Bib(_, BibError::MissingRequiredField(fields)) = &diagnostic {
+        message.push_str(&format!(": `{fields}`"));
+    }
+
+    if let Diagnostic::Build(_, error) = &diagnostic {
+        if let Some(help) = &error.help {
+            message.push_str("\n\n");
+            message.push_str(help);
+        }
+    }
+
     let tags = match &diagnostic {
         Diagnostic::Tex(_, error) => match error {
             TexError::UnexpectedRCurly => None,
@@ -123,6 +177,10 @@ pub fn diagnostic(
             TexError::UnusedLabel => Some(vec![lsp_types::DiagnosticTag::UNNECESSARY]),
             TexError::UndefinedLabel => None,
             TexError::UndefinedCitation => None,
+            TexError::UndefinedGlossaryEntry => None,
+            TexError::UnusedAcronym => Some(vec![lsp_types::DiagnosticTag::UNNECESSARY]),
+            TexError::UnresolvedLink => None,
+            TexError::MissingGraphics => None,
             TexError::DuplicateLabel(_) => None,
         },
         Diagnostic::Bib(_, error) => match error {
@@ -133,8 +191,15 @@ pub fn diagnostic(
             BibError::ExpectingFieldValue => None,
             BibError::UnusedEntry => Some(vec![lsp_types::DiagnosticTag::UNNECESSARY]),
             BibError::DuplicateEntry(_) => None,
+            BibError::MissingRequiredField(_) => None,
+            BibError::DuplicateField(_) => None,
+            BibError::UnknownField => None,
+            BibError::InvalidDate => None,
+            BibError::InvalidNameList => None,
+            BibError::InvalidNumber => None,
+            BibError::UndefinedCrossref => None,
         },
-        Diagnostic::Build(_, _) => None,
+        Diagnostic::Build(_, _) | Diagnostic::Blg(_, _) => None,
         Diagnostic::Chktex(_) => None,
     };
 
@@ -166,6 +231,10 @@ pub fn diagnostic(
             TexError::UnusedLabel => None,
             TexError::UndefinedLabel => None,
             TexError::UndefinedCitation => None,
+            TexError::UndefinedGlossaryEntry => None,
+            TexError::UnusedAcronym => None,
+            TexError::UnresolvedLink => None,
+            TexError::MissingGraphics => None,
             TexError::DuplicateLabel(others) => make_conflict_info(workspace, others, "label"),
         },
         Diagnostic::Bib(_, error) => match error {
@@ -176,8 +245,15 @@ pub fn diagnostic(
             BibError::ExpectingFieldValue => None,
             BibError::UnusedEntry => None,
             BibError::DuplicateEntry(others) => make_conflict_info(workspace, others, "entry"),
+            BibError::MissingRequiredField(_) => None,
+            BibError::DuplicateField(others) => make_conflict_info(workspace, others, "field"),
+            BibError::UnknownField => None,
+            BibError::InvalidDate => None,
+            BibError::InvalidNameList => None,
+            BibError::InvalidNumber => None,
+            BibError::UndefinedCrossref => None,
         },
-        Diagnostic::Build(_, _) => None,
+        Diagnostic::Build(_, _) | Diagnostic::Blg(_, _) => None,
         Diagnostic::Chktex(_) => None,
     };
 
@@ -240,13 +316,13 @@ pub fn inlay_hint(hint: InlayHint, line_index: &LineIndex) -> Option<lsp_types::
 }
 
 pub fn document_link(
-    link: DocumentLocation,
+    link: links::DocumentLink,
     line_index: &LineIndex,
 ) -> Option<lsp_types::DocumentLink> {
     Some(lsp_types::DocumentLink {
         data: None,
         tooltip: None,
-        target: Some(link.document.uri.clone()),
+        target: Some(link.target),
         range: line_index.line_col_lsp_range(link.range)?,
     })
 }
@@ -283,11 +359,20 @@ pub fn location_link(
 ) -> Option<lsp_types::LocationLink> {
     let origin_selection_range = line_index.line_col_lsp_range(result.origin_selection_range);
 
-    let target_line_index = &result.target.line_index;
-    let target_uri = result.target.uri.clone();
-    let target_range = target_line_index.line_col_lsp_range(result.target_range)?;
-    let target_selection_range =
-        target_line_index.line_col_lsp_range(result.target_selection_range)?;
+    let (target_uri, target_range, target_selection_range) = match result.target {
+        DefinitionTarget::Document(target) => (
+            target.uri.clone(),
+            target.line_index.line_col_lsp_range(result.target_range)?,
+            target
+                .line_index
+                .line_col_lsp_range(result.target_selection_range)?,
+        ),
+        DefinitionTarget::File(uri) => (
+            uri,
+            lsp_types::Range::default(),
+            lsp_types::Range::default(),
+        ),
+    };
 
     Some(lsp_types::LocationLink {
         origin_selection_range,
@@ -405,6 +490,107 @@ pub fn workspace_edit(result: RenameResult, new_name: &str) -> lsp_types::Worksp
     lsp_types::WorkspaceEdit::new(changes)
 }
 
+pub fn code_action(
+    workspace: &Workspace,
+    document: &Document,
+    action: CodeAction,
+) -> Option<lsp_types::CodeAction> {
+    let mut edits = Vec::new();
+    for edit in action.edits {
+        let start = document.line_index.line_col_lsp(edit.range.start())?;
+        let end = document.line_index.line_col_lsp(edit.range.end())?;
+        let range = lsp_types::Range::new(start, end);
+        edits.push(lsp_types::TextEdit::new(range, edit.text));
+    }
+
+    let mut changes = HashMap::default();
+    changes.insert(document.uri.clone(), edits);
+
+    Some(lsp_types::CodeAction {
+        title: action.title,
+        kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+        diagnostics: Some(vec![diagnostic(workspace, document, &action.diagnostic)?]),
+        edit: Some(lsp_types::WorkspaceEdit::new(changes)),
+        is_preferred: Some(action.is_preferred),
+        ..lsp_types::CodeAction::default()
+    })
+}
+
+pub fn semantic_tokens(
+    document: &Document,
+    tokens: Vec<SemanticToken>,
+) -> Option<lsp_types::SemanticTokens> {
+    let line_index = &document.line_index;
+    let mut data = Vec::new();
+    let mut previous = lsp_types::Position::default();
+    for token in tokens {
+        // Tokens are not allowed to span multiple lines so we have to split them.
+        let start = line_index.line_col(token.range.start());
+        let end = line_index.line_col(token.range.end());
+        for line in start.line..=end.line {
+            let line_start = line_index.offset(LineCol { line, col: 0 })?;
+            let line_end = line_index
+                .offset(LineCol {
+                    line: line + 1,
+                    col: 0,
+                })
+                .unwrap_or_else(|| TextSize::of(document.text.as_str()));
+
+            let line_text = document.text[usize::from(line_start)..usize::from(line_end)]
+                .trim_end_matches(['\r', '\n']);
+
+            let line_end = line_start + TextSize::of(line_text);
+            let range = TextRange::new(
+                token.range.start().max(line_start),
+                token.range.end().min(line_end),
+            );
+
+            if range.is_empty() {
+                continue;
+            }
+
+            let range = line_index.line_col_lsp_range(range)?;
+            let delta_line = range.start.line - previous.line;
+            let delta_start = if delta_line == 0 {
+                range.start.character - previous.character
+            } else {
+                range.start.character
+            };
+
+            data.push(lsp_types::SemanticToken {
+                delta_line,
+                delta_start,
+                length: range.end.character - range.start.character,
+                token_type: semantic_token_type(token.kind),
+                token_modifiers_bitset: token.modifiers.bits(),
+            });
+
+            previous = range.start;
+        }
+    }
+
+    Some(lsp_types::SemanticTokens {
+        result_id: None,
+        data,
+    })
+}
+
+/// Returns the index of the token type in the legend of `features::semantic_tokens`.
+fn semantic_token_type(kind: TokenKind) -> u32 {
+    match kind {
+        TokenKind::Command => 0,
+        TokenKind::EnvironmentName => 1,
+        TokenKind::Label => 2,
+        TokenKind::Citation => 3,
+        TokenKind::Math => 4,
+        TokenKind::Comment => 5,
+        TokenKind::Verbatim => 6,
+        TokenKind::EntryType => 7,
+        TokenKind::FieldName => 8,
+        TokenKind::StringReference => 9,
+    }
+}
+
 pub fn location(location: DocumentLocation) -> Option<lsp_types::Location> {
     let document = location.document;
     let range = document.line_index.line_col_lsp_range(location.range)?;
@@ -424,10 +610,14 @@ pub fn document_highlight(
     Some(lsp_types::DocumentHighlight { range, kind })
 }
 
-pub fn hover(hover: Hover, line_index: &LineIndex) -> Option<lsp_types::Hover> {
+pub fn hover(
+    hover: Hover,
+    line_index: &LineIndex,
+    client_flags: &ClientFlags,
+) -> Option<lsp_types::Hover> {
     let contents = match hover.data {
         HoverData::Citation(text) => lsp_types::MarkupContent {
-            kind: lsp_types::MarkupKind::Markdown,
+            kind: markup_kind(client_flags.hover_markdown),
             value: text,
         },
         HoverData::Package(description) => lsp_types::MarkupContent {
@@ -435,21 +625,93 @@ pub fn hover(hover: Hover, line_index: &LineIndex) -> Option<lsp_types::Hover> {
             value: description.into(),
         },
         HoverData::EntryType(type_) => lsp_types::MarkupContent {
-            kind: lsp_types::MarkupKind::Markdown,
+            kind: markup_kind(client_flags.hover_markdown),
             value: type_.documentation?.into(),
         },
         HoverData::FieldType(type_) => lsp_types::MarkupContent {
-            kind: lsp_types::MarkupKind::Markdown,
+            kind: markup_kind(client_flags.hover_markdown),
             value: type_.documentation.into(),
         },
         HoverData::Label(label) => lsp_types::MarkupContent {
             kind: lsp_types::MarkupKind::PlainText,
             value: label.reference(),
         },
+        HoverData::GlossaryEntry(entry) => lsp_types::MarkupContent {
+            kind: markup_kind(client_flags.hover_markdown),
+            value: match (&entry.title, &entry.description) {
+                (Some(title), Some(description)) if client_flags.hover_markdown => {
+                    format!("**{title}**: {description}")
+                }
+                (Some(title), Some(description)) => format!("{title}: {description}"),
+                (Some(text), None) | (None, Some(text)) => text.clone(),
+                (None, None) => return None,
+            },
+        },
         HoverData::StringRef(text) => lsp_types::MarkupContent {
             kind: lsp_types::MarkupKind::PlainText,
             value: text,
         },
+        HoverData::Definition(text) if client_flags.hover_markdown => lsp_types::MarkupContent {
+            kind: lsp_types::MarkupKind::Markdown,
+            value: format!("```latex\n{text}\n```"),
+        },
+        HoverData::Definition(text) => lsp_types::MarkupContent {
+            kind: lsp_types::MarkupKind::PlainText,
+            value: text,
+        },
+        HoverData::Command {
+            file_names,
+            command,
+        } => {
+            let mut sections = Vec::new();
+            let mut signature = format!("\\{}", command.name);
+            for i in 1..=command.parameters.len() {
+                signature.push_str(&format!("{{#{i}}}"));
+            }
+
+            let mut signature = format_code(signature, client_flags.hover_markdown);
+            if let Some(glyph) = &command.glyph {
+                signature.push_str(&format!(" {glyph}"));
+            }
+
+            sections.push(signature);
+            if let Some(image) = command.image.filter(|_| client_flags.hover_markdown) {
+                sections.push(format!(
+                    "![{}](data:image/png;base64,{image}|width=48,height=48)",
+                    command.name
+                ));
+            }
+
+            sections.push(format!("Package: {}", format_package_files(file_names)));
+            for (i, parameter) in command.parameters.iter().enumerate() {
+                let choices: Vec<_> = parameter.0.iter().map(|arg| arg.name).collect();
+                if !choices.is_empty() {
+                    let parameter = format_code(format!("#{}", i + 1), client_flags.hover_markdown);
+                    sections.push(format!("{parameter}: {}", choices.join(", ")));
+                }
+            }
+
+            lsp_types::MarkupContent {
+                kind: markup_kind(client_flags.hover_markdown),
+                value: sections.join("\n\n"),
+            }
+        }
+        HoverData::Environment { file_names, name } => lsp_types::MarkupContent {
+            kind: markup_kind(client_flags.hover_markdown),
+            value: format!(
+                "{}\n\nPackage: {}",
+                format_code(format!("\\begin{{{name}}}"), client_flags.hover_markdown),
+                format_package_files(file_names)
+            ),
+        },
+        HoverData::Graphics(path) if client_flags.hover_markdown => lsp_types::MarkupContent {
+            kind: lsp_types::MarkupKind::Markdown,
+            value: format_image_preview(&path),
+        },
+        HoverData::Graphics(path) => lsp_types::MarkupContent {
+            kind: lsp_types::MarkupKind::PlainText,
+            value: path.display().to_string(),
+        },
     };
 
     Some(lsp_types::Hover {
@@ -457,3 +719,183 @@ pub fn hover(hover: Hover, line_index: &LineIndex) -> Option<lsp_types::Hover> {
         range: line_index.line_col_lsp_range(hover.range),
     })
 }
+
+/// The markup kind of a hover or of a citation that was rendered with
+/// [`super::from_proto::citation_format`].
+pub fn markup_kind(markdown: bool) -> lsp_types::MarkupKind {
+    if markdown {
+        lsp_types::MarkupKind::Markdown
+    } else {
+        lsp_types::MarkupKind::PlainText
+    }
+}
+
+/// Formats inline code unless the client only supports plain text.
+fn format_code(text: String, markdown: bool) -> String {
+    if markdown {
+        format!("`{text}`")
+    } else {
+        text
+    }
+}
+
+pub fn format_package_files(file_names: &[&str]) -> String {
+    if file_names.is_empty() {
+        "built-in".into()
+    } else {
+        file_names.join(", ")
+    }
+}
+
+/// Images up to this size are embedded into the hover.
+const MAX_IMAGE_PREVIEW_SIZE: u64 = 1024 * 1024;
+
+fn format_image_preview(path: &Path) -> String {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let mime_type = match path
+        .extension()
+        .and_then(OsStr::to_str)
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        Some("png") => Some("image/png"),
+        Some("jpg" | "jpeg") => Some("image/jpeg"),
+        Some("svg") => Some("image/svg+xml"),
+        Some("bmp") => Some("image/bmp"),
+        _ => None,
+    };
+
+    let preview = mime_type
+        .filter(|_| std::fs::metadata(path).is_ok_and(|meta| meta.len() <= MAX_IMAGE_PREVIEW_SIZE))
+        .and_then(|mime_type| {
+            let data = std::fs::read(path).ok()?;
+            Some(format!(
+                "![{name}](data:{mime_type};base64,{})",
+                encode_base64(&data)
+            ))
+        });
+
+    let path = path.display();
+    match preview {
+        Some(preview) => format!("{preview}\n\n`{path}`"),
+        None => format!("`{path}`"),
+    }
+}
+
+fn encode_base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut output = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (n >> (18 - 6 * i)) & 0x3F;
+                output.push(ALPHABET[index as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+
+    output
+}
+
+pub fn signature_help(help: SignatureHelp) -> lsp_types::SignatureHelp {
+    let parameters = help
+        .signature
+        .parameters
+        .into_iter()
+        .map(|parameter| lsp_types::ParameterInformation {
+            label: lsp_types::ParameterLabel::Simple(parameter.label),
+            documentation: parameter
+                .documentation
+                .map(lsp_types::Documentation::String),
+        })
+        .collect();
+
+    let signature = lsp_types::SignatureInformation {
+        label: help.signature.label,
+        documentation: None,
+        parameters: Some(parameters),
+        active_parameter: None,
+    };
+
+    lsp_types::SignatureHelp {
+        signatures: vec![signature],
+        active_signature: Some(0),
+        active_parameter: Some(help.active_parameter as u32),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base_db::semantics::{
+        tex::{GlossaryEntry, GlossaryEntryKind},
+        Span,
+    };
+    use hover::{Hover, HoverData};
+    use line_index::LineIndex;
+
+    use crate::util::from_proto;
+
+    use super::{encode_base64, hover};
+
+    #[test]
+    fn test_encode_base64() {
+        assert_eq!(encode_base64(b""), "");
+        assert_eq!(encode_base64(b"f"), "Zg==");
+        assert_eq!(encode_base64(b"fo"), "Zm8=");
+        assert_eq!(encode_base64(b"foo"), "Zm9v");
+        assert_eq!(encode_base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    fn check_plain_text_hover(data: HoverData, expected: &str) {
+        let client_flags = from_proto::client_flags(lsp_types::ClientCapabilities::default(), None);
+        let line_index = LineIndex::new("");
+        let range = rowan::TextRange::default();
+        let hover = hover(Hover { range, data }, &line_index, &client_flags).unwrap();
+        assert_eq!(
+            hover.contents,
+            lsp_types::HoverContents::Markup(lsp_types::MarkupContent {
+                kind: lsp_types::MarkupKind::PlainText,
+                value: expected.into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_hover_environment_plain_text() {
+        let data = HoverData::Environment {
+            file_names: &["amsmath.sty"],
+            name: "align",
+        };
+
+        check_plain_text_hover(data, "\\begin{align}\n\nPackage: amsmath.sty");
+    }
+
+    #[test]
+    fn test_hover_glossary_entry_plain_text() {
+        let entry = GlossaryEntry {
+            kind: GlossaryEntryKind::Definition,
+            name: Span::new("foo".into(), rowan::TextRange::default()),
+            title: Some("Foo".into()),
+            description: Some("A foo".into()),
+            full_range: rowan::TextRange::default(),
+        };
+
+        check_plain_text_hover(HoverData::GlossaryEntry(&entry), "Foo: A foo");
+    }
+
+    #[test]
+    fn test_hover_definition_plain_text() {
+        let data = HoverData::Definition("\\newcommand{\\foo}{Foo}".into());
+        check_plain_text_hover(data, "\\newcommand{\\foo}{Foo}");
+    }
+}